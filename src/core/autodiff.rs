@@ -4,7 +4,9 @@ use rand::rng;
 use rand_distr::{Normal, Distribution};
 
 pub use crate::graph::data::concat::concat;
-pub use crate::graph::ops::dot_product::dot;
+pub use crate::graph::ops::dot_product::{dot, matmul};
+pub use crate::graph::ops::pool::PoolOp;
+pub use crate::graph::ops::random::Generator;
 pub use crate::devices;
 use crate::{core::add_to_dep, ir::optimize::*, ir_b_device_callback};
 
@@ -19,9 +21,10 @@ pub use crate::{
 };
 
 pub use super::control::*;
+pub use super::gradcheck::gradcheck;
 
-use super::{ir_b_add, ir_b_execute, is_harsh, set_harsh_dep_list, ConstantNode, IRBase, DEP_TRACKER, IRB};
-use crate::IRCmds::{Heading, EX};
+use super::{ir_b_add, ir_b_add_placeholder, ir_b_compile, ir_b_execute, ir_b_exit, ir_b_feed, ir_b_placeholder_dim, is_harsh, set_harsh_dep_list, CompiledProgram, ConstantNode, IRBase, DEP_TRACKER, HARSH_DEP_LIST, IRB};
+use crate::IRCmds::Heading;
 // new tensor
 pub fn tensor (data: Vec<f32>, dim: Vec<usize>) -> Tensor {
     if data.len() != dim.iter().product::<usize>() {
@@ -31,7 +34,7 @@ pub fn tensor (data: Vec<f32>, dim: Vec<usize>) -> Tensor {
 
     // add to dependency tracker
     if !is_harsh() {
-        add_to_dep(v.id.clone());
+        add_to_dep(v.id);
     }
 
     Tensor::new(TensorNode {
@@ -72,6 +75,36 @@ pub fn fill (val: f32, dim: Vec<usize>) -> Tensor {
     )    
 }
 
+/*
+ Input buffer whose contents are set with `feed` before each `execute()`
+ * The IR doesn't have to be rebuilt between executions (ex: new batch from the dataset)
+ * Contents are zero until fed
+ */
+pub fn placeholder (dim: Vec<usize>) -> Tensor {
+    let v = Value::zeros(dim.clone());
+    ir_b_add_placeholder(v.id, dim);
+    v.to_node()
+}
+
+pub fn feed (t: &Tensor, data: Vec<f32>) {
+    let v = t.val().expect("Can only feed placeholders");
+    let dim = ir_b_placeholder_dim(v.id).expect("Can only feed placeholders");
+    assert_eq!(data.len(), dim.iter().product::<usize>(), "# of values doesn't match placeholder size when feeding");
+
+    ir_b_feed(v.id, data);
+}
+
+/*
+ Random tensors, generated on the device from the global generator (see Generator)
+ * drawn where they're called, so inside an ir_for they're redrawn every iteration
+ * they're also redrawn every run (execute); parameters should use `randn`
+ * use `generator(seed)` for a reproducible sequence
+ */
+pub fn rand (dim: Vec<usize>) -> Tensor {
+    Generator::global().rand(dim)
+}
+
+// drawn on the host, so it's carried over to the next run like any tensor (ex: weights); see Generator::randn for a device draw
 pub fn randn (dim: Vec<usize>) -> Tensor {
     let mut rng = rng();
     let normal = Normal::new(0.0, 1.0).unwrap(); // Mean = 0, Std = 1
@@ -79,6 +112,18 @@ pub fn randn (dim: Vec<usize>) -> Tensor {
     tensor(samples, dim)
 }
 
+pub fn bernoulli (p: f32, dim: Vec<usize>) -> Tensor {
+    Generator::global().bernoulli(p, dim)
+}
+
+pub fn randint (low: i32, high: i32, dim: Vec<usize>) -> Tensor {
+    Generator::global().randint(low, high, dim)
+}
+
+pub fn generator (seed: u32) -> Generator {
+    Generator::new(seed)
+}
+
 pub fn set_device <T: Device + Send + Sync + 'static> (device: T) {
     // set base
     let mut guard = IRB.lock().expect("Can't lock IR builder");
@@ -94,6 +139,11 @@ pub fn set_device <T: Device + Send + Sync + 'static> (device: T) {
     let mut guard = DEP_TRACKER.lock().expect("Can't lock DEP tracker");
     *guard = Some(HashSet::new());
     drop(guard);
+
+    // harsh dep list is opt-in per program
+    let mut guard = HARSH_DEP_LIST.lock().expect("Can't lock harsh dep list");
+    *guard = false;
+    drop(guard);
 }
 
 /**
@@ -106,15 +156,24 @@ pub fn add_heading (cmt: &str) {
 }
 
 pub fn execute () {
-    ir_b_add(EX); // add exit
+    ir_b_exit(); // add exit
     ir_b_device_callback();
     ir_optimize();
 
     ir_b_execute(false);    // execute
 }
 
+// same as `execute()`, but the program can be ran many times without recompiling
+pub fn compile () -> CompiledProgram {
+    ir_b_exit(); // add exit
+    ir_b_device_callback();
+    ir_optimize();
+
+    ir_b_compile(false)
+}
+
 pub fn print_and_exec () {
-    ir_b_add(EX); // add exit
+    ir_b_exit(); // add exit
     ir_b_device_callback();
     ir_optimize();
 
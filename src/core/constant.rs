@@ -49,7 +49,7 @@ impl NodeTrait for ConstantNode {
 
 fn c_constant (contents: f32, dim: &Vec<usize>) -> Value {
     let id = ir_b_id();
-    ir_b_add(IRCmds::CreateConstant { contents, id: id.clone(), dim: dim.clone() });
+    ir_b_add(IRCmds::CreateConstant { contents, id: id, dim: dim.clone() });
 
     return Value {
         dim: dim.clone(),
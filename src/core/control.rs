@@ -1,4 +1,4 @@
-use crate::{core::add_to_dep, ir_b_create_temp_proc, ir_b_return_temp_proc, IRCmds, Tensor};
+use crate::{core::add_to_dep, ir_b_create_temp_proc, ir_b_return_temp_proc, IRCmds, Tensor, Value};
 use std::ops::Range;
 
 use super::{autodiff, ir_b_add};
@@ -18,7 +18,7 @@ where
     
     // add if evaluations to main 
     let v = expr().forward();
-    add_to_dep(v.id.clone());    
+    add_to_dep(v.id);    
     if v.dim != vec![1] { panic!("If expression must have dim of [1]") }
     
     // rest of the code will then execute
@@ -46,7 +46,7 @@ where
 {
     // add if evaluations to main 
     let v = expr().forward();
-    add_to_dep(v.id.clone());     // add to dependency list
+    add_to_dep(v.id);     // add to dependency list
     if v.dim != vec![1] { panic!("Expr must return a boolean") }
     
     // func
@@ -78,7 +78,7 @@ where
 {
     // first, evaluate control expr
     let v = var.forward(); // first, evaluate control expr
-    add_to_dep(v.id.clone());
+    add_to_dep(v.id);
     if v.dim != vec![1] { panic!("Expr must return a boolean.") } 
     
     // create func
@@ -99,7 +99,8 @@ where
     F: FnOnce(&Tensor)
 {
     // internally, it's just a while loop
-    let mut x = autodiff::scalar(r.start as f32);
+    // the counter isn't kept; otherwise, it would be persisted and the loop wouldn't rerun on the next execution
+    let mut x = Value::val(r.start as f32).to_node_with_grad();
     let x_end = autodiff::constant(r.end as f32, vec![1]);
 
     let result = x.less_than(&x_end);
@@ -1,22 +1,23 @@
 // contains the dependency tracker
 use std::{collections::HashSet, sync::Mutex};
+use crate::VarId;
 
-pub static DEP_TRACKER: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+pub static DEP_TRACKER: Mutex<Option<HashSet<VarId>>> = Mutex::new(None);
 pub static HARSH_DEP_LIST: Mutex<bool> = Mutex::new(false);
 
-pub fn add_to_dep (id: String) {
+pub fn add_to_dep (id: VarId) {
     let mut guard = DEP_TRACKER.lock().unwrap();
     let dp = guard.as_mut().expect("Can't unpack dep tracker");
     dp.insert(id);
 }
 
-pub fn is_in_dep (id: String) -> bool {
+pub fn is_in_dep (id: VarId) -> bool {
     let mut guard = DEP_TRACKER.lock().unwrap();
     let dp = guard.as_mut().expect("Can't unpack dep tracker");
     dp.contains(&id)
 }
 
-pub fn ret_dep_list () -> HashSet<String> {
+pub fn ret_dep_list () -> HashSet<VarId> {
     let mut guard = DEP_TRACKER.lock().unwrap();
     let dp = guard.as_mut().expect("Can't unpack dep tracker");
     dp.clone()
@@ -5,4 +5,10 @@ pub mod env_flags {
         if let Ok(val) = std::env::var("IROPT") { if val == "0" { return true } }
         false 
     }
+
+    // skips the memory + fusion optimizations when lowering to kernels (to_kernel)
+    pub fn disable_kernel_opt () -> bool {
+        if let Ok(val) = std::env::var("KOPT") { if val == "0" { return true } }
+        false 
+    }
 }
\ No newline at end of file
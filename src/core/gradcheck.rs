@@ -0,0 +1,85 @@
+use crate::{Tensor, Value, ValueData};
+use super::autodiff;
+
+// a gradient of gradcheck that isn't within tol
+#[derive(Clone, Debug, PartialEq)]
+pub struct GradMismatch {
+    pub input: usize,   // index into `inputs`
+    pub index: usize,   // element of the input
+    pub analytical: f32,
+    pub numerical: f32
+}
+
+/**
+ * Numerical gradient checker
+ * Compares the gradients from `Tensor::backward` with central finite differences: (f(x + eps) - f(x - eps)) / (2 * eps)
+ * The output of f is summed into a scalar, so the gradient of every output element is 1.
+ *
+ * `inputs` are (data, dim) pairs. f is called once with the inputs declared as `autodiff::tensor` (for the backward pass),
+ * and twice per element of every input with that element perturbed.
+ * Everything is built into a single program and executed on the current device; so `set_device` must be called beforehand,
+ * and f must build the exact same graph on every call.
+ *
+ * Every input must be used within f (otherwise, the input has no gradient).
+ * Returns every gradient that isn't within tol (relative to the magnitude) as the error,
+ * so `gradcheck(...).expect("...")` shows them in the panic message.
+ */
+pub fn gradcheck<F> (f: F, inputs: Vec<(Vec<f32>, Vec<usize>)>, eps: f32, tol: f32) -> Result<(), Vec<GradMismatch>>
+where
+    F: Fn(&[Tensor]) -> Tensor
+{
+    let to_scalar = |t: Tensor| t.flatten().unsqueeze(0).sum(-1);
+
+    // ========== Analytical gradients ==========
+    let leaves: Vec<Tensor> = inputs.iter()
+        .map(|(data, dim)| autodiff::tensor(data.clone(), dim.clone()))
+        .collect();
+
+    let res = to_scalar(f(&leaves));
+    res.forward();
+    res.backward();
+    let grads: Vec<Value> = leaves.iter().map(|l| l.grad()).collect();
+
+    // ========== Perturbed evaluations ==========
+    // perturbed inputs aren't declared by the user, so they don't have to be kept
+    let eval = |i: usize, j: usize, delta: f32| -> Value {
+        let args: Vec<Tensor> = leaves.iter().enumerate().map(|(k, l)| {
+            if k != i { return l.clone(); }
+
+            let (data, dim) = &inputs[i];
+            let mut data = data.clone();
+            data[j] += delta;
+            Value::new(data, dim.clone()).to_node()
+        }).collect();
+
+        let v = to_scalar(f(&args)).forward();
+        v.keep();
+        v
+    };
+
+    let perturbed: Vec<Vec<(Value, Value)>> = inputs.iter().enumerate().map(|(i, (data, _))| {
+        (0..data.len()).map(|j| (eval(i, j, eps), eval(i, j, -eps))).collect()
+    }).collect();
+
+    autodiff::execute();
+
+    // ========== Compare ==========
+    let mut mismatches = vec![];
+    for (i, (grad, pert)) in grads.iter().zip(perturbed.iter()).enumerate() {
+        let grad: ValueData = grad.get();
+        assert!(!grad.is_none, "Gradient of input {} wasn't computed", i);
+        assert_eq!(grad.dim, inputs[i].1, "Gradient of input {} has the wrong shape", i);
+
+        for (j, (plus, minus)) in pert.iter().enumerate() {
+            let numerical = (plus.get().data[0] - minus.get().data[0]) / (2.0 * eps);
+            let analytical = grad.data[j];
+
+            let diff = (analytical - numerical).abs();
+            if diff.is_nan() || diff > tol * 1.0_f32.max(numerical.abs()).max(analytical.abs()) {
+                mismatches.push(GradMismatch { input: i, index: j, analytical, numerical });
+            }
+        }
+    }
+
+    if mismatches.is_empty() { Ok(()) } else { Err(mismatches) }
+}
@@ -1,5 +1,6 @@
 use std::string::String;
-use crate::{core::print::display_colored_side_by_side, kernel_decl::KernelProcedure, to_kernel::to_kernel, trackers::KernelTracker, ValueData};
+use crate::{core::{add_to_dep, VarId, next_program_id, print::display_colored_side_by_side, ret_dep_list, CompiledProgram}, ir::opts::track_var_changed, to_kernel::to_kernel, ValueData};
+use std::collections::HashMap;
 use std::sync::{Mutex, Arc};
 
 // All different IR needs to implement these functions
@@ -7,24 +8,24 @@ use std::sync::{Mutex, Arc};
 #[derive(Clone, PartialEq, Debug)]
 pub enum IRCmds {
     // Create
-    CreateMat {contents: Arc<Vec<f32>>, dim: Vec<usize>, id: String}, // COULD BE VERY LARGE IN SIZE; this is why we encapsulate it in Rc
-    CreateConstant {contents: f32, id: String, dim: Vec<usize>},
+    CreateMat {contents: Arc<Vec<f32>>, dim: Vec<usize>, id: VarId}, // COULD BE VERY LARGE IN SIZE; this is why we encapsulate it in Rc
+    CreateConstant {contents: f32, id: VarId, dim: Vec<usize>},
 
     // ELW Operations 
     // No subtraction as a - b = a + (-b)
     // No division as a/b = a * (1/b). (1/b) is using reciprocal.
     // most hardware accel use these optimizations under the hood.
-    ElwMultiply   {a: String, b: String, res: String}, 
-    ElwAdd        {a: String, b: String, res: String},
+    ElwMultiply   {a: VarId, b: VarId, res: VarId}, 
+    ElwAdd        {a: VarId, b: VarId, res: VarId},
 
     // Operation Equal operations; s -- self, o -- other
-    ElwMultiplyEq {s: String, o: String}, // *= 
-    ElwAddEq      {s: String, o: String}, // += 
+    ElwMultiplyEq {s: VarId, o: VarId}, // *= 
+    ElwAddEq      {s: VarId, o: VarId}, // += 
 
     // Element-wise Equality Operations. Expects tensor with either 0 (doesn't satisfy condition) and 1 (satisfy condition)
-    EqualZero {a: String, res: String}, // a == 0 --> res; 
-    MoreZero  {a: String, res: String}, // a > 0 --> res
-    LessZero  {a: String, res: String}, // a < 0 --> res
+    EqualZero {a: VarId, res: VarId}, // a == 0 --> res; 
+    MoreZero  {a: VarId, res: VarId}, // a > 0 --> res
+    LessZero  {a: VarId, res: VarId}, // a < 0 --> res
 
     /*
     Reduce Operations
@@ -45,10 +46,9 @@ pub enum IRCmds {
 
     We do this because it simplifies the reduce kernel for each device. We take advantage of the fact that data manipulation is 0-cost unless actually needed.
     */
-    Sum {a: String, res: String}, 
-
-    // add max, min (min can be in terms of max), etc.
-    // add max within the elew kernel
+    Sum  {a: VarId, res: VarId}, 
+    Max  {a: VarId, res: VarId},  // min(x) = -max(-x)
+    Prod {a: VarId, res: VarId}, 
 
     /* 
     Technically, Dot Product CAN BE expressed with Sum and ElwMult. In fact, this is what TinyGrad does
@@ -56,23 +56,31 @@ pub enum IRCmds {
     TinyGrad does recognizes this and applies these specific opts implementations if necessary, but descerning them from list of IR instructions POV is weird.
     Considering its importance in machine learning, I decided to seperate it to a seperate command itself.
     */
-    DotProduct {a: String, b: String, res: String},         // (a,b) x (b,c) --> (a,c). Both tensors are 2-dim.
+    DotProduct {a: VarId, b: VarId, res: VarId},         // (a,b) x (b,c) --> (a,c). Both tensors are 2-dim, or 3-dim with a leading batch dim:
+                                                            // (n,a,b) x (n,b,c) --> (n,a,c). A 2-dim side is shared across the batch
 
     // Data Manipulation --> every operation is 0-cost except Contigious
     // At kernel level, we just use fancy indexing. Check out matrix tracker (kernel/trackers/matrix.rs) and access expression generation (kernel/access_expr.rs)
-    View      {a: String, target_dim: Vec<usize>, res: String},
-    Index     {a: String, index: usize, dim: usize, res: String}, 
-    Concat    {a: String, b: String, dim: usize, res: String},
-    Permute   {a: String, p: Vec<usize>, res: String}, 
-    Broadcast {a: String, dim: usize, r: usize, res: String}, 
-    Contigious {a: String, res: String},  // all the above operations use fancy indexing. However, this operation constructs the full matrix explicitly.
+    View      {a: VarId, target_dim: Vec<usize>, res: VarId},
+    Index     {a: VarId, index: usize, dim: usize, res: VarId}, 
+    Concat    {a: VarId, b: VarId, dim: usize, res: VarId},
+    Permute   {a: VarId, p: Vec<usize>, res: VarId}, 
+    Broadcast {a: VarId, dim: usize, r: usize, res: VarId}, 
+    Unfold    {a: VarId, dim: usize, size: usize, step: usize, dilation: usize, res: VarId}, // sliding windows along dim; the window is a new last dim (ex: im2col)
+    Pad       {a: VarId, dim: usize, before: usize, after: usize, mode: PadMode, res: VarId}, // out of bounds reads are a constant or remapped into a; nothing is copied
+    Contigious {a: VarId, res: VarId},  // all the above operations use fancy indexing. However, this operation constructs the full matrix explicitly.
+
+    // Random
+    // Counter-based (Philox4x32-10): element i of res is sampled from the bits hashed from key = (state[0] (seed), state[2] (epoch)) and counter = (i, state[1] (offset))
+    // It only reads the state; the offset has to be advanced afterwards (see c_rand), otherwise the next Rand repeats the same numbers
+    Rand {state: VarId, dist: RandDist, dim: Vec<usize>, res: VarId},
 
     // Single-input Functions
-    Exp2  {a: String, res: String},   // fine: shift?
-    Log2  {a: String, res: String},   // fine: counting?
-    Sin   {a: String, res: String},   // not as fine: lookup table + quadratic interpolation (c0 + c1*x + c2*x*x); implement at conditioner
-    Recip {a: String, res: String},   // lookup-table-driven approximations combined with iterative refinement, optimized for parallel execution and hardware efficiency
-    Sqrt  {a: String, res: String},   // calculates inverse square root and then reciprocal...
+    Exp2  {a: VarId, res: VarId},   // fine: shift?
+    Log2  {a: VarId, res: VarId},   // fine: counting?
+    Sin   {a: VarId, res: VarId},   // not as fine: lookup table + quadratic interpolation (c0 + c1*x + c2*x*x); implement at conditioner
+    Recip {a: VarId, res: VarId},   // lookup-table-driven approximations combined with iterative refinement, optimized for parallel execution and hardware efficiency
+    Sqrt  {a: VarId, res: VarId},   // calculates inverse square root and then reciprocal...
 
     // Control Functions
     
@@ -81,22 +89,22 @@ pub enum IRCmds {
         [block] 
     }
     */
-    While {conditional_var: String, block: IRProcedure}, // covers for loops as well
+    While {conditional_var: VarId, block: IRProcedure}, // covers for loops as well
 
     /*
     No short circuiting (evaluates "if else" condition even if "if condition" is true)
 
-    if ( [conditions[0][0] <-- String ] == 1.0 ) {
+    if ( [conditions[0][0] <-- VarId ] == 1.0 ) {
         [ conditions[0][1] <-- block; IRProcedure ] 
     }
-    else if ( [conditions[1][0] <-- String ] == 1.0 ) {
+    else if ( [conditions[1][0] <-- VarId ] == 1.0 ) {
         [ conditions[1][1] <-- block; IRProcedure ] 
     } 
     else {
         [ else_proc ]
     }
     */
-    If {conditions: Vec<(String, IRProcedure)>, else_proc: Option<IRProcedure>},   
+    If {conditions: Vec<(VarId, IRProcedure)>, else_proc: Option<IRProcedure>},   
 
     EX,
 
@@ -104,6 +112,23 @@ pub enum IRCmds {
     Heading {cmt: String},                       // just a comment
 }
 
+// how Pad fills the elements outside of the source
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PadMode {
+    Constant(f32),  // fill with the value
+    Reflect,        // mirror around the edge, without repeating it: [a b c] --> b [a b c] b
+    Replicate       // repeat the edge: [a b c] --> a [a b c] c
+}
+
+// distribution sampled by Rand
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RandDist {
+    Uniform { low: f32, high: f32 },    // [low, high)
+    Normal { mean: f32, std: f32 },     // Box-Muller over two of the hashed words
+    Bernoulli { p: f32 },               // 1 with probability p, 0 otherwise
+    RandInt { low: i32, high: i32 }     // integers in [low, high)
+}
+
 // wrapper over list of instructions to run
 #[derive(Clone, PartialEq, Debug)]
 pub struct IRProcedure {
@@ -115,7 +140,10 @@ pub struct IRBase {
     pub id: u32, 
     pub proc_id: u32,
     pub proc: IRProcedure,
-    pub temp_proc: Vec<IRProcedure> // Follows a stack processes
+    pub temp_proc: Vec<IRProcedure>, // Follows a stack processes
+    pub placeholders: HashMap<VarId, Vec<usize>>, // placeholder id --> dim
+    pub exited: bool, // main ends with the EX of a previous execution
+    pub rng: Option<(VarId, VarId, VarId)> // (state, wrap, step) of the random generator. state = [seed, offset, epoch] (see Generator)
 }
 
 pub static DEVICE: Mutex<Option<Box<dyn Device + Send + Sync>>> = Mutex::new(None);
@@ -124,11 +152,18 @@ pub static IRB: Mutex<Option<IRBase>> = Mutex::new(None);
 pub static L: Option<IRBase> = None;
 
 pub trait Device {
-    // Execute a list of instructions 
-    fn execute (&mut self, proc: KernelProcedure, tracker: KernelTracker);
+    // Compiles a list of instructions, so it can be ran many times. `program.id` is unique per program
+    // If no compilation is needed, then just leave this function empty
+    fn compile (&mut self, program: &CompiledProgram);
+
+    // Runs a compiled list of instructions. Variables in `program.persist` must be kept on the device for the next run
+    fn run (&mut self, program: &CompiledProgram);
+
+    // The program `id` won't be ran anymore; drop anything cached for it
+    fn release (&mut self, _id: usize) {}
     
     // Transfers matrix id to device.
-    fn get_tensor (&self, id: &String) -> ValueData;
+    fn get_tensor (&self, id: VarId) -> ValueData;
     
     // If the device needs any specific requirements / changes to the IR before passing to IR optimization, you can declare it here.
     // If no optimizations needed, then just leave this function empty
@@ -138,12 +173,15 @@ pub trait Device {
     // There are many implementations of dot product a device can have (ex: b might need to be transposed for column-wise accessing).
     // Therefore, this function exists to accomodate any DP implementations
     fn dot_prod_shape (&self, a: &Vec<usize>, b: &Vec<usize>) -> Vec<usize> {
-        vec![a.first().unwrap().clone(), b.last().unwrap().clone()]
+        let mut shape = if a.len() == 3 { vec![a[0]] } else if b.len() == 3 { vec![b[0]] } else { vec![] };
+        shape.push(a[a.len()-2]);
+        shape.push(*b.last().unwrap());
+        shape
     }
 }
 
 // helper functions for generating IR
-pub fn if_b_id () -> String {
+pub fn if_b_id () -> VarId {
     let mut guard = IRB.lock().unwrap();
     let irb = guard.as_mut().expect("Can't unpack guard");
     irb.unique_id()
@@ -156,7 +194,52 @@ pub fn ir_b_add (cmd : IRCmds) {
     drop(guard);
 }
 
-pub fn ir_b_id () -> String {
+// adds EX, unless the program was already executed before (ex: running it again after `feed`)
+pub fn ir_b_exit () {
+    let mut guard = IRB.lock().unwrap();
+    let ir_b = guard.as_mut().expect("Can't unpack IRBuilder");
+    if !ir_b.exited {
+        ir_b.add_cmd(IRCmds::EX);
+        ir_b.exited = true;
+    }
+    drop(guard);
+}
+
+pub fn ir_b_add_placeholder (id: VarId, dim: Vec<usize>) {
+    let mut guard = IRB.lock().unwrap();
+    let ir_b = guard.as_mut().expect("Can't unpack IRBuilder");
+    ir_b.placeholders.insert(id, dim);
+    drop(guard);
+}
+
+pub fn ir_b_placeholder_dim (id: VarId) -> Option<Vec<usize>> {
+    let guard = IRB.lock().unwrap();
+    let ir_b = guard.as_ref().expect("Can't unpack IRBuilder");
+    let dim = ir_b.placeholders.get(&id).cloned();
+    drop(guard);
+    dim
+}
+
+pub fn ir_b_feed (id: VarId, data: Vec<f32>) {
+    let mut guard = IRB.lock().unwrap();
+    let ir_b = guard.as_mut().expect("Can't unpack IRBuilder");
+    ir_b.feed(id, data);
+    drop(guard);
+}
+
+// id of the random generator state, declared at main on first use (so it's not redeclared every iteration of a loop)
+// the state is kept, so it's persisted across runs; the seed is picked once per IR builder
+pub fn ir_b_rng_state () -> (VarId, VarId, VarId) {
+    let mut guard = IRB.lock().unwrap();
+    let ir_b = guard.as_mut().expect("Can't unpack IRBuilder");
+    let rng = ir_b.rng_state();
+    drop(guard);
+
+    add_to_dep(rng.0);
+    rng
+}
+
+pub fn ir_b_id () -> VarId {
     let mut guard = IRB.lock().unwrap();
     let ir_b = guard.as_mut().expect("Can't unpack IRBuilder");
     let x = ir_b.unique_id();
@@ -164,6 +247,15 @@ pub fn ir_b_id () -> String {
     return x
 }
 
+// returns a copy of the procedure built so far (ex: for interpreting it with `ir::interp`)
+pub fn ir_b_proc () -> IRProcedure {
+    let guard = IRB.lock().unwrap();
+    let ir_b = guard.as_ref().expect("Can't unpack IRBuilder");
+    let proc = ir_b.proc.clone();
+    drop(guard);
+    proc
+}
+
 pub fn ir_b_device_callback () {
     let mut guard_device = DEVICE.lock().unwrap();
     let mut guard = IRB.lock().unwrap();
@@ -177,7 +269,7 @@ pub fn ir_b_device_callback () {
     drop(guard);
 }
 
-pub fn ir_b_execute (debug: bool) {
+pub fn ir_b_compile (debug: bool) -> CompiledProgram {
     // get cmds
     let mut guard_irb = IRB.lock().unwrap();
     let IRBase { proc, .. } = guard_irb.as_mut().expect("Can't unpack IRBuilder");
@@ -191,10 +283,28 @@ pub fn ir_b_execute (debug: bool) {
         display_colored_side_by_side(format!("{}", kernel_proc), format!("{}", proc));
     }
 
-    device.execute(kernel_proc, kernel_tracker);
+    // kept variables that are changed throughout the program are carried over to the next run
+    let dep_list = ret_dep_list();
+    let persist = track_var_changed(proc).into_iter()
+        .filter(|v| dep_list.contains(v))
+        .collect();
+
+    let program = CompiledProgram {
+        id: next_program_id(),
+        proc: kernel_proc,
+        tracker: kernel_tracker,
+        persist
+    };
+    device.compile(&program);
 
     drop(guard_device);
     drop(guard_irb);
+
+    program
+}
+
+pub fn ir_b_execute (debug: bool) {
+    ir_b_compile(debug).run();
 }
 
 pub fn ir_b_create_temp_proc () {
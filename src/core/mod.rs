@@ -10,6 +10,9 @@ pub mod dependency;
 pub mod env;
 pub mod procedure;
 pub mod print;
+pub mod gradcheck;
+pub mod program;
+pub mod var_id;
 
 pub use autodiff::*;
 pub use node::*;
@@ -19,4 +22,6 @@ pub use value_data::*;
 pub use constant::*;
 pub use ir::*;
 pub use dependency::*;
-pub use env::*;
\ No newline at end of file
+pub use env::*;
+pub use program::*;
+pub use var_id::*;
\ No newline at end of file
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::{kernel_decl::{KernelProcedure, Kernels}, trackers::KernelTracker, IRCmds, VarId};
+use super::{DEVICE, IRB};
+
+static PROGRAM_ID: AtomicUsize = AtomicUsize::new(0);
+
+pub fn next_program_id () -> usize {
+    PROGRAM_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/*
+Kernel procedure that's compiled once (ex: OpenCL programs), and can be ran any number of times
+ * the device keeps everything related to the program (keyed by `id`) until the program is dropped
+ * placeholders are fed (`autodiff::feed`) before every run
+ * `persist` are the kept variables that are changed throughout the program (ex: weights updated by an optimizer)
+   The device keeps them between runs (of any program), so the next run starts from their latest value rather than their declared contents
+*/
+pub struct CompiledProgram {
+    pub id: usize,
+    pub proc: KernelProcedure,
+    pub tracker: KernelTracker,
+    pub persist: Vec<VarId>
+}
+
+impl CompiledProgram {
+    pub fn run (&mut self) {
+        // update the contents of the placeholders
+        let fed = placeholder_contents();
+        if !fed.is_empty() {
+            self.proc.apply(&mut |proc| {
+                for cmd in proc.iter_mut() {
+                    if let Kernels::Alloc { id, content: Some(content), .. } = cmd {
+                        if let Some(data) = fed.get(id) { *content = data.clone(); }
+                    }
+                }
+            });
+        }
+
+        let mut guard = DEVICE.lock().unwrap();
+        let device = guard.as_mut().expect("Can't unpack device");
+        device.run(self);
+        drop(guard);
+    }
+}
+
+impl Drop for CompiledProgram {
+    fn drop (&mut self) {
+        // the device could've been poisoned by a panic; then there's nothing to release
+        if let Ok(mut guard) = DEVICE.lock() {
+            if let Some(device) = guard.as_mut() {
+                device.release(self.id);
+            }
+        }
+    }
+}
+
+// latest contents of each placeholder in the IR
+fn placeholder_contents () -> HashMap<VarId, Arc<Vec<f32>>> {
+    let mut guard = IRB.lock().unwrap();
+    let ir_b = guard.as_mut().expect("Can't unpack IRBuilder");
+
+    let mut fed = HashMap::new();
+    let placeholders = ir_b.placeholders.clone();
+    ir_b.proc.apply(&mut |proc| {
+        for cmd in proc.iter() {
+            if let IRCmds::CreateMat { contents, id, .. } = cmd {
+                if placeholders.contains_key(id) { fed.insert(*id, contents.clone()); }
+            }
+        }
+    });
+
+    drop(guard);
+    fed
+}
@@ -3,14 +3,14 @@
 // Node, Tensor, Ops, etc. are classes used to just build the computation graph. 
 
 use std::sync::Arc;
-use crate::{IRCmds, NodeTrait, ValueData};
+use crate::{IRCmds, NodeTrait, ValueData, VarId};
 use super::{add_to_dep, ir_b_add, ir_b_id, Tensor, TensorNode, DEVICE};
 
 // Value just stores the dimension as we go through forward and backward propogation. However, the actual value is computed when we execute IR
 #[derive(Clone, Debug)]
 pub struct Value {
     pub dim: Vec<usize>,
-    pub id: String
+    pub id: VarId
 }
 
 impl Value {
@@ -27,7 +27,7 @@ impl Value {
     pub fn empty () -> Value {
         Self {
             dim: vec![],
-            id: "".to_string()
+            id: VarId::NONE
         }
     }
 
@@ -68,7 +68,7 @@ impl Value {
         ir_b_add(IRCmds::CreateMat { 
             contents: Arc::new(data.clone()),
             dim: dim.clone(), 
-            id: id.clone() 
+            id: id 
         });
         
         
@@ -84,7 +84,7 @@ impl Value {
         let mut guard = DEVICE.lock().unwrap();
         let ir_b = guard.as_mut().expect("Can't unpack IRBuilder");
 
-        let res = ir_b.get_tensor(&self.id);
+        let res = ir_b.get_tensor(self.id);
         drop(guard);
         res
     }
@@ -95,7 +95,7 @@ impl Value {
     * However, for tensor::empty, there is a chance you might need to call this manually within your script.
     */
     pub fn keep (&self) {
-        add_to_dep(self.id.clone());
+        add_to_dep(self.id);
     }
 }
 
@@ -1,12 +1,13 @@
 use std::fmt::Display;
 use std::sync::Arc;
+use crate::VarId;
 
 // Value stored with Data; used primarily by user to get data from IR
 #[derive(Clone, Debug)]
 pub struct ValueData {
     pub dim: Vec<usize>,
     pub data: Arc<Vec<f32>>,
-    pub id: String,
+    pub id: VarId,
     pub is_none: bool
 }
 
@@ -28,7 +29,7 @@ impl ValueData {
         ValueData {
             dim: vec![],
             data: Arc::new(vec![]),
-            id: "".to_string(),
+            id: VarId::NONE,
             is_none: true
         }
     }
@@ -40,7 +41,7 @@ impl ValueData {
                 let mult = 10.0_f32.powi(num_digits as i32);
                 (x * mult).round() / mult
             }).collect()),
-            id: self.id.clone(),
+            id: self.id,
             is_none: self.is_none
         }
     }
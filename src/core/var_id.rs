@@ -0,0 +1,32 @@
+// id of a variable in the IR (and every kernel/device after it)
+use std::fmt::{Debug, Display, Formatter};
+use crate::IRBase;
+
+/*
+ Ids are cheap to copy, compare and hash; they only turn into a string (base 26: a, b, ..., z, aa, ...)
+ when displayed, ex: when printing the IR or generating the source code of a kernel
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VarId(pub u32);
+
+impl VarId {
+    // id of a value that isn't declared in the IR (ex: autodiff::empty)
+    pub const NONE: VarId = VarId(u32::MAX);
+    // shared buffer that tetris_opt packs temporary allocations into
+    pub const TEMP: VarId = VarId(u32::MAX - 1);
+}
+
+impl Display for VarId {
+    fn fmt (&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if *self == VarId::NONE { return write!(f, "none"); }
+        if *self == VarId::TEMP { return write!(f, "_temp"); }
+        write!(f, "{}", IRBase::unique_id_idx(self.0))
+    }
+}
+
+// same as display, so the IR stays readable when debug printed
+impl Debug for VarId {
+    fn fmt (&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::kernel_decl::{Input, Output};
+use crate::VarId;
+
+// Index of the "thread" the kernel is currently evaluated at.
+// On OpenCL, these come from get_global_id/get_group_id/get_local_id. On the host, we just loop over them.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ThreadIdx {
+    pub global: i64,
+    pub x: i64,
+    pub y: i64
+}
+
+impl ThreadIdx {
+    pub fn global (global: i64) -> ThreadIdx {
+        ThreadIdx { global, x: 0, y: 0 }
+    }
+
+    pub fn xy (x: i64, y: i64) -> ThreadIdx {
+        ThreadIdx { global: 0, x, y }
+    }
+}
+
+// Host-side equivalent of OpenCLContext. Holds every buffer allocated by the kernel procedure
+// as well as the temporary variable used inside fused kernels
+pub struct CPUContext {
+    buffers: HashMap<VarId, Vec<f32>>,
+    pub persistent: HashMap<VarId, Vec<f32>>, // values carried over from the previous run; used instead of the first alloc content
+    pub temp: f32
+}
+
+impl CPUContext {
+    pub fn new () -> CPUContext {
+        CPUContext {
+            buffers: HashMap::new(),
+            persistent: HashMap::new(),
+            temp: 0.0
+        }
+    }
+
+    pub fn create_buffer (&mut self, id: &VarId, size: usize) {
+        self.buffers.insert(*id, vec![0.0; size]);
+    }
+
+    pub fn write_buffer (&mut self, id: &VarId, data: &Arc<Vec<f32>>) {
+        if let Some(buf) = self.buffers.get(id) {
+            assert_eq!(buf.len(), data.len(), "Write size is not equal to alloc size!");
+        }
+        self.buffers.insert(*id, data.as_ref().clone());
+    }
+
+    pub fn dealloc_buffer (&mut self, id: &VarId) {
+        self.buffers.remove(id);
+    }
+
+    pub fn get_buffer (&self, id: &VarId) -> Option<&Vec<f32>> {
+        self.buffers.get(id)
+    }
+
+    pub fn read_buffer (&self, id: &VarId) -> &Vec<f32> {
+        self.buffers.get(id).unwrap_or_else(|| panic!("Invalid buffer id \"{}\" at reading", id))
+    }
+
+    pub fn read_input (&self, inp: &Input, idx: &ThreadIdx) -> f32 {
+        match inp {
+            Input::Constant { val } => *val,
+            Input::Mat { mat } => {
+                let access = mat.access.eval_cpu(idx);
+                let buf = self.read_buffer(&mat.id);
+                assert!(
+                    access >= 0 && (access as usize) < buf.len(), 
+                    "Out of bounds read at \"{}\" (access: {}, size: {})", mat.id, access, buf.len()
+                );
+                buf[access as usize]
+            },
+            Input::ConcatMatrix { id_one, id_two, conditional } => {
+                // only evaluate the branch that is taken; the other access expression may be negative
+                if conditional.eval_cpu(idx) != 0 {
+                    self.read_input(id_two, idx)
+                } else {
+                    self.read_input(id_one, idx)
+                }
+            },
+            Input::Temp => self.temp
+        }
+    }
+
+    pub fn write_output (&mut self, out: &Output, idx: &ThreadIdx, val: f32) {
+        match out {
+            Output::Mat { mat } => {
+                let access = mat.access.eval_cpu(idx);
+                let buf = self.buffers.get_mut(&mat.id)
+                    .unwrap_or_else(|| panic!("Invalid buffer id \"{}\" at writing", mat.id));
+                assert!(
+                    access >= 0 && (access as usize) < buf.len(), 
+                    "Out of bounds write at \"{}\" (access: {}, size: {})", mat.id, access, buf.len()
+                );
+                buf[access as usize] = val;
+            },
+            Output::Temp => { self.temp = val; }
+        }
+    }
+}
+
+impl Default for CPUContext {
+    fn default () -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::core::ret_dep_list;
+use crate::devices::cpu::alloc::execute_alloc;
+use crate::devices::cpu::binary::execute_binary;
+use crate::devices::cpu::context::CPUContext;
+use crate::devices::cpu::dotprod::execute_dot_prod;
+use crate::devices::cpu::fuse_dp_elw::execute_fuse_dp_elw;
+use crate::devices::cpu::fuse_elw::execute_elw_expr;
+use crate::devices::cpu::fuse_reduce_elw::execute_fuse_reduce_elw;
+use crate::devices::cpu::movement::execute_movement;
+use crate::devices::cpu::rand::execute_rand;
+use crate::devices::cpu::reduce::execute_reduce;
+use crate::devices::cpu::unary::execute_unary;
+use crate::kernel_decl::{KernelProcedure, Kernels};
+use crate::{CompiledProgram, IRBase, ValueData, Device, VarId};
+
+// Pure-Rust reference device. Walks the kernel procedure on the host, one "thread" at a time.
+// This is slow, but it doesn't need any drivers and is used as the ground truth for the other backends.
+pub struct Reference {
+    result: HashMap<VarId, Arc<Vec<f32>>>,
+    result_shape: HashMap<VarId, Vec<usize>>,
+    persistent: HashMap<VarId, Vec<f32>> // persisted variables between runs
+}
+
+impl Reference {
+    pub fn new () -> Reference {
+        Reference {
+            result: HashMap::new(),
+            result_shape: HashMap::new(),
+            persistent: HashMap::new()
+        }
+    }
+}
+
+impl Default for Reference {
+    fn default () -> Self {
+        Self::new()
+    }
+}
+
+impl Device for Reference {
+    // kernels are interpreted; nothing to compile
+    fn compile (&mut self, _: &CompiledProgram) {}
+
+    fn run (&mut self, program: &CompiledProgram) {
+        let CompiledProgram { proc, tracker, persist, .. } = program;
+        let mut context = CPUContext::new();
+        context.persistent = std::mem::take(&mut self.persistent);
+
+        proc_exec(proc, &mut context);
+
+        // persisted values that this program doesn't use are kept for later runs
+        self.persistent = std::mem::take(&mut context.persistent);
+        for p in persist.iter() {
+            if let Some(buf) = context.get_buffer(p) {
+                self.persistent.insert(*p, buf.clone());
+            }
+        }
+
+        // From all dep list, get variables
+        let dep_list = ret_dep_list();
+        for st in dep_list.iter() {
+            let shape = tracker.get_shape(st).clone();
+            if let Some(buf) = context.get_buffer(st) {
+                self.result.insert(*st, Arc::new(buf.clone()));
+            }
+            else if let Some(c) = tracker.get_constant(st) {
+                // constants are never allocated; they are inlined into the kernels
+                let size = shape.iter().product();
+                self.result.insert(*st, Arc::new(vec![c; size]));
+            }
+            else {
+                continue;
+            }
+            self.result_shape.insert(*st, shape);
+        }
+    }
+
+    fn get_tensor (&self, id: VarId) -> ValueData {
+        if let Some(data) = self.result.get(&id) {
+            ValueData {
+                id: id,
+                dim: self.result_shape.get(&id).unwrap().clone(),
+                data: data.clone(), 
+                is_none: false
+            }
+        } else {
+            ValueData::none()  
+        }
+    }
+
+    fn ir_callback (&self, _: &mut IRBase) {}
+}
+
+fn proc_exec (proc: &KernelProcedure, context: &mut CPUContext) -> bool {
+    let mut exit = false;
+
+    for cmd in proc.iter() {
+        if let Kernels::EX = cmd {
+            return true;
+        }
+        else if let Kernels::If { conditions, else_proc } = cmd {
+            let mut run_cond = false;
+            for (cond, c_proc) in conditions.iter() {
+                if context.read_buffer(cond)[0] == 1.0 {
+                    exit = proc_exec(c_proc, context); // run whatever is inside condition
+                    run_cond = true;                   // set run condition
+                    break;                             // don't eval any other conditions
+                }
+            }
+
+            if let Some(e_proc) = else_proc {
+                if !run_cond { exit = proc_exec(e_proc, context); }
+            }
+        }
+        else if let Kernels::While { conditional_var, block } = cmd {
+            while context.read_buffer(conditional_var)[0] != 0.0 {
+                exit = proc_exec(block, context);
+                if exit { break; }
+            }
+        }
+        else {
+            exec(cmd, context);
+        }
+
+        if exit { return true; }
+    }
+
+    false
+}
+
+fn exec (cmd: &Kernels, context: &mut CPUContext) {
+    match cmd {
+        Kernels::Alloc { .. } => { execute_alloc(context, cmd); },
+        Kernels::Dealloc { .. } => { execute_alloc(context, cmd); },
+        Kernels::Unary { .. } => { execute_unary(context, cmd); },
+        Kernels::Binary { .. } => { execute_binary(context, cmd); },
+        Kernels::DotProd { .. } => { execute_dot_prod(context, cmd); },
+        Kernels::Reduce { .. } => { execute_reduce(context, cmd); },
+        Kernels::Movement { .. } => { execute_movement(context, cmd); },
+        Kernels::Rand { .. } => { execute_rand(context, cmd); },
+        Kernels::ElwExpr { .. } => { execute_elw_expr(context, cmd); },
+        Kernels::DPElwExpr { .. } => { execute_fuse_dp_elw(context, cmd); },
+        Kernels::ReduceElwExpr { .. } => { execute_fuse_reduce_elw(context, cmd); },
+        Kernels::While { .. } => {}, // handled by parent funcs
+        Kernels::If { .. } => {}, // handled by parent funcs
+        Kernels::EX => {}, // handled by parent funcs
+    }
+}
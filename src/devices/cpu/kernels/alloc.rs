@@ -0,0 +1,30 @@
+use std::sync::Arc;
+use crate::devices::cpu::context::CPUContext;
+use crate::kernel_decl::Kernels;
+
+pub fn execute_alloc (ctx: &mut CPUContext, cmd: &Kernels) {
+    match cmd {
+        Kernels::Alloc { id, size, content } => {
+            if let Some(c) = content {
+                assert_eq!(c.len(), *size, "Size of content is not equal size of alloc");
+
+                // only the first alloc of a run starts from the persisted value (ex: not every iteration of a while loop)
+                if let Some(p) = ctx.persistent.remove(id) {
+                    assert_eq!(p.len(), *size, "Size of persisted value is not equal size of alloc");
+                    ctx.write_buffer(id, &Arc::new(p));
+                } else {
+                    ctx.write_buffer(id, c);
+                }
+            }
+            else {
+                ctx.create_buffer(id, *size);
+            }
+        },
+        Kernels::Dealloc { id, .. } => {
+            // unlike OpenCL, we actually drop the buffer.
+            // Any read after a dealloc is a bug in the allocation passes, and we want the reference to catch that
+            ctx.dealloc_buffer(id);
+        },
+        _ => {}
+    }
+}
@@ -0,0 +1,24 @@
+use crate::devices::cpu::context::{CPUContext, ThreadIdx};
+use crate::kernel_decl::{BinaryOp, Input, Kernels, Output};
+
+impl BinaryOp {
+    pub fn eval_cpu (&self, a: f32, b: f32) -> f32 {
+        match self {
+            BinaryOp::Add => a + b,
+            BinaryOp::Multiply => a * b
+        }
+    }
+}
+
+pub fn cpu_binary_to_body (ctx: &mut CPUContext, idx: &ThreadIdx, a: &Input, b: &Input, res: &Output, op: &BinaryOp) {
+    let val = op.eval_cpu(ctx.read_input(a, idx), ctx.read_input(b, idx));
+    ctx.write_output(res, idx, val);
+}
+
+pub fn execute_binary (ctx: &mut CPUContext, cmd: &Kernels) {
+    if let Kernels::Binary { a, b, res, op, size, .. } = cmd {
+        for g in 0..*size {
+            cpu_binary_to_body(ctx, &ThreadIdx::global(g as i64), a, b, res, op);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::devices::cpu::context::{CPUContext, ThreadIdx};
+use crate::kernel_decl::{Input, Kernels, Output};
+
+// computes a single element (x, y) of the dot product and writes it to res
+// a is read from row a.1 and b from rows b.1.. (these differ from x when batched)
+pub fn cpu_dot_prod_to_body (ctx: &mut CPUContext, x: usize, y: usize, input_size: usize, a: (&Input, usize), b: (&Input, usize), res: &Output) {
+    let mut value = 0.0;
+    for k in 0..input_size {
+        let element_a = ctx.read_input(a.0, &ThreadIdx::xy(a.1 as i64, k as i64));
+        let element_b = ctx.read_input(b.0, &ThreadIdx::xy((b.1 + k) as i64, y as i64));
+        value += element_a * element_b;
+    }
+
+    ctx.write_output(res, &ThreadIdx::xy(x as i64, y as i64), value);
+}
+
+// x walks the rows of every batch; returns the starting row of a and b, offset by the batch's stride (0 if shared across the batch)
+pub fn batch_rows (x: usize, rows: usize, batch_stride: (usize, usize)) -> (usize, usize) {
+    let (batch, row) = (x / rows, x % rows);
+    (batch * batch_stride.0 + row, batch * batch_stride.1)
+}
+
+pub fn execute_dot_prod (ctx: &mut CPUContext, cmd: &Kernels) {
+    if let Kernels::DotProd { a, b, res, a_shape, res_shape, batch_size, batch_stride, .. } = cmd {
+        for x in 0..batch_size * a_shape.0 {
+            let (a_x, b_x) = batch_rows(x, a_shape.0, *batch_stride);
+            for y in 0..res_shape.1 {
+                cpu_dot_prod_to_body(ctx, x, y, a_shape.1, (a, a_x), (b, b_x), res);
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::devices::cpu::context::ThreadIdx;
+use crate::kernel_decl::{Expression, Value};
+
+impl Value {
+    pub fn eval_cpu (&self, idx: &ThreadIdx) -> i64 {
+        match self {
+            Value::Constant { val } => *val as i64,
+            Value::Global => idx.global,
+            Value::X => idx.x,
+            Value::Y => idx.y
+        }
+    }
+}
+
+impl Expression {
+    // integer semantics follow OpenCL C (truncated division and remainder)
+    pub fn eval_cpu (&self, idx: &ThreadIdx) -> i64 {
+        match self {
+            Expression::Val { v } => v.eval_cpu(idx),
+            Expression::Add { a, b } => a.eval_cpu(idx) + b.eval_cpu(idx),
+            Expression::Minus { a, b } => a.eval_cpu(idx) - b.eval_cpu(idx),
+            Expression::Mult { a, b } => a.eval_cpu(idx) * b.eval_cpu(idx),
+            Expression::Div { a, b } => a.eval_cpu(idx) / b.eval_cpu(idx),
+            Expression::Remainder { a, b } => a.eval_cpu(idx) % b.eval_cpu(idx),
+            Expression::ShiftRight { a, b } => a.eval_cpu(idx) >> b.eval_cpu(idx),
+            Expression::ShiftLeft { a, b } => a.eval_cpu(idx) << b.eval_cpu(idx),
+            Expression::BitwiseAnd { a, b } => a.eval_cpu(idx) & b.eval_cpu(idx),
+            Expression::MoreThan { a, b } => (a.eval_cpu(idx) > b.eval_cpu(idx)) as i64,
+            Expression::LessThan { a, b } => (a.eval_cpu(idx) < b.eval_cpu(idx)) as i64
+        }
+    }
+}
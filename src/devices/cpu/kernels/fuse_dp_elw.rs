@@ -0,0 +1,24 @@
+use crate::devices::cpu::context::{CPUContext, ThreadIdx};
+use crate::devices::cpu::dotprod::{batch_rows, cpu_dot_prod_to_body};
+use crate::devices::cpu::fuse_elw::cpu_elw_kernels_to_body;
+use crate::kernel_decl::Kernels;
+
+pub fn execute_fuse_dp_elw (ctx: &mut CPUContext, cmd: &Kernels) {
+    if let Kernels::DPElwExpr { kernels, a_shape, res_shape, .. } = cmd {
+        let (a, b, res, batch_size, batch_stride) = match kernels.first() {
+            Some(Kernels::DotProd { a, b, res, batch_size, batch_stride, .. }) => (a, b, res, *batch_size, *batch_stride),
+            _ => panic!("First command is not a DP operation!")
+        };
+
+        for x in 0..batch_size * a_shape.0 {
+            let (a_x, b_x) = batch_rows(x, a_shape.0, batch_stride);
+            for y in 0..res_shape.1 {
+                ctx.temp = 0.0;
+                cpu_dot_prod_to_body(ctx, x, y, a_shape.1, (a, a_x), (b, b_x), res);
+
+                let idx = ThreadIdx { global: (x * res_shape.1 + y) as i64, x: x as i64, y: y as i64 };
+                cpu_elw_kernels_to_body(ctx, &idx, &kernels[1..]);
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::devices::cpu::binary::cpu_binary_to_body;
+use crate::devices::cpu::context::{CPUContext, ThreadIdx};
+use crate::devices::cpu::movement::cpu_movement_to_body;
+use crate::devices::cpu::unary::cpu_unary_to_body;
+use crate::kernel_decl::Kernels;
+
+pub fn cpu_elw_kernels_to_body (ctx: &mut CPUContext, idx: &ThreadIdx, kernels: &[Kernels]) {
+    for k in kernels {
+        match k {
+            Kernels::Binary { a, b, res, op, .. } => { cpu_binary_to_body(ctx, idx, a, b, res, op); },
+            Kernels::Movement { a, res, .. } => { cpu_movement_to_body(ctx, idx, a, res); },
+            Kernels::Unary { a, res, op, .. } => { cpu_unary_to_body(ctx, idx, a, res, op); },
+            _ => panic!("Invalid kernel in elw fusion!")
+        }
+    }
+}
+
+pub fn execute_elw_expr (ctx: &mut CPUContext, cmd: &Kernels) {
+    if let Kernels::ElwExpr { kernels, size, .. } = cmd {
+        for g in 0..*size {
+            ctx.temp = 0.0;
+            cpu_elw_kernels_to_body(ctx, &ThreadIdx::global(g as i64), kernels);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::devices::cpu::context::{CPUContext, ThreadIdx};
+use crate::devices::cpu::fuse_elw::cpu_elw_kernels_to_body;
+use crate::devices::cpu::reduce::cpu_reduce_to_body;
+use crate::kernel_decl::Kernels;
+
+pub fn execute_fuse_reduce_elw (ctx: &mut CPUContext, cmd: &Kernels) {
+    if let Kernels::ReduceElwExpr { kernels, vec_size, reduce_size, .. } = cmd {
+        let (a, res, op) = match kernels.first() {
+            Some(Kernels::Reduce { a, res, op, .. }) => (a, res, op),
+            _ => panic!("First command is not a Reduce operation!")
+        };
+
+        for x in 0..*vec_size {
+            ctx.temp = 0.0;
+            cpu_reduce_to_body(ctx, x, *reduce_size, a, res, op);
+
+            let idx = ThreadIdx { global: x as i64, x: x as i64, y: 0 };
+            cpu_elw_kernels_to_body(ctx, &idx, &kernels[1..]);
+        }
+    }
+}
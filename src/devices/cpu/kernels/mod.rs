@@ -0,0 +1,12 @@
+pub mod binary;
+pub mod dotprod;
+pub mod movement;
+pub mod reduce;
+pub mod unary;
+pub mod expression;
+pub mod alloc;
+pub mod rand;
+
+pub mod fuse_elw;
+pub mod fuse_dp_elw;
+pub mod fuse_reduce_elw;
@@ -0,0 +1,15 @@
+use crate::devices::cpu::context::{CPUContext, ThreadIdx};
+use crate::kernel_decl::{Input, Kernels, Output};
+
+pub fn cpu_movement_to_body (ctx: &mut CPUContext, idx: &ThreadIdx, a: &Input, res: &Output) {
+    let val = ctx.read_input(a, idx);
+    ctx.write_output(res, idx, val);
+}
+
+pub fn execute_movement (ctx: &mut CPUContext, cmd: &Kernels) {
+    if let Kernels::Movement { a, res, size, .. } = cmd {
+        for g in 0..*size {
+            cpu_movement_to_body(ctx, &ThreadIdx::global(g as i64), a, res);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::devices::cpu::context::{CPUContext, ThreadIdx};
+use crate::kernel_decl::Kernels;
+use crate::RandDist;
+
+// Philox4x32-10 (Salmon et al., "Parallel Random Numbers: As Easy as 1, 2, 3")
+// Stateless: the same (seed, offset, idx) always hashes to the same number, so every thread can generate its own element
+const PHILOX_M0: u32 = 0xD2511F53;
+const PHILOX_M1: u32 = 0xCD9E8D57;
+const PHILOX_W0: u32 = 0x9E3779B9;
+const PHILOX_W1: u32 = 0xBB67AE85;
+
+pub fn philox (key: [u32; 2], ctr: [u32; 4]) -> [u32; 4] {
+    let (mut k, mut c) = (key, ctr);
+    for round in 0..10 {
+        if round > 0 {
+            k = [k[0].wrapping_add(PHILOX_W0), k[1].wrapping_add(PHILOX_W1)];
+        }
+        let p0 = (PHILOX_M0 as u64) * (c[0] as u64);
+        let p1 = (PHILOX_M1 as u64) * (c[2] as u64);
+        c = [
+            ((p1 >> 32) as u32) ^ c[1] ^ k[0],
+            p1 as u32,
+            ((p0 >> 32) as u32) ^ c[3] ^ k[1],
+            p0 as u32
+        ];
+    }
+    c
+}
+
+// the top 24 bits of a word as a float in [0, 1); they are exactly representable as f32
+fn to_unit (bits: u32) -> f32 {
+    (bits >> 8) as f32 * (1.0 / 16777216.0)
+}
+
+impl RandDist {
+    // must match the OpenCL codegen (opencl/kernels/rand.rs)
+    pub fn eval_cpu (&self, bits: [u32; 4]) -> f32 {
+        match *self {
+            RandDist::Uniform { low, high } => low + to_unit(bits[0]) * (high - low),
+            RandDist::Normal { mean, std } => {
+                let u1 = to_unit(bits[0]) + 1.0 / 16777216.0; // (0, 1], so the log is finite
+                let u2 = to_unit(bits[1]);
+                mean + std * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
+            },
+            RandDist::Bernoulli { p } => if to_unit(bits[0]) < p { 1.0 } else { 0.0 },
+            // multiply-shift instead of a float scale, so the result never rounds up to high
+            RandDist::RandInt { low, high } => {
+                let n = (high - low) as u64;
+                (low as i64 + ((bits[0] as u64 * n) >> 32) as i64) as f32
+            }
+        }
+    }
+}
+
+pub fn philox_sample (dist: &RandDist, seed: u32, offset: u32, epoch: u32, idx: u32) -> f32 {
+    dist.eval_cpu(philox([seed, epoch], [idx, offset, 0, 0]))
+}
+
+pub fn execute_rand (ctx: &mut CPUContext, cmd: &Kernels) {
+    if let Kernels::Rand { state, res, dist, size, .. } = cmd {
+        let seed = ctx.read_input(state, &ThreadIdx::global(0)) as u32;
+        let offset = ctx.read_input(state, &ThreadIdx::global(1)) as u32;
+        let epoch = ctx.read_input(state, &ThreadIdx::global(2)) as u32;
+
+        for g in 0..*size {
+            ctx.write_output(res, &ThreadIdx::global(g as i64), philox_sample(dist, seed, offset, epoch, g as u32));
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::devices::cpu::context::{CPUContext, ThreadIdx};
+use crate::kernel_decl::{Input, Kernels, Output, ReduceOp};
+
+impl ReduceOp {
+    pub fn init_cpu (&self) -> f32 {
+        match self {
+            ReduceOp::Sum => 0.0,
+            ReduceOp::Max => f32::NEG_INFINITY,
+            ReduceOp::Prod => 1.0
+        }
+    }
+
+    pub fn eval_cpu (&self, orig: f32, new: f32) -> f32 {
+        match self {
+            ReduceOp::Sum => orig + new,
+            ReduceOp::Max => orig.max(new),
+            ReduceOp::Prod => orig * new
+        }
+    }
+}
+
+// reduces vector x along y and writes to res. Returns the reduced value
+pub fn cpu_reduce_to_body (ctx: &mut CPUContext, x: usize, reduce_size: usize, a: &Input, res: &Output, op: &ReduceOp) -> f32 {
+    let mut acc = op.init_cpu();
+    for y in 0..reduce_size {
+        acc = op.eval_cpu(acc, ctx.read_input(a, &ThreadIdx::xy(x as i64, y as i64)));
+    }
+
+    ctx.write_output(res, &ThreadIdx::xy(x as i64, 0), acc);
+    acc
+}
+
+pub fn execute_reduce (ctx: &mut CPUContext, cmd: &Kernels) {
+    if let Kernels::Reduce { a, res, op, vec_size, reduce_size, .. } = cmd {
+        for x in 0..*vec_size {
+            cpu_reduce_to_body(ctx, x, *reduce_size, a, res, op);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::devices::cpu::context::{CPUContext, ThreadIdx};
+use crate::kernel_decl::{Input, Kernels, Output, UnaryOp};
+
+impl UnaryOp {
+    // mirrors the OpenCL codegen (ex: sqrt is taken over the absolute value)
+    pub fn eval_cpu (&self, a: f32) -> f32 {
+        match self {
+            UnaryOp::Exp2 => a.exp2(),
+            UnaryOp::Log2 => a.log2(),
+            UnaryOp::Sin => a.sin(),
+            UnaryOp::Neg => -a,
+            UnaryOp::Recip => 1.0 / a,
+            UnaryOp::Sqrt => a.abs().sqrt(),
+            UnaryOp::EqualZero => if a == 0.0 { 1.0 } else { 0.0 },
+            UnaryOp::MoreZero => if a > 0.0 { 1.0 } else { 0.0 },
+            UnaryOp::LessZero => if a < 0.0 { 1.0 } else { 0.0 }
+        }
+    }
+}
+
+pub fn cpu_unary_to_body (ctx: &mut CPUContext, idx: &ThreadIdx, a: &Input, res: &Output, op: &UnaryOp) {
+    let val = op.eval_cpu(ctx.read_input(a, idx));
+    ctx.write_output(res, idx, val);
+}
+
+pub fn execute_unary (ctx: &mut CPUContext, cmd: &Kernels) {
+    if let Kernels::Unary { a, res, op, size, .. } = cmd {
+        for g in 0..*size {
+            cpu_unary_to_body(ctx, &ThreadIdx::global(g as i64), a, res, op);
+        }
+    }
+}
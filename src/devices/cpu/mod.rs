@@ -0,0 +1,6 @@
+pub mod kernels;
+pub mod device;
+pub mod context;
+
+pub use kernels::*;
+pub use device::*;
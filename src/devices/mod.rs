@@ -1,5 +1,6 @@
 pub mod cuda;
 pub mod opencl;
+pub mod cpu;
 
 // pub use cuda::*;
-pub use opencl::*;
\ No newline at end of file
+pub use opencl::*;
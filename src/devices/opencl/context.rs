@@ -1,5 +1,5 @@
 use std::fmt::Display;
-use std::{collections::HashMap, ptr::null_mut};
+use std::{collections::{HashMap, HashSet}, ptr::null_mut};
 use std::sync::Arc;
 
 use opencl3::kernel::ExecuteKernel;
@@ -12,19 +12,29 @@ use opencl3::{
     program::Program, 
     types::{cl_float, CL_BLOCKING}
 };
+use crate::VarId;
 
 
 // Wrapper over the actual opencl3 context, but also includes any variables or compiled programs
 // As we go through the program, it will cache any buffers and program compiled
+// Kernels are cached per program (kernel names are only unique within a program), while buffers are shared between programs
 pub struct OpenCLContext {
     queue: CommandQueue,
     context: Context,
-    buffers: HashMap<String, Buffer<f32>>,
-    buffer_size: HashMap<String, usize>,
-    kernels: HashMap<String, Kernel>,
-    kernel_src: HashMap<String, String>
+    buffers: HashMap<VarId, Buffer<f32>>,
+    buffer_size: HashMap<VarId, usize>,
+    kernels: HashMap<(usize, String), Kernel>,
+    kernel_src: HashMap<(usize, String), String>,
+
+    pub program: usize,                 // program currently compiled/executed
+    pub warmup: bool,                   // only compile kernels; don't write buffers or execute kernels
+    pub persistent: HashSet<VarId>     // buffers that hold the value from the previous run; the first alloc doesn't overwrite them
 }
 
+// The context is only ever accessed through the DEVICE mutex (see `OpenCL`), so it's never used by two threads at once.
+// opencl3's Kernel isn't Sync as clSetKernelArg isn't thread-safe
+unsafe impl Sync for OpenCLContext {}
+
 impl OpenCLContext {
     pub fn new (device: CLDevice) -> OpenCLContext {
         let context = Context::from_device(&device)
@@ -39,27 +49,40 @@ impl OpenCLContext {
             buffers: HashMap::new(),
             buffer_size: HashMap::new(),
             kernels: HashMap::new(),
-            kernel_src: HashMap::new()
+            kernel_src: HashMap::new(),
+            program: 0,
+            warmup: false,
+            persistent: HashSet::new()
         }
     }
 
-    pub fn create_buffer (&mut self, id: &String, size: usize) {
+    pub fn has_buffer (&self, id: &VarId) -> bool {
+        self.buffers.contains_key(id)
+    }
+
+    // drop the compiled kernels of a program
+    pub fn release (&mut self, program: usize) {
+        self.kernels.retain(|(p, _), _| *p != program);
+        self.kernel_src.retain(|(p, _), _| *p != program);
+    }
+
+    pub fn create_buffer (&mut self, id: &VarId, size: usize) {
         self.write_buffer(id, &Arc::new(vec![0.0; size]));
     }
 
-    pub fn write_buffer (&mut self, id: &String, data: &Arc<Vec<f32>>) {
+    pub fn write_buffer (&mut self, id: &VarId, data: &Arc<Vec<f32>>) {
         let size = data.len();
 
         let OpenCLContext { queue, buffers, .. } = self;
 
-        let buf = buffers.entry(id.clone()).or_insert(
+        let buf = buffers.entry(*id).or_insert(
     unsafe {
                 Buffer::<cl_float>::create(&self.context, CL_MEM_READ_WRITE, size, null_mut())
                     .expect("Can't create buffer")
             }
         );
 
-        self.buffer_size.entry(id.clone())
+        self.buffer_size.entry(*id)
             .and_modify(|v| assert_eq!(*v, size, "Write size is not equal to alloc size!"))
             .or_insert(size);
         
@@ -71,7 +94,7 @@ impl OpenCLContext {
         write_event.wait().expect("Can't wait for write buffer")
     }
 
-    pub fn read_buffer (&mut self, id: &String) -> Vec<f32> {
+    pub fn read_buffer (&mut self, id: &VarId) -> Vec<f32> {
         let buf = self.buffers.get(id).expect(format!("Invalid buffer id \"{}\" at reading", id).as_str());
         let size = self.buffer_size.get(id).expect("Invalid buffer id at reading size");
 
@@ -87,17 +110,17 @@ impl OpenCLContext {
         results
     }
 
-    pub fn get_kernel<F> (&mut self, kernel_name: &String, gen_src_code: F) -> (&HashMap<String, Buffer<f32>>, ExecuteKernel, &CommandQueue)
+    pub fn get_kernel<F> (&mut self, kernel_name: &String, gen_src_code: F) -> (&HashMap<VarId, Buffer<f32>>, ExecuteKernel, &CommandQueue)
         where F: Fn() -> String 
     {
         let OpenCLContext { kernels, context, buffers, queue, .. } = self;
 
-        let k = kernels.entry(kernel_name.clone())
+        let k = kernels.entry((self.program, kernel_name.clone()))
             .or_insert_with(|| {
                 let src_code = gen_src_code();
                 let program = Program::create_and_build_from_source(&context, &src_code, "")
                     .expect(format!("Can't build program:\n{}", src_code).as_str());
-                self.kernel_src.insert(kernel_name.clone(), src_code);
+                self.kernel_src.insert((self.program, kernel_name.clone()), src_code);
 
                 Kernel::create(&program, kernel_name).expect("Can't create kernel")
             });
@@ -108,8 +131,8 @@ impl OpenCLContext {
 
 impl Display for OpenCLContext {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (id, src) in self.kernel_src.iter() {
-            let _ = write!(f, "========== For kernel {} ==========\n", id);
+        for ((program, id), src) in self.kernel_src.iter() {
+            let _ = write!(f, "========== For kernel {} (program {}) ==========\n", id, program);
             let _ = write!(f, "{}\n\n", src);
         }
 
@@ -18,11 +18,11 @@ use crate::devices::fuse_dp_elw::execute_fuse_dp_elw;
 use crate::devices::fuse_elw::execute_elw_expr;
 use crate::devices::fuse_reduce_elw::execute_fuse_reduce_elw;
 use crate::devices::movement::execute_movement;
+use crate::devices::rand::execute_rand;
 use crate::devices::reduce::execute_reduce;
 use crate::devices::unary::execute_unary;
 use crate::kernel_decl::{KernelProcedure, Kernels};
-use crate::trackers::KernelTracker;
-use crate::{IRBase, ValueData, Device};
+use crate::{CompiledProgram, IRBase, ValueData, Device, VarId};
 
 pub enum CLDeviceType {
     CPU,
@@ -32,9 +32,9 @@ pub enum CLDeviceType {
 }
 
 pub struct OpenCL {
-    device: CLDevice,
-    result: HashMap<String, Arc<Vec<f32>>>,
-    result_shape: HashMap<String, Vec<usize>>
+    context: OpenCLContext, // compiled programs (keyed by program id) + buffers shared between programs
+    result: HashMap<VarId, Arc<Vec<f32>>>,
+    result_shape: HashMap<VarId, Vec<usize>>
 }
 
 impl OpenCL {
@@ -51,7 +51,7 @@ impl OpenCL {
         println!("Using device: {}", device.name().expect("Can't get device name"));
 
         OpenCL { 
-            device,
+            context: OpenCLContext::new(device),
             result: HashMap::new(),
             result_shape: HashMap::new()
         }
@@ -59,40 +59,57 @@ impl OpenCL {
 }
 
 impl Device for OpenCL {
-    fn execute (&mut self, proc: KernelProcedure, tracker: KernelTracker) {
-        self.result.clear();
-        self.result_shape.clear();
-        let mut context = OpenCLContext::new(self.device);
+    fn compile (&mut self, program: &CompiledProgram) {
+        let context = &mut self.context;
+        context.program = program.id;
         
-        // warmup; compile programs, initialize buffers + writing to those buffers, executing kernels, etc.
+        // warmup; compile programs. Buffers aren't touched, as they may hold persisted variables
         println!("Compiling...");
-        let mut proc = proc;
+        context.warmup = true;
+        let mut proc = program.proc.clone();
         proc.step_cmd(&mut |v, idx| {
             let cmd = v.get(*idx).unwrap();
-            exec(cmd, &mut context); 
+            exec(cmd, context); 
             true
         });
+        context.warmup = false;
+    }
+
+    fn run (&mut self, program: &CompiledProgram) {
+        let CompiledProgram { id, proc, tracker, persist } = program;
+        let context = &mut self.context;
+        context.program = *id;
 
         // Actually Execute
         println!("Executing...");
         let start = Instant::now();
-        proc_exec(&proc, &mut context);
+        proc_exec(proc, context);
         println!("elapsed: {} s", start.elapsed().as_secs_f64());
 
+        // the buffers of changed variables are left as is for the next run
+        for p in persist.iter() {
+            if context.has_buffer(p) { context.persistent.insert(*p); }
+        }
+
         // From all dep list, get variables
         let dep_list = ret_dep_list();
         for st in dep_list.iter() {
-            self.result.insert(st.clone(), Arc::new(context.read_buffer(st)));
-            self.result_shape.insert(st.clone(), tracker.get_shape(st).clone());
+            if !context.has_buffer(st) { continue; }
+            self.result.insert(*st, Arc::new(context.read_buffer(st)));
+            self.result_shape.insert(*st, tracker.get_shape(st).clone());
         }
     }
 
-    fn get_tensor (&self, id: &String) -> ValueData {
+    fn release (&mut self, id: usize) {
+        self.context.release(id);
+    }
+
+    fn get_tensor (&self, id: VarId) -> ValueData {
         // not implemented yet
-        if let Some(data) = self.result.get(id) {
+        if let Some(data) = self.result.get(&id) {
             ValueData {
-                id: id.clone(),
-                dim: self.result_shape.get(id).unwrap().clone(),
+                id: id,
+                dim: self.result_shape.get(&id).unwrap().clone(),
                 data: data.clone(), 
                 is_none: false
             }
@@ -154,6 +171,7 @@ fn exec (cmd: &Kernels, opencl_context: &mut OpenCLContext) {
         Kernels::DotProd { .. } => { execute_dot_prod(opencl_context, cmd); },
         Kernels::Reduce { .. } => { execute_reduce(opencl_context, cmd); },
         Kernels::Movement { .. } => { execute_movement(opencl_context, cmd); },
+        Kernels::Rand { .. } => { execute_rand(opencl_context, cmd); },
         Kernels::ElwExpr { .. } => { execute_elw_expr(opencl_context, cmd); } ,
         Kernels::DPElwExpr { .. } => { execute_fuse_dp_elw(opencl_context, cmd); },
         Kernels::ReduceElwExpr { .. } => { execute_fuse_reduce_elw(opencl_context, cmd); },
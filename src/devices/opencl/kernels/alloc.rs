@@ -3,7 +3,11 @@ use crate::{devices::context::OpenCLContext, kernel_decl::Kernels};
 pub fn execute_alloc (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
     match cmd {
         Kernels::Alloc { id, size, content } => {
+            if opencl_context.warmup { return; }
+
             if let Some(c) = content {
+                // the buffer still holds the value from the previous run; only the first alloc is skipped
+                if opencl_context.persistent.remove(id) { return; }
                 assert_eq!(c.len(), *size, "Size of content is not equal size of alloc (opencl not supported)");
                 opencl_context.write_buffer(id, c);
             }
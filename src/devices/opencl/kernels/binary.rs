@@ -19,6 +19,7 @@ pub fn execute_binary (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
             let kernel_name = format!("_{}", id);
             let parsed_args = get_inputs_args(vec![a, b], vec![res]);
 
+            let warmup = opencl_context.warmup;
             let (
                 buffers, 
                 mut e_kernel, 
@@ -38,6 +39,8 @@ pub fn execute_binary (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
                 )          
             });
 
+            if warmup { return; } // only compile the kernel
+
             let kernel_event = unsafe {
                 for id in parsed_args {
                     e_kernel.set_arg(buffers.get(&id).unwrap());
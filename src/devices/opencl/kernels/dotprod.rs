@@ -3,14 +3,15 @@ use opencl3::types::cl_int;
 
 pub fn execute_dot_prod (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
     match cmd {
-        Kernels::DotProd { id, a, b, res, a_shape, res_shape, .. } => {
-            let batch_size = a_shape.0;            
+        Kernels::DotProd { id, a, b, res, a_shape, res_shape, batch_size, batch_stride, .. } => {
+            let rows = a_shape.0;
             let input_size = a_shape.1; 
             let output_size = res_shape.1;
 
             let kernel_name = format!("_{}", id);
             let parsed_args = get_inputs_args(vec![a, b], vec![res]);
 
+            let warmup = opencl_context.warmup;
             let (
                 buffers, 
                 mut e_kernel, 
@@ -28,13 +29,16 @@ pub fn execute_dot_prod (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
                     int tx = get_global_id(0); // Column index in C; --> output size
                     int ty = get_global_id(1); // Row index in C;    --> batch size
 
+                    int _batch = ty / {}; // batches are stacked along the rows of C
+                    int _row = ty % {};
+
                     float value = 0.0f;
                     for (int k = 0; k < wA; ++k) {{
-                        int _x = ty;
+                        int _x = _batch * {} + _row;
                         int _y = k;
                         float elementA = {};
 
-                        _x = k;
+                        _x = _batch * {} + k;
                         _y = tx;
                         float elementB = {};
                         value += elementA * elementB;
@@ -47,12 +51,18 @@ pub fn execute_dot_prod (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
                 "#, 
                     kernel_name, 
                     args.join(","),
+                    rows,
+                    rows,
+                    batch_stride.0,
                     a.to_opencl(),
+                    batch_stride.1,
                     b.to_opencl(),
                     res.to_opencl()
                 )          
             });
 
+            if warmup { return; } // only compile the kernel
+
             let kernel_event = unsafe {
                 for id in parsed_args {
                     e_kernel.set_arg(buffers.get(&id).unwrap());
@@ -63,7 +73,7 @@ pub fn execute_dot_prod (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
 
                 e_kernel
                     .set_global_work_size(output_size)
-                    .set_global_work_size(batch_size)
+                    .set_global_work_size(batch_size * rows)
                     .enqueue_nd_range(&queue)
                     .expect("Can't create execute kernel")
             };
@@ -39,7 +39,10 @@ impl Input {
         match self {
             Input::Constant { val } => val.to_string(),
             Input::Mat { mat } => mat.to_opencl(),
-            Input::ConcatMatrix { .. } => todo!(), // not doing this one yet because might be multi-lined or something
+            // select; only the taken side is read, as the access of the other side may be out of bounds (ex: pad)
+            Input::ConcatMatrix { id_one, id_two, conditional } => {
+                format!("({} ? {} : {})", conditional.to_opencl(), id_two.to_opencl(), id_one.to_opencl())
+            },
             Input::Temp => "_temp_var".to_string()
         }
     }
@@ -6,13 +6,18 @@ pub fn execute_fuse_dp_elw (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
     match cmd {
         Kernels::DPElwExpr { id, kernels, a_shape, res_shape, .. } => {
             // get important information
-            let batch_size = a_shape.0;            
+            let rows = a_shape.0;
             let input_size = a_shape.1; 
             let output_size = res_shape.1;
+            let (batch_size, batch_stride) = match kernels.first() {
+                Some(Kernels::DotProd { batch_size, batch_stride, .. }) => (*batch_size, *batch_stride),
+                _ => panic!("First command is not a DP operation!")
+            };
 
             let kernel_name = format!("_{}", id);
             let parsed_args = get_inputs_args(cmd.get_inputs(), cmd.get_outputs());
 
+            let warmup = opencl_context.warmup;
             let (
                 buffers, 
                 mut e_kernel, 
@@ -45,13 +50,16 @@ pub fn execute_fuse_dp_elw (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
                     int tx = get_global_id(0); // Column index in C; --> output size
                     int ty = get_global_id(1); // Row index in C;    --> batch size
 
+                    int _batch = ty / {}; // batches are stacked along the rows of C
+                    int _row = ty % {};
+
                     float value = 0.0f;
                     for (int k = 0; k < wA; ++k) {{
-                        int _x = ty;
+                        int _x = _batch * {} + _row;
                         int _y = k;
                         float elementA = {};
 
-                        _x = k;
+                        _x = _batch * {} + k;
                         _y = tx;
                         float elementB = {};
                         value += elementA * elementB;
@@ -69,13 +77,19 @@ pub fn execute_fuse_dp_elw (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
                 "#, 
                     kernel_name, 
                     args.join(","),
+                    rows,
+                    rows,
+                    batch_stride.0,
                     a_dot.to_opencl(),
+                    batch_stride.1,
                     b_dot.to_opencl(),
                     res_dot.to_opencl(),
                     cl_elw_kernels_to_body(&kernels)
                 )          
             });
 
+            if warmup { return; } // only compile the kernel
+
             let kernel_event = unsafe {
                 for id in parsed_args {
                     e_kernel.set_arg(buffers.get(&id).unwrap());
@@ -85,7 +99,7 @@ pub fn execute_fuse_dp_elw (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
 
                 e_kernel
                     .set_global_work_size(output_size)
-                    .set_global_work_size(batch_size)
+                    .set_global_work_size(batch_size * rows)
                     .enqueue_nd_range(&queue)
                     .expect("Can't create execute kernel")
             };
@@ -28,6 +28,7 @@ pub fn execute_elw_expr (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
             let outs = cmd.get_outputs();
             let parsed_args = get_inputs_args(inps, outs);
 
+            let warmup = opencl_context.warmup;
             let (
                 buffers,
                 mut e_kernel,
@@ -48,6 +49,8 @@ pub fn execute_elw_expr (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
                 ) 
             });
 
+            if warmup { return; } // only compile the kernel
+
             let kernel_event = unsafe {
                 for id in parsed_args {
                     e_kernel.set_arg(buffers.get(&id).unwrap());
@@ -7,6 +7,7 @@ pub fn execute_fuse_reduce_elw (opencl_context: &mut OpenCLContext, cmd: &Kernel
             let kernel_name = format!("_{}", id);
             let parsed_args = get_inputs_args(cmd.get_inputs(), cmd.get_outputs());
 
+            let warmup = opencl_context.warmup;
             let (
                 buffers, 
                 mut e_kernel, 
@@ -42,7 +43,7 @@ pub fn execute_fuse_reduce_elw (opencl_context: &mut OpenCLContext, cmd: &Kernel
                     int local_size = get_local_size(0);
 
                     // Load data into local memory
-                    scratch[_y] = (_y < l_size) ? {} : 0.0f;
+                    scratch[_y] = (_y < l_size) ? {} : {};
 
                     barrier(CLK_LOCAL_MEM_FENCE); // waits until transfer to local memory is all finished
 
@@ -67,12 +68,15 @@ pub fn execute_fuse_reduce_elw (opencl_context: &mut OpenCLContext, cmd: &Kernel
                     kernel_name, 
                     args.join(","),
                     a_rd.to_opencl(),
+                    op_rd.init_opencl(),
                     op_rd.to_opencl("scratch[_y]".to_string(), "scratch[_y + offset]".to_string()),
                     res_rd.to_opencl(),
                     cl_elw_kernels_to_body(&kernels)
                 )          
             });
 
+            if warmup { return; } // only compile the kernel
+
             let kernel_event = unsafe {
                 let local_size = (((*reduce_size as f32) / 2.0).ceil() as usize) * 2;
 
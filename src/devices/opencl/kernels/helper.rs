@@ -1,20 +1,19 @@
-use crate::{kernel_decl::{Input, Output}};
+use crate::{kernel_decl::{Input, Output}, VarId};
 
 // this could be replaced by kernel helper if I am being honest
-pub fn get_inputs_args (inputs: Vec<&Input>, output: Vec<&Output>) -> Vec<String> {
-    let mut t: Vec<String> = vec![];
+pub fn get_inputs_args (inputs: Vec<&Input>, output: Vec<&Output>) -> Vec<VarId> {
+    let mut t: Vec<VarId> = vec![];
     for arg in inputs.iter() {
         match arg {
-            Input::ConcatMatrix { .. } => {
-                todo!()
+            Input::ConcatMatrix { .. } | Input::Mat { .. } => {
+                for id in arg.get_id() {
+                    if !t.contains(id) {
+                        t.push(*id)
+                    }
+                }
             },
             Input::Constant { .. } => { },
             Input::Temp { } => {},
-            Input::Mat { mat } => {
-                if !t.contains(&mat.id) {
-                    t.push(mat.id.clone())
-                }
-            }
         }
     }
 
@@ -22,7 +21,7 @@ pub fn get_inputs_args (inputs: Vec<&Input>, output: Vec<&Output>) -> Vec<String
         match out {
             Output::Mat { mat } => {
                 if !t.contains(&mat.id) {
-                    t.push(mat.id.clone());
+                    t.push(mat.id);
                 }
             },
             Output::Temp {} => {}
@@ -6,6 +6,7 @@ pub mod unary;
 pub mod expression;
 pub mod helper;
 pub mod alloc;
+pub mod rand;
 
 pub mod fuse_elw;
 pub mod fuse_dp_elw;
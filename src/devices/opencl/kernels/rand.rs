@@ -0,0 +1,84 @@
+use crate::{devices::{context::OpenCLContext, helper::get_inputs_args}, kernel_decl::{Input, Kernels, Output}, RandDist};
+
+impl RandDist {
+    // maps the hashed words _c0, _c1 to a sample; must match RandDist::eval_cpu
+    pub fn to_opencl (&self) -> String {
+        let unit = |w: &str| format!("((float)({} >> 8) * (1.0f / 16777216.0f))", w);
+        match *self {
+            RandDist::Uniform { low, high } => format!("({:?}f + {} * {:?}f)", low, unit("_c0"), high - low),
+            RandDist::Normal { mean, std } => format!(
+                "({:?}f + {:?}f * sqrt(-2.0f * log({} + (1.0f / 16777216.0f))) * cos({:?}f * {}))",
+                mean, std, unit("_c0"), 2.0 * std::f32::consts::PI, unit("_c1")
+            ),
+            RandDist::Bernoulli { p } => format!("({} < {:?}f ? 1.0f : 0.0f)", unit("_c0"), p),
+            RandDist::RandInt { low, high } => format!("(float)({} + (int)mul_hi(_c0, {}u))", low, (high - low) as u32)
+        }
+    }
+}
+
+// Philox4x32-10; must match the reference (cpu/kernels/rand.rs)
+// the seed, offset and epoch are read from state at _global_id = 0, 1 and 2, before the actual _global_id is declared
+pub fn cl_rand_to_body (state: &Input, res: &Output, dist: &RandDist) -> String {
+    format!(r#"
+                    float _seed, _offset, _epoch;
+                    {{ const size_t _global_id = 0; _seed = {state}; }}
+                    {{ const size_t _global_id = 1; _offset = {state}; }}
+                    {{ const size_t _global_id = 2; _epoch = {state}; }}
+                    const size_t _global_id = get_global_id(0);
+
+                    uint _k0 = (uint)_seed, _k1 = (uint)_epoch;
+                    uint _c0 = (uint)_global_id, _c1 = (uint)_offset, _c2 = 0, _c3 = 0;
+                    for (int _r = 0; _r < 10; _r++) {{
+                        if (_r > 0) {{ _k0 += 0x9E3779B9; _k1 += 0xBB67AE85; }}
+                        uint _hi0 = mul_hi(0xD2511F53u, _c0), _lo0 = 0xD2511F53u * _c0;
+                        uint _hi1 = mul_hi(0xCD9E8D57u, _c2), _lo1 = 0xCD9E8D57u * _c2;
+                        _c0 = _hi1 ^ _c1 ^ _k0; _c1 = _lo1;
+                        _c2 = _hi0 ^ _c3 ^ _k1; _c3 = _lo0;
+                    }}
+                    {res} = {sample};"#,
+        state = state.to_opencl(),
+        res = res.to_opencl(),
+        sample = dist.to_opencl()
+    )
+}
+
+pub fn execute_rand (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
+    if let Kernels::Rand { id, state, res, dist, size } = cmd {
+        let kernel_name = format!("_{}", id);
+        let parsed_args = get_inputs_args(vec![state], vec![res]);
+
+        let warmup = opencl_context.warmup;
+
+        let (
+            buffers, 
+            mut e_kernel, 
+            queue
+        ) = opencl_context.get_kernel(&kernel_name, || {
+            format!(r#"
+            __kernel void {} (
+                {}
+            ) {{
+                {}
+            }}
+            "#, 
+                kernel_name, 
+                parsed_args.iter().map(|v| format!("__global float* {}", v)).collect::<Vec<String>>().join(","),
+                cl_rand_to_body(state, res, dist)
+            )          
+        });
+
+        if warmup { return; } // only compile the kernel
+
+        let kernel_event = unsafe {
+            for id in parsed_args.iter() {
+                e_kernel.set_arg(buffers.get(id).unwrap());
+            }
+            e_kernel
+                .set_global_work_size(*size)
+                .enqueue_nd_range(queue)
+                .expect("Can't create execute kernel")
+        };
+
+        kernel_event.wait().expect("Can't wait for kernel event");
+    }
+}
@@ -6,6 +6,16 @@ impl ReduceOp {
         match self {
             ReduceOp::Sum => format!("{} += {};", orig, new),
             ReduceOp::Max => format!("{} = max({}, {});", orig, orig, new),
+            ReduceOp::Prod => format!("{} *= {};", orig, new),
+        }
+    }
+
+    // pads the local memory past the reduce size
+    pub fn init_opencl (&self) -> String {
+        match self {
+            ReduceOp::Sum => "0.0f".to_string(),
+            ReduceOp::Max => "-INFINITY".to_string(),
+            ReduceOp::Prod => "1.0f".to_string()
         }
     }
 }
@@ -16,6 +26,7 @@ pub fn execute_reduce (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
             let kernel_name = format!("_{}", id);
             let parsed_args = get_inputs_args(vec![a], vec![res]);
 
+            let warmup = opencl_context.warmup;
             let (
                 buffers, 
                 mut e_kernel, 
@@ -37,7 +48,7 @@ pub fn execute_reduce (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
                     int local_size = get_local_size(0);
 
                     // Load data into local memory
-                    scratch[_y] = (_y < l_size) ? {} : 0.0f;
+                    scratch[_y] = (_y < l_size) ? {} : {};
 
                     barrier(CLK_LOCAL_MEM_FENCE); // waits until transfer to local memory is all finished
 
@@ -58,11 +69,14 @@ pub fn execute_reduce (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
                     kernel_name, 
                     args.join(","),
                     a.to_opencl(),
+                    op.init_opencl(),
                     op.to_opencl("scratch[_y]".to_string(), "scratch[_y + offset]".to_string()),
                     res.to_opencl()
                 )          
             });
 
+            if warmup { return; } // only compile the kernel
+
             let kernel_event = unsafe {
                 let local_size = (((*reduce_size as f32) / 2.0).ceil() as usize) * 2;
 
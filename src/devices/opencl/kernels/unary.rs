@@ -26,6 +26,7 @@ pub fn execute_unary (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
             let kernel_name = format!("_{}", id);
             let parsed_args = get_inputs_args(vec![a], vec![res]);
 
+            let warmup = opencl_context.warmup;
             let (
                 buffers, 
                 mut e_kernel, 
@@ -45,6 +46,8 @@ pub fn execute_unary (opencl_context: &mut OpenCLContext, cmd: &Kernels) {
                 )          
             });
 
+            if warmup { return; } // only compile the kernel
+
             let kernel_event = unsafe {
                 for id in parsed_args {
                     e_kernel.set_arg(buffers.get(&id).unwrap());
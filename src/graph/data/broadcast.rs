@@ -1,4 +1,4 @@
-use crate::{ir_b_add, ir_b_id, NodeTrait, Tensor, Value};
+use crate::{ir_b_add, ir_b_id, NodeTrait, Tensor, Value, VarId};
 
 #[derive(Clone)]
 pub struct BroadcastNode {
@@ -14,7 +14,7 @@ impl NodeTrait for BroadcastNode {
             return v;
         } 
         let p_val = self.parent.forward();
-        let res_val = c_broadcast(&p_val, self.repeat, self.dim, self.val.as_ref().map(|v| v.id.clone()));
+        let res_val = c_broadcast(&p_val, self.repeat, self.dim, self.val.as_ref().map(|v| v.id));
         self.val = Some(res_val.clone());
         res_val
     }
@@ -27,7 +27,7 @@ impl NodeTrait for BroadcastNode {
 
     fn backward (&mut self, grad:Value) {
         self.parent.n.borrow_mut().backward(
-            grad.to_node().sum(self.dim as i32).forward()
+            grad.to_node().sum(self.dim as i32).unsqueeze(self.dim as i32).forward()
         );
     }      
 
@@ -88,14 +88,14 @@ for i in 0..self.d.len() {
 }
  */
 
-fn c_broadcast (p: &Value, r: usize, dim: usize, id: Option<String>) -> Value {
+fn c_broadcast (p: &Value, r: usize, dim: usize, id: Option<VarId>) -> Value {
     // check if broadcastable
     let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
     ir_b_add(crate::IRCmds::Broadcast { 
-        a: p.id.clone(), 
+        a: p.id, 
         r,
         dim,
-        res: id.clone()
+        res: id
     });
 
     let mut r_dim = p.dim.clone();
@@ -1,7 +1,7 @@
 use crate::ir::IRCmds;
 use crate::core::node::{Tensor, NodeTrait};
 use crate::core::value::Value;
-use crate::{ir_b_add, ir_b_id};
+use crate::{ir_b_add, ir_b_id, VarId};
 
 // ============ Clone Node (operation) ============ 
 #[derive(Clone)]
@@ -24,7 +24,7 @@ impl NodeTrait for ConcatNode {
             values.push(n);
         }
 
-        let res = c_concat(values, self.dim, self.val.as_ref().map(|v| v.id.clone()));
+        let res = c_concat(values, self.dim, self.val.as_ref().map(|v| v.id));
         self.val = Some(res.clone());
         res
     }
@@ -42,7 +42,7 @@ impl NodeTrait for ConcatNode {
         let mut c: usize = 0;
         for i in 0..self.nodes.len() {
             let d_interest = self.nodes[i].dim()[self.dim];
-            let g = grad.to_node().r(c..(c+d_interest), self.dim as i32).forward();
+            let g = grad.to_node().narrow(self.dim as i32, c, d_interest).forward();
             self.nodes[i].n.borrow_mut().backward(g);
             c += d_interest;
         } 
@@ -72,7 +72,7 @@ pub fn concat (nodes: Vec<Tensor>, dim: i32) -> Tensor { // light wrapper in aut
 }
 
 // ============= Concat Core Functionality ============
-fn c_concat (nodes: Vec<Value>, dim: usize, id: Option<String>) -> Value { // main function used here
+fn c_concat (nodes: Vec<Value>, dim: usize, id: Option<VarId>) -> Value { // main function used here
     // check dimension
     let mut first_dim: Vec<usize> = vec![];
     for i in nodes.iter() {
@@ -91,14 +91,14 @@ fn c_concat (nodes: Vec<Value>, dim: usize, id: Option<String>) -> Value { // ma
 
     // add to IR
     // continously apply concat
-    let mut prev_node_id = nodes[0].id.clone();
+    let mut prev_node_id = nodes[0].id;
     let mut total_d = nodes[0].dim.clone();
     let last_id = id.or_else(|| Some(ir_b_id())).unwrap();
 
     for i in 1..nodes.len() {
-        let node_id = nodes[i].id.clone();
+        let node_id = nodes[i].id;
         let new_id = if i == nodes.len()-1 { 
-            last_id.clone()
+            last_id
         } else { 
             ir_b_id() 
         };
@@ -107,7 +107,7 @@ fn c_concat (nodes: Vec<Value>, dim: usize, id: Option<String>) -> Value { // ma
             a: prev_node_id,
             b: node_id,
             dim: dim.clone(),
-            res: new_id.clone()
+            res: new_id
         });
 
         prev_node_id = new_id;
@@ -1,5 +1,5 @@
 use crate::core::node::{Tensor, NodeTrait};
-use crate::{ir_b_add, ir_b_id, Value, IRCmds};
+use crate::{ir_b_add, ir_b_id, Value, IRCmds, VarId};
 
 #[derive(Clone)]
 pub struct ContigiousNode {
@@ -14,7 +14,7 @@ impl NodeTrait for ContigiousNode {
         }
 
         let p_val = self.parent.forward();  
-        let res_val = c_contigious(&p_val, self.val.as_ref().map(|v| v.id.clone()));
+        let res_val = c_contigious(&p_val, self.val.as_ref().map(|v| v.id));
         self.val = Some(res_val.clone());
         res_val 
     }        
@@ -46,11 +46,11 @@ impl Tensor {
     }
 }
 
-fn c_contigious (a: &Value, id: Option<String>) -> Value {
+fn c_contigious (a: &Value, id: Option<VarId>) -> Value {
     let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
     ir_b_add(IRCmds::Contigious { 
-        a: a.id.clone(),
-        res: id.clone()
+        a: a.id,
+        res: id
     });
 
     Value {
@@ -1,7 +1,6 @@
 use std::ops::Range;
 
-use crate::{concat, ir_b_add, ir_b_id};
-use crate::core::autodiff;
+use crate::{concat, ir_b_add, ir_b_id, VarId};
 use crate::core::node::{Tensor, NodeTrait};
 use crate::core::value::Value;
 use crate::ir::IRCmds;
@@ -23,7 +22,7 @@ impl NodeTrait for IndexNode {
             return v;
         }
         let p_val = self.parent.forward();
-        let res_val = c_idx(&p_val, self.idx, self.dim, self.val.as_ref().map(|i| i.id.clone()));
+        let res_val = c_idx(&p_val, self.idx, self.dim, self.val.as_ref().map(|i| i.id));
         self.val = Some(res_val.clone());
         res_val
     }
@@ -37,18 +36,20 @@ impl NodeTrait for IndexNode {
     fn backward (&mut self, grad: Value) {
         let i_dim = self.parent.dim().clone();
 
+        // zeros before and after the index (along self.dim); skip if empty
         let mut zero_first_dim = i_dim.clone();
         zero_first_dim[self.dim] = self.idx;
 
         let mut zero_second_dim = i_dim.clone();
-        zero_second_dim[self.dim] = i_dim[0] - self.idx - 1;
+        zero_second_dim[self.dim] = i_dim[self.dim] - self.idx - 1;
 
-        let first_zero = Value::zeros(zero_first_dim).to_node();
-        let second_zero = Value::zeros(zero_second_dim).to_node();
-        let grad_n = grad.to_node();
+        let mut grads = vec![];
+        if zero_first_dim[self.dim] > 0 { grads.push(Value::zeros(zero_first_dim).to_node()); }
+        grads.push(grad.to_node().unsqueeze(self.dim as i32));
+        if zero_second_dim[self.dim] > 0 { grads.push(Value::zeros(zero_second_dim).to_node()); }
 
         self.parent.n.borrow_mut().backward(
-            concat(vec![first_zero, grad_n.unsqueeze(0), second_zero], self.dim as i32).forward()
+            concat(grads, self.dim as i32).forward()
         ); 
     }
 
@@ -80,23 +81,20 @@ impl Tensor {
         })
     }
 
-    // add more using permute & map
+    // select range at dim
     pub fn r (&self, r: Range<usize>, dim: i32) -> Tensor {
-        autodiff::concat(
-            r.map(|i| self.i(i, dim).unsqueeze(dim)).collect::<Vec<Tensor>>(), 
-            dim
-        )
+        self.narrow(dim, r.start, r.len())
     }
 }
 
 // ============= Index Node Core Func ============
-fn c_idx (a: &Value, idx: usize, dim: usize, id: Option<String>) -> Value {
+fn c_idx (a: &Value, idx: usize, dim: usize, id: Option<VarId>) -> Value {
     let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
     ir_b_add(IRCmds::Index { 
-        a: a.id.clone(), 
+        a: a.id, 
         index: idx.clone(), 
         dim: dim.clone(),
-        res: id.clone()
+        res: id
     });
 
     // find target dim
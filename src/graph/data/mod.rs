@@ -4,4 +4,5 @@ pub mod view;
 pub mod permute;    
 pub mod repeat;     
 pub mod broadcast;
-pub mod contigious;
\ No newline at end of file
+pub mod contigious;pub mod unfold;
+pub mod pad;
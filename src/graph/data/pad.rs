@@ -0,0 +1,149 @@
+use crate::{autodiff, ir_b_add, ir_b_id, IRCmds, NodeTrait, PadMode, Tensor, Value, VarId};
+
+/**
+ * Padding along any dims: constant, reflect or replicate
+ * Lowered to access expressions (a conditional for constant pads, a remapped index otherwise), so no padded copy is materialized
+ * The gradient is the center slice; reflect and replicate also add back the gradient of the elements they copied
+ */
+#[derive(Clone)]
+pub struct PadNode {
+    parent: Tensor,
+    pads: Vec<(usize, usize)>, // (before, after) for every dim
+    mode: PadMode,
+    val: Option<Value>
+}
+
+impl NodeTrait for PadNode {
+    fn forward (&mut self) -> Value {
+        if let Some(v) = self.val() {
+            return v;
+        }
+        let p_val = self.parent.forward();
+        let res_val = c_pad(&p_val, &self.pads, self.mode, self.val.as_ref().map(|v| v.id));
+        self.val = Some(res_val.clone());
+        res_val
+    }
+
+    fn dim (&self) -> Vec<usize> {
+        self.parent.dim().iter().zip(self.pads.iter()).map(|(d, (b, a))| d + b + a).collect()
+    }
+
+    fn backward (&mut self, grad: Value) {
+        let p_dim = self.parent.dim();
+        let mut g = grad.to_node();
+
+        for (dim, &(before, after)) in self.pads.iter().enumerate() {
+            if before + after == 0 { continue }
+            let len = p_dim[dim];
+            let d = dim as i32;
+            let mut terms = vec![g.narrow(d, before, len)];
+
+            match self.mode {
+                PadMode::Constant(_) => {},
+                PadMode::Replicate => {
+                    // every padded element is a copy of the edge
+                    if before > 0 {
+                        terms.push(g.narrow(d, 0, before).sum(d).unsqueeze(d).pad_dim(dim, 0, len - 1, PadMode::Constant(0.0)));
+                    }
+                    if after > 0 {
+                        terms.push(g.narrow(d, before + len, after).sum(d).unsqueeze(d).pad_dim(dim, len - 1, 0, PadMode::Constant(0.0)));
+                    }
+                },
+                PadMode::Reflect => {
+                    // padded element i (before) is a copy of before - i; (after) of len - 2 - i
+                    if before > 0 {
+                        let flipped = flip(&g.narrow(d, 0, before), dim);
+                        terms.push(flipped.pad_dim(dim, 1, len - 1 - before, PadMode::Constant(0.0)));
+                    }
+                    if after > 0 {
+                        let flipped = flip(&g.narrow(d, before + len, after), dim);
+                        terms.push(flipped.pad_dim(dim, len - 1 - after, 1, PadMode::Constant(0.0)));
+                    }
+                }
+            }
+            g = terms.into_iter().reduce(|a, b| a + b).unwrap();
+        }
+
+        self.parent.n.borrow_mut().backward(g.forward());
+    }
+
+    fn val (&self) -> Option<Value> {
+        self.parent.val()?;
+        self.val.clone()
+    }
+
+    fn deep_copy (&self) -> Box<dyn NodeTrait> {
+        Box::new(self.clone())
+    }
+}
+
+// reverse along dim (pads are small, so it's a concat of single indices)
+fn flip (x: &Tensor, dim: usize) -> Tensor {
+    let n = x.dim()[dim];
+    if n == 1 { return x.clone() }
+    autodiff::concat((0..n).rev().map(|i| x.i(i, dim as i32).unsqueeze(dim as i32)).collect(), dim as i32)
+}
+
+// ================== Creating Node ==================
+impl Tensor {
+    // pads[i] = (before, after) of the trailing dims, in order; ex: [N, C, H, W].pad(&[(1, 1), (2, 2)], ..) pads H by 1 and W by 2
+    pub fn pad (&self, pads: &[(usize, usize)], mode: PadMode) -> Tensor {
+        let p_dim = self.dim();
+        assert!(pads.len() <= p_dim.len(), "More pads than dims");
+
+        let mut all_pads = vec![(0, 0); p_dim.len() - pads.len()];
+        all_pads.extend_from_slice(pads);
+
+        for (d, &(before, after)) in p_dim.iter().zip(all_pads.iter()) {
+            if let PadMode::Reflect = mode {
+                assert!(before < *d && after < *d, "Reflect padding must be smaller than the dim");
+            }
+        }
+        if all_pads.iter().all(|(b, a)| b + a == 0) { return self.clone() }
+
+        Tensor::new(PadNode {
+            parent: self.clone(),
+            pads: all_pads,
+            mode,
+            val: None
+        })
+    }
+
+    // pad a single dim
+    pub fn pad_dim (&self, dim: usize, before: usize, after: usize, mode: PadMode) -> Tensor {
+        let rank = self.dim().len();
+        assert!(dim < rank, "invalid dimension!");
+        let mut pads = vec![(0, 0); rank - dim];
+        pads[0] = (before, after);
+        self.pad(&pads, mode)
+    }
+}
+
+// ============= Pad Node Core Func ============
+fn c_pad (a: &Value, pads: &[(usize, usize)], mode: PadMode, id: Option<VarId>) -> Value {
+    let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
+    let last = pads.iter().rposition(|(b, a)| b + a > 0).unwrap();
+
+    // a single Pad cmd per padded dim
+    let mut prev = a.id;
+    let mut d = a.dim.clone();
+    for (dim, &(before, after)) in pads.iter().enumerate() {
+        if before + after == 0 { continue }
+        let res = if dim == last { id } else { ir_b_id() };
+        ir_b_add(IRCmds::Pad {
+            a: prev,
+            dim,
+            before,
+            after,
+            mode,
+            res: res
+        });
+        d[dim] += before + after;
+        prev = res;
+    }
+
+    Value {
+        dim: d,
+        id
+    }
+}
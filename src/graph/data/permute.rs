@@ -1,4 +1,4 @@
-use crate::{core::{node::{NodeTrait, Tensor}, value::Value}, ir_b_add, ir_b_id};
+use crate::{core::{node::{NodeTrait, Tensor}, value::Value}, ir_b_add, ir_b_id, VarId};
 use crate::ir::IRCmds;
 
 // ============ Permute Node (operation) ============ 
@@ -16,7 +16,7 @@ impl NodeTrait for PermuteNode {
             return v;
         }
         let p_val = self.parent.forward();
-        let res_val = c_permute(&p_val, self.permute.clone(), self.val.as_ref().map(|v| v.id.clone()));
+        let res_val = c_permute(&p_val, self.permute.clone(), self.val.as_ref().map(|v| v.id));
         self.val = Some(res_val.clone());
         res_val
     }
@@ -77,7 +77,7 @@ impl Tensor {
 }
 
 // ============= Permute Node Core Func ============
-fn c_permute (a: &Value, permute: Vec<usize>, id: Option<String>) -> Value {
+fn c_permute (a: &Value, permute: Vec<usize>, id: Option<VarId>) -> Value {
     // find if dim mismatch
     assert_eq!(permute.len(), a.dim.len(), "Permute dimension mismatch");
     for i in permute.iter() {
@@ -97,9 +97,9 @@ fn c_permute (a: &Value, permute: Vec<usize>, id: Option<String>) -> Value {
 
     let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
     ir_b_add(IRCmds::Permute { 
-        a: a.id.clone(), 
+        a: a.id, 
         p: permute.clone(), 
-        res: id.clone() 
+        res: id 
     });
 
     // find dim
@@ -0,0 +1,304 @@
+use crate::{autodiff, ir_b_add, ir_b_id, IRCmds, NodeTrait, PadMode, Tensor, Value, VarId};
+
+/**
+ * Sliding windows along a dim (the building block of im2col)
+ * [.., L, ..] --> [.., windows, .., size] where res[.., w, .., k] = a[.., w * step + k * dilation, ..]
+ * Only data movement; the kernel tracker maps it back to the source index
+ */
+pub fn unfold_windows (len: usize, size: usize, step: usize, dilation: usize) -> usize {
+    assert!(size > 0 && step > 0 && dilation > 0, "Unfold size, step and dilation must be positive");
+    let span = dilation * (size - 1) + 1;
+    assert!(span <= len, "Unfold window ({}) is larger than the dim ({})", span, len);
+    (len - span) / step + 1
+}
+
+// ============ Unfold Node ============
+#[derive(Clone)]
+pub struct UnfoldNode {
+    parent: Tensor,
+    dim: usize,
+    size: usize,
+    step: usize,
+    dilation: usize,
+    val: Option<Value>
+}
+
+impl NodeTrait for UnfoldNode {
+    fn forward (&mut self) -> Value {
+        if let Some(v) = self.val() {
+            return v;
+        }
+        let p_val = self.parent.forward();
+        let res_val = c_unfold(&p_val, self.dim, self.size, self.step, self.dilation, self.val.as_ref().map(|v| v.id));
+        self.val = Some(res_val.clone());
+        res_val
+    }
+
+    fn dim (&self) -> Vec<usize> {
+        let mut d = self.parent.dim();
+        d[self.dim] = unfold_windows(d[self.dim], self.size, self.step, self.dilation);
+        d.push(self.size);
+        d
+    }
+
+    fn backward (&mut self, grad: Value) {
+        // overlapping windows sum back into the source
+        let len = self.parent.dim()[self.dim];
+        self.parent.n.borrow_mut().backward(c_fold(&grad, self.dim, len, self.step, self.dilation, None));
+    }
+
+    fn val (&self) -> Option<Value> {
+        self.parent.val()?;
+        self.val.clone()
+    }
+
+    fn deep_copy (&self) -> Box<dyn NodeTrait> {
+        Box::new(self.clone())
+    }
+}
+
+// ============ Fold Node ============
+// adjoint of unfold; forward is composed of data movement + adds, backward is a single unfold
+#[derive(Clone)]
+pub struct FoldNode {
+    parent: Tensor,
+    dim: usize,
+    len: usize,
+    step: usize,
+    dilation: usize,
+    val: Option<Value>
+}
+
+impl NodeTrait for FoldNode {
+    fn forward (&mut self) -> Value {
+        if let Some(v) = self.val() {
+            return v;
+        }
+        let p_val = self.parent.forward();
+        let res_val = c_fold(&p_val, self.dim, self.len, self.step, self.dilation, self.val.as_ref().map(|v| v.id));
+        self.val = Some(res_val.clone());
+        res_val
+    }
+
+    fn dim (&self) -> Vec<usize> {
+        let mut d = self.parent.dim();
+        d.pop();
+        d[self.dim] = self.len;
+        d
+    }
+
+    fn backward (&mut self, grad: Value) {
+        let size = *self.parent.dim().last().unwrap();
+        self.parent.n.borrow_mut().backward(c_unfold(&grad, self.dim, size, self.step, self.dilation, None));
+    }
+
+    fn val (&self) -> Option<Value> {
+        self.parent.val()?;
+        self.val.clone()
+    }
+
+    fn deep_copy (&self) -> Box<dyn NodeTrait> {
+        Box::new(self.clone())
+    }
+}
+
+// ============ Narrow Node ============
+// a[.., start..start+len, ..]. Forward is an unfold + index, backward zero pads instead of folding
+#[derive(Clone)]
+pub struct NarrowNode {
+    parent: Tensor,
+    dim: usize,
+    start: usize,
+    len: usize,
+    val: Option<Value>
+}
+
+impl NodeTrait for NarrowNode {
+    fn forward (&mut self) -> Value {
+        if let Some(v) = self.val() {
+            return v;
+        }
+        let p_val = self.parent.forward();
+        let res_val = c_narrow(&p_val, self.dim, self.start, self.len, self.val.as_ref().map(|v| v.id));
+        self.val = Some(res_val.clone());
+        res_val
+    }
+
+    fn dim (&self) -> Vec<usize> {
+        let mut d = self.parent.dim();
+        d[self.dim] = self.len;
+        d
+    }
+
+    fn backward (&mut self, grad: Value) {
+        let total = self.parent.dim()[self.dim];
+        let g = zero_pad(&grad.to_node(), self.dim, self.start, total - self.start - self.len);
+        self.parent.n.borrow_mut().backward(g.forward());
+    }
+
+    fn val (&self) -> Option<Value> {
+        self.parent.val()?;
+        self.val.clone()
+    }
+
+    fn deep_copy (&self) -> Box<dyn NodeTrait> {
+        Box::new(self.clone())
+    }
+}
+
+// ================== Creating Node ==================
+impl Tensor {
+    // sliding windows of `size` along dim, appended as the last dim
+    pub fn unfold (&self, dim: i32, size: usize, step: usize, dilation: usize) -> Tensor {
+        let p_dim = self.dim();
+        let dim = if dim < 0 { p_dim.len() as i32 + dim } else { dim } as usize;
+        assert!(dim < p_dim.len(), "invalid dimension!");
+        unfold_windows(p_dim[dim], size, step, dilation);
+
+        Tensor::new(UnfoldNode {
+            parent: self.clone(),
+            dim,
+            size,
+            step,
+            dilation,
+            val: None
+        })
+    }
+
+    // adjoint of unfold: sums the windows (last dim) back into a dim of length `len` (ex: col2im)
+    pub fn fold (&self, dim: i32, len: usize, step: usize, dilation: usize) -> Tensor {
+        let p_dim = self.dim();
+        let rank = p_dim.len() - 1;
+        let dim = if dim < 0 { rank as i32 + dim } else { dim } as usize;
+        assert!(dim < rank, "invalid dimension!");
+
+        let size = p_dim[rank];
+        let windows = p_dim[dim];
+        assert_eq!(unfold_windows(len, size, step, dilation), windows, "Fold length doesn't match the number of windows");
+
+        Tensor::new(FoldNode {
+            parent: self.clone(),
+            dim,
+            len,
+            step,
+            dilation,
+            val: None
+        })
+    }
+
+    // slice [start, start + len) along dim
+    pub fn narrow (&self, dim: i32, start: usize, len: usize) -> Tensor {
+        let p_dim = self.dim();
+        let dim = if dim < 0 { p_dim.len() as i32 + dim } else { dim } as usize;
+        assert!(dim < p_dim.len(), "invalid dimension!");
+        assert!(len > 0 && start + len <= p_dim[dim], "narrow out of bounds");
+        if len == p_dim[dim] { return self.clone() }
+
+        Tensor::new(NarrowNode {
+            parent: self.clone(),
+            dim,
+            start,
+            len,
+            val: None
+        })
+    }
+}
+
+// zeros before and after along dim
+pub(crate) fn zero_pad (x: &Tensor, dim: usize, before: usize, after: usize) -> Tensor {
+    fill_pad(x, dim, before, after, 0.0)
+}
+
+pub(crate) fn fill_pad (x: &Tensor, dim: usize, before: usize, after: usize, val: f32) -> Tensor {
+    x.pad_dim(dim, before, after, PadMode::Constant(val))
+}
+
+// step - 1 zeros after every element along dim: [.., n, ..] --> [.., n * step, ..]
+fn dilate (x: &Tensor, dim: usize, step: usize) -> Tensor {
+    if step == 1 { return x.clone() }
+    let mut d = x.dim().iter().map(|v| *v as i32).collect::<Vec<i32>>();
+    d[dim] = -1;
+    zero_pad(&x.unsqueeze(dim as i32 + 1), dim + 1, 0, step - 1).view(d)
+}
+
+// ============= Unfold Node Core Func ============
+fn c_unfold (a: &Value, dim: usize, size: usize, step: usize, dilation: usize, id: Option<VarId>) -> Value {
+    let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
+    ir_b_add(IRCmds::Unfold {
+        a: a.id,
+        dim,
+        size,
+        step,
+        dilation,
+        res: id
+    });
+
+    let mut d = a.dim.clone();
+    d[dim] = unfold_windows(d[dim], size, step, dilation);
+    d.push(size);
+
+    Value {
+        dim: d,
+        id
+    }
+}
+
+fn c_fold (a: &Value, dim: usize, len: usize, step: usize, dilation: usize, id: Option<VarId>) -> Value {
+    // place window offset k at k * dilation (every step), then sum
+    let x = a.to_node();
+    let size = *a.dim.last().unwrap();
+    let mut terms = (0..size).map(|k| {
+        let t = zero_pad(&dilate(&x.i(k, -1), dim, step), dim, k * dilation, 0);
+        let n = t.dim()[dim];
+        if n > len { t.narrow(dim as i32, 0, len) } else { zero_pad(&t, dim, 0, len - n) }
+    }).collect::<Vec<Tensor>>();
+
+    let last = terms.pop().unwrap().forward();
+    let rest = match terms.into_iter().reduce(|a, b| a + b) {
+        Some(t) => t.forward(),
+        None => autodiff::constant(0.0, last.dim.clone()).forward()
+    };
+
+    let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
+    ir_b_add(IRCmds::ElwAdd {
+        a: rest.id,
+        b: last.id,
+        res: id
+    });
+
+    Value {
+        dim: last.dim,
+        id
+    }
+}
+
+fn c_narrow (a: &Value, dim: usize, start: usize, len: usize, id: Option<VarId>) -> Value {
+    // [.., L, ..] --> [.., windows, .., len] --> [.., len, .., windows] --> pick window start
+    let u = c_unfold(a, dim, len, 1, 1, None);
+    let last = u.dim.len() - 1;
+    let mut p = (0..=last).collect::<Vec<usize>>();
+    p.swap(dim, last);
+
+    let p_id = ir_b_id();
+    ir_b_add(IRCmds::Permute {
+        a: u.id,
+        p,
+        res: p_id
+    });
+
+    let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
+    ir_b_add(IRCmds::Index {
+        a: p_id,
+        index: start,
+        dim: last,
+        res: id
+    });
+
+    let mut d = a.dim.clone();
+    d[dim] = len;
+
+    Value {
+        dim: d,
+        id
+    }
+}
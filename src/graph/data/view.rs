@@ -1,7 +1,7 @@
 use crate::ir::IRCmds;
 use crate::core::node::{Tensor, NodeTrait};
 use crate::core::value::Value;
-use crate::{ir_b_add, ir_b_id};
+use crate::{ir_b_add, ir_b_id, VarId};
 
 // ============ View Node ============ 
 #[derive(Clone)]
@@ -19,7 +19,7 @@ impl NodeTrait for ViewNode {
         }
         
         let v = self.parent.forward();
-        let res_val = c_view(&v, self.target_dim.clone(), self.val.as_ref().map(|v| v.id.clone()));
+        let res_val = c_view(&v, self.target_dim.clone(), self.val.as_ref().map(|v| v.id));
         self.val = Some(res_val.clone());
         res_val
     }
@@ -115,7 +115,7 @@ impl Tensor {
 }
 
 // ================== Core Functionality ================== 
-fn c_view (p: &Value, target_dim: Vec<usize>, id: Option<String>) -> Value {
+fn c_view (p: &Value, target_dim: Vec<usize>, id: Option<VarId>) -> Value {
     assert_eq!(
         target_dim.iter().product::<usize>(), 
         p.dim.iter().product::<usize>(), 
@@ -125,9 +125,9 @@ fn c_view (p: &Value, target_dim: Vec<usize>, id: Option<String>) -> Value {
     // add to IR
     let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
     ir_b_add(IRCmds::View { 
-        a: p.id.clone(), 
+        a: p.id, 
         target_dim: target_dim.clone(), 
-        res: id.clone() 
+        res: id 
     });
     
     Value {
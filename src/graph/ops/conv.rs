@@ -0,0 +1,80 @@
+use crate::{autodiff, Tensor};
+use crate::graph::data::unfold::zero_pad;
+
+/*
+ 2d convolutions as im2col + matmul
+ * im2col is two unfolds, so it stays pure data movement (indexing) and feeds the DotProd kernel directly
+ * conv_transpose2d is the adjoint: matmul then col2im (fold)
+ * groups are the batch dim of a batched matmul
+ * Backward passes come from the composed ops
+*/
+impl Tensor {
+    // x: [N, C, H, W], weight: [C_out, C / groups, kh, kw] --> [N, C_out, H_out, W_out]
+    pub fn conv2d (&self, weight: &Tensor, stride: usize, padding: usize, dilation: usize, groups: usize) -> Tensor {
+        let (x_dim, w_dim) = (self.dim(), weight.dim());
+        assert_eq!(x_dim.len(), 4, "conv2d input must be [N, C, H, W]");
+        assert_eq!(w_dim.len(), 4, "conv2d weight must be [C_out, C / groups, kh, kw]");
+
+        let (n, c) = (x_dim[0], x_dim[1]);
+        let (c_out, cg, kh, kw) = (w_dim[0], w_dim[1], w_dim[2], w_dim[3]);
+        assert!(groups > 0 && c % groups == 0 && c_out % groups == 0, "Channels must be divisible by groups");
+        assert_eq!(cg, c / groups, "conv2d weight channels must equal C / groups");
+
+        // ========== im2col ==========
+        let x = zero_pad(&zero_pad(self, 2, padding, padding), 3, padding, padding);
+        let cols = x.unfold(2, kh, stride, dilation).unfold(3, kw, stride, dilation); // [N, C, Ho, Wo, kh, kw]
+        let (ho, wo) = (cols.dim()[2], cols.dim()[3]);
+
+        let cols = cols
+            .permute(&vec![0, 2, 3, 1, 4, 5])
+            .view(vec![(n * ho * wo) as i32, groups as i32, (cg * kh * kw) as i32])
+            .permute(&vec![1, 0, 2]); // [G, N*Ho*Wo, C/G*kh*kw]
+
+        let w = weight
+            .view(vec![groups as i32, (c_out / groups) as i32, (cg * kh * kw) as i32])
+            .permute(&vec![0, 2, 1]); // [G, C/G*kh*kw, C_out/G]
+
+        autodiff::matmul(cols, w)
+            .permute(&vec![1, 0, 2])
+            .view(vec![n as i32, ho as i32, wo as i32, c_out as i32])
+            .permute(&vec![0, 3, 1, 2])
+    }
+
+    // x: [N, C, H, W], weight: [C, C_out / groups, kh, kw] --> [N, C_out, (H - 1) * stride - 2 * padding + dilation * (kh - 1) + 1, ..]
+    pub fn conv_transpose2d (&self, weight: &Tensor, stride: usize, padding: usize, dilation: usize, groups: usize) -> Tensor {
+        let (x_dim, w_dim) = (self.dim(), weight.dim());
+        assert_eq!(x_dim.len(), 4, "conv_transpose2d input must be [N, C, H, W]");
+        assert_eq!(w_dim.len(), 4, "conv_transpose2d weight must be [C, C_out / groups, kh, kw]");
+
+        let (n, c, h, w) = (x_dim[0], x_dim[1], x_dim[2], x_dim[3]);
+        let (cog, kh, kw) = (w_dim[1], w_dim[2], w_dim[3]);
+        assert!(groups > 0 && c % groups == 0, "Channels must be divisible by groups");
+        assert_eq!(w_dim[0], c, "conv_transpose2d weight must have C input channels");
+        let c_out = cog * groups;
+
+        let full_h = (h - 1) * stride + dilation * (kh - 1) + 1;
+        let full_w = (w - 1) * stride + dilation * (kw - 1) + 1;
+        assert!(full_h > 2 * padding && full_w > 2 * padding, "conv_transpose2d padding is too large");
+
+        // ========== matmul to columns ==========
+        let x = self
+            .permute(&vec![0, 2, 3, 1])
+            .view(vec![(n * h * w) as i32, groups as i32, (c / groups) as i32])
+            .permute(&vec![1, 0, 2]); // [G, N*H*W, C/G]
+
+        let wt = weight.view(vec![groups as i32, (c / groups) as i32, (cog * kh * kw) as i32]); // [G, C/G, C_out/G*kh*kw]
+
+        let cols = autodiff::matmul(x, wt)
+            .permute(&vec![1, 0, 2])
+            .view(vec![n as i32, h as i32, w as i32, c_out as i32, kh as i32, kw as i32])
+            .permute(&vec![0, 3, 1, 2, 4, 5]); // [N, C_out, H, W, kh, kw]
+
+        // ========== col2im ==========
+        let res = cols
+            .fold(3, full_w, stride, dilation)
+            .fold(2, full_h, stride, dilation); // [N, C_out, full_h, full_w]
+
+        if padding == 0 { return res }
+        res.narrow(2, padding, full_h - 2 * padding).narrow(3, padding, full_w - 2 * padding)
+    }
+}
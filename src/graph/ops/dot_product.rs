@@ -1,6 +1,6 @@
 use crate::core::{node::NodeTrait, value::Value, Tensor};
 use crate::ir::IRCmds;
-use crate::{ir_b_add, ir_b_id};
+use crate::{ir_b_add, ir_b_id, VarId};
 
 // ============ Dot Node (operation) ============ 
 #[derive(Clone)]
@@ -19,26 +19,35 @@ impl NodeTrait for DotProductNode {
 
         let left = self.left.forward();
         let right = self.right.forward();
-        let r_val = c_dot_product(&left, &right, self.val.as_ref().map(|v| v.id.clone()));
+        let r_val = c_dot_product(&left, &right, self.val.as_ref().map(|v| v.id));
         self.val = Some(r_val.clone());
         r_val
     }
 
     fn dim (&self) -> Vec<usize> {
-        vec![
-            *self.left.dim().first().unwrap(),
-            *self.right.dim().last().unwrap(),
-        ]    
+        dot_dim(&self.left.dim(), &self.right.dim())
     }
 
+    // a 2-dim side is shared across the batch, so its gradient is summed over the batch
     fn backward (&mut self, grad:Value) {
         let g_node = grad.to_node();
+        let left_batched = self.left.dim().len() == 3;
+        let right_batched = self.right.dim().len() == 3;
+
+        let left_grad = dot(g_node.clone(), transpose(&self.right));
         self.left.n.borrow_mut().backward(
-            dot(g_node.clone(), self.right.clone().t()).forward()
-        );
-        self.right.n.borrow_mut().backward(
-            dot(self.left.clone().t(), g_node).forward()
+            if !left_batched && right_batched { left_grad.sum(0) } else { left_grad }.forward()
         );
+
+        let right_grad = if left_batched && !right_batched {
+            // (n,a,b) --> (n*a,b); sum_i a_i^T g_i is a single 2-dim dot product
+            let k = *self.left.dim().last().unwrap();
+            let n = *self.right.dim().last().unwrap();
+            dot(self.left.view(vec![-1, k as i32]).t(), g_node.view(vec![-1, n as i32]))
+        } else {
+            dot(transpose(&self.left), g_node)
+        };
+        self.right.n.borrow_mut().backward(right_grad.forward());
     }
 
     fn val (&self) -> Option<Value> {
@@ -53,6 +62,7 @@ impl NodeTrait for DotProductNode {
 }
 
 // ============== Creating Dot Product Node ============ 
+// Both tensors are 2-dim, or 3-dim with a leading batch dim (a 2-dim side is shared across the batch)
 pub fn dot (a: Tensor, b: Tensor) -> Tensor { // wrapper is in dot product
     Tensor::new(DotProductNode {
         left: a,
@@ -61,27 +71,84 @@ pub fn dot (a: Tensor, b: Tensor) -> Tensor { // wrapper is in dot product
     })
 }
 
-// ============= Outer Product Core Funct ============= 
-fn c_dot_product (left: &Value, right: &Value, id: Option<String>) -> Value {
-    let a_dim = left.dim.clone();
-    let b_dim = right.dim.clone();
+/*
+Matrix multiplication over the last two dims, with the leading (batch) dims broadcasted against each other
+    (..., a, b) x (..., b, c) --> (..., a, c)
+The batch dims are flattened into the single batch dim of dot. A side without batch (or a batch of 1) isn't broadcasted,
+but shared across the batch (no copy is made)
+*/
+pub fn matmul (a: Tensor, b: Tensor) -> Tensor {
+    let (a_dim, b_dim) = (a.dim(), b.dim());
+    assert!(a_dim.len() >= 2 && b_dim.len() >= 2, "matmul needs tensors with at least 2 dims");
+
+    let a_batch = a_dim[..a_dim.len()-2].to_vec();
+    let b_batch = b_dim[..b_dim.len()-2].to_vec();
+
+    // ========== broadcast batch dims (aligned to the right) ==========
+    let n = a_batch.len().max(b_batch.len());
+    let pad = |d: &Vec<usize>| -> Vec<usize> { [vec![1; n - d.len()], d.clone()].concat() };
+    let (a_pad, b_pad) = (pad(&a_batch), pad(&b_batch));
+
+    let batch: Vec<usize> = a_pad.iter().zip(b_pad.iter()).map(|(&x, &y)| {
+        assert!(x == y || x == 1 || y == 1, "Batch dims {:?} and {:?} can't be broadcasted at matmul", a_batch, b_batch);
+        x.max(y)
+    }).collect();
+    let batch_size: usize = batch.iter().product();
+
+    let to_dot = |t: Tensor, t_pad: &Vec<usize>| -> Tensor {
+        let d = t.dim();
+        let (rows, cols) = (d[d.len()-2] as i32, d[d.len()-1] as i32);
+
+        if t_pad.iter().product::<usize>() == 1 {
+            return if d.len() == 2 { t } else { t.view(vec![rows, cols]) };
+        }
+
+        let mut t = t.view([t_pad.iter().map(|&v| v as i32).collect(), vec![rows, cols]].concat());
+        for (i, (&from, &to)) in t_pad.iter().zip(batch.iter()).enumerate() {
+            if from != to { t = t.broadcast(i as i32, to); }
+        }
+        t.view(vec![batch_size as i32, rows, cols])
+    };
 
-    assert_eq!(a_dim.len(), 2, "A must be a 2d array"); 
-    assert_eq!(b_dim.len(), 2, "B must be a 2d array"); 
-    assert_eq!(a_dim[1], b_dim[0], "A's second dimension must equal B's first dimension");
+    let res = dot(to_dot(a, &a_pad), to_dot(b, &b_pad));
+    if batch.is_empty() || (batch.len() == 1 && batch_size > 1) { return res; } // already in shape
+
+    let res_dim = res.dim();
+    res.view([batch.iter().map(|&v| v as i32).collect(), vec![res_dim[res_dim.len()-2] as i32, res_dim[res_dim.len()-1] as i32]].concat())
+}
+
+// swaps the last two dims
+fn transpose (t: &Tensor) -> Tensor {
+    if t.dim().len() == 3 { t.permute(&vec![0, 2, 1]) } else { t.t() }
+}
+
+fn dot_dim (a_dim: &[usize], b_dim: &[usize]) -> Vec<usize> {
+    assert!(a_dim.len() == 2 || a_dim.len() == 3, "A must be a 2d array (or batched 3d array)"); 
+    assert!(b_dim.len() == 2 || b_dim.len() == 3, "B must be a 2d array (or batched 3d array)"); 
+    assert_eq!(a_dim[a_dim.len()-1], b_dim[b_dim.len()-2], "A's last dimension must equal B's second to last dimension");
+    if a_dim.len() == 3 && b_dim.len() == 3 {
+        assert_eq!(a_dim[0], b_dim[0], "A and B must have the same batch size");
+    }
+
+    let mut d = if a_dim.len() == 3 { vec![a_dim[0]] } else if b_dim.len() == 3 { vec![b_dim[0]] } else { vec![] };
+    d.push(a_dim[a_dim.len()-2]);
+    d.push(b_dim[b_dim.len()-1]);
+    d
+}
+
+// ============= Outer Product Core Funct ============= 
+fn c_dot_product (left: &Value, right: &Value, id: Option<VarId>) -> Value {
+    let dim = dot_dim(&left.dim, &right.dim);
 
     let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
     ir_b_add(IRCmds::DotProduct { 
-        a: left.id.clone(),
-        b: right.id.clone(),
-        res: id.clone()
+        a: left.id,
+        b: right.id,
+        res: id
     });
 
-    return Value {
-        dim: vec![
-            *left.dim.first().unwrap(),
-            *right.dim.last().unwrap()
-        ],
+    Value {
+        dim,
         id,
     }
-}
\ No newline at end of file
+}
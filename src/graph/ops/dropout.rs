@@ -0,0 +1,19 @@
+use crate::{RandDist, Tensor};
+use crate::graph::ops::random::rand_node;
+
+impl Tensor {
+    /*
+     Zeroes every element with probability p, and scales the rest by 1 / (1 - p) so the expected value is unchanged
+     * the mask is drawn on the device each time the IR runs (ex: every iteration of an ir_for)
+     * the gradient goes through the same mask
+     * when not training, it's the identity
+    */
+    pub fn dropout (&self, p: f32, training: bool) -> Tensor {
+        assert!((0.0..1.0).contains(&p), "Dropout probability must be in [0, 1)");
+        if !training || p == 0.0 { return self.clone() }
+
+        let dim = self.dim();
+        let keep = rand_node(RandDist::Bernoulli { p: 1.0 - p }, dim);
+        self.clone() * keep * (1.0 / (1.0 - p))
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{autodiff, ir_b_add, ir_b_id, IRCmds, NodeTrait, Tensor, Value};
+use crate::{autodiff, ir_b_add, ir_b_id, IRCmds, NodeTrait, Tensor, Value, VarId};
 
 macro_rules! create_equality {
     (
@@ -20,7 +20,7 @@ macro_rules! create_equality {
                 }
 
                 let val = self.parent.forward(); 
-                let res_val = $core_name(&val, self.val.as_ref().map(|v| v.id.clone()));
+                let res_val = $core_name(&val, self.val.as_ref().map(|v| v.id));
                 self.val = Some(res_val.clone());
                 res_val 
             } 
@@ -48,10 +48,10 @@ macro_rules! create_equality {
             }
         }
         
-        fn $core_name (a: &Value, id: Option<String>) -> Value {
+        fn $core_name (a: &Value, id: Option<VarId>) -> Value {
             let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
             ir_b_add(IRCmds::$ir_name {
-                a: a.id.clone(),
+                a: a.id,
                 res: id.clone()
             }); 
             
@@ -1,7 +1,7 @@
 // Elementwise function: sin, cos, exp, etc.
 
-use std::f32::consts::{FRAC_PI_2, E};
-use crate::{IRCmds, Tensor, NodeTrait, Value, ir_b_id, ir_b_add};
+use std::f32::consts::{FRAC_PI_2, E, PI};
+use crate::{autodiff, IRCmds, Tensor, NodeTrait, Value, ir_b_id, ir_b_add, VarId};
 
 macro_rules! create_func {
     ($n:ident, $core_func:ident, $st:ident, $ir:ident, $bckw:expr) => {
@@ -19,7 +19,7 @@ macro_rules! create_func {
                     return v;
                 }
 
-                let res_val = $core_func(&self.parent.forward(), self.val.as_ref().map(|v| v.id.clone())); 
+                let res_val = $core_func(&self.parent.forward(), self.val.as_ref().map(|v| v.id)); 
                 self.val = Some(res_val.clone());
                 res_val
             }
@@ -53,11 +53,11 @@ macro_rules! create_func {
         }
 
         // ============= Add ELW Core Func --> Value + Value ============
-        pub fn $core_func (a: &Value, id: Option<String>) -> Value {
+        pub fn $core_func (a: &Value, id: Option<VarId>) -> Value {
             let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
         
             ir_b_add(IRCmds::$ir {
-                a: a.id.clone(),
+                a: a.id,
                 res: id.clone()
             });
 
@@ -137,4 +137,131 @@ impl Tensor {
             (p * self.log2()).exp2()
         }
     }
-}
\ No newline at end of file
+}
+
+// ============ Composed Func (explicit derivative) ============ 
+// The forward is built from other ops (ex: a polynomial approximation), but the backward uses the derivative given here
+// instead of going through the composition (ex: exp overflowing to inf gives NaN gradients in tanh)
+#[derive(Clone)]
+pub struct ComposedFuncNode {
+    parent: Tensor,
+    f: fn(&Tensor) -> Tensor,
+    df: fn(&Tensor, &Tensor) -> Tensor, // (x, f(x)) --> f'(x)
+    val: Option<Value>
+}
+
+impl NodeTrait for ComposedFuncNode {
+    fn forward (&mut self) -> Value {
+        if let Some(v) = self.val() { 
+            return v;
+        }
+
+        let x = self.parent.forward().to_node();
+        let res_val = (self.f)(&x).forward();
+        self.val = Some(res_val.clone());
+        res_val
+    }
+
+    fn dim (&self) -> Vec<usize> {
+        self.parent.dim()
+    }
+
+    fn backward (&mut self, grad: Value) {
+        let x = self.parent.val().expect("parent val invalid at composed func").to_node();
+        let y = self.val.clone().expect("composed func must be forwarded before backward").to_node();
+        self.parent.n.borrow_mut().backward((grad.to_node() * (self.df)(&x, &y)).forward());
+    }
+
+    fn val (&self) -> Option<Value> {
+        self.parent.val()?;
+        self.val.clone()
+    }
+
+    fn deep_copy (&self) -> Box<dyn NodeTrait> {
+        Box::new(self.clone())
+    }
+}
+
+impl Tensor {
+    pub(crate) fn composed (&self, f: fn(&Tensor) -> Tensor, df: fn(&Tensor, &Tensor) -> Tensor) -> Tensor {
+        Tensor::new(ComposedFuncNode {
+            parent: self.clone(),
+            f,
+            df,
+            val: None
+        })
+    }
+}
+
+// c0 + c1*x + c2*x^2 + ...
+fn horner (x: &Tensor, coeffs: &[f32]) -> Tensor {
+    let (last, rest) = coeffs.split_last().expect("horner needs coefficients");
+    rest.iter().rev().fold(autodiff::constant(*last, x.dim()), |acc, c| acc * x.clone() + *c)
+}
+
+impl Tensor {
+    // -1, 0 or 1. No gradient
+    pub fn sign (&self) -> Tensor {
+        let zero = autodiff::constant(0.0, self.dim());
+        self.more_than(&zero) - self.less_than(&zero)
+    }
+
+    // the gradient at 0 is 0
+    pub fn abs (&self) -> Tensor {
+        self.clone() * self.sign()
+    }
+
+    // the gradient is 0 where clamped
+    pub fn clamp (&self, min: f32, max: f32) -> Tensor {
+        assert!(min <= max, "clamp requires min <= max");
+        let below = self.less_than(&autodiff::constant(min, self.dim()));
+        let above = self.more_than(&autodiff::constant(max, self.dim()));
+        self.clone() * (1.0 - below.clone() - above.clone()) + below * min + above * max
+    }
+
+    // Abramowitz & Stegun 7.1.26 (|error| <= 1.5e-7)
+    pub fn erf (&self) -> Tensor {
+        self.composed(
+            |x| {
+                let t = (x.abs() * 0.3275911 + 1.0).recip();
+                let poly = t.clone() * horner(&t, &[0.2548296, -0.28449672, 1.4214138, -1.4531521, 1.0614054]);
+                x.sign() * (1.0 - poly * (-x.pow2()).exp())
+            },
+            // 2 / sqrt(pi) * e^(-x^2)
+            |x, _| (-x.pow2()).exp() * (2.0 / PI.sqrt())
+        )
+    }
+
+    // Abramowitz & Stegun 4.4.46 on |x| (|error| <= 2e-8); acos(-x) = pi - acos(x)
+    pub fn acos (&self) -> Tensor {
+        self.composed(
+            |x| {
+                let a = x.abs();
+                let s = (1.0 - a.clone()).sqrt() * horner(&a, &[
+                    1.5707963, -0.2145988, 0.08897899, -0.050174303,
+                    0.03089188, -0.017088126, 0.00667009, -0.0012624911
+                ]);
+                let neg = x.less_than(&autodiff::constant(0.0, x.dim()));
+                neg.clone() * PI + (1.0 - neg * 2.0) * s
+            },
+            // -1 / sqrt(1 - x^2)
+            |x, _| -(1.0 - x.pow2()).sqrt().recip()
+        )
+    }
+
+    // atan(x) = asin(x / sqrt(1 + x^2)), written so that x = 0 and x = inf don't give NaN
+    pub fn atan (&self) -> Tensor {
+        self.composed(
+            |x| {
+                let s = x.sign() * (x.pow2().recip() + 1.0).sqrt().recip();
+                FRAC_PI_2 - s.acos()
+            },
+            // 1 / (1 + x^2)
+            |x, _| (x.pow2() + 1.0).recip()
+        )
+    }
+
+    pub fn acosh (&self) -> Tensor {
+        (self.clone() + (self.pow2() - 1.0).sqrt()).ln()
+    }
+}
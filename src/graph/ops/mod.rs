@@ -1,5 +1,10 @@
 pub mod func;
 pub mod ops;
 pub mod sum;
+pub mod reduce;
 pub mod equality;
-pub mod dot_product;
\ No newline at end of file
+pub mod dot_product;
+pub mod conv;
+pub mod pool;
+pub mod random;
+pub mod dropout;
\ No newline at end of file
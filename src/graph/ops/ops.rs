@@ -2,7 +2,7 @@ use crate::graph::data::broadcast::try_broadcast;
 use crate::ir::IRCmds;
 use crate::core::node::{Tensor, NodeTrait};
 use crate::core::value::Value;
-use crate::{autodiff, ir_b_add, ir_b_id};
+use crate::{autodiff, ir_b_add, ir_b_id, VarId};
 
 macro_rules! create_op {
     (
@@ -41,7 +41,7 @@ macro_rules! create_op {
                     $core_name_op_eql(&left, &right) 
                 } 
                 else { 
-                    let id = self.val.as_ref().map(|v| v.id.clone());
+                    let id = self.val.as_ref().map(|v| v.id);
                     $core_name(&left, &right, id) 
                 };
 
@@ -91,7 +91,7 @@ macro_rules! create_op {
         }
 
         // ============= Core Func ============
-        fn $core_name (a: &Value, b: &Value, id: Option<String>) -> Value {
+        fn $core_name (a: &Value, b: &Value, id: Option<VarId>) -> Value {
             let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
             
             // Assuming this gets called anyways from try_broadcasting
@@ -99,8 +99,8 @@ macro_rules! create_op {
             // assert_eq!(a.dim, b.dim, "Dimensional mismatch +, -, *, / operation");
 
             ir_b_add(IRCmds::$ir_name {
-                a: a.id.clone(),
-                b: b.id.clone(),
+                a: a.id,
+                b: b.id,
                 res: id.clone()
             });
 
@@ -116,13 +116,13 @@ macro_rules! create_op {
             // assert_eq!(a.dim, b.dim, "Dimensional mismatch +, -, *, / operation");
 
             ir_b_add(IRCmds::$ir_name_op_eql {
-                s: a.id.clone(),
-                o: b.id.clone()
+                s: a.id,
+                o: b.id
             });
 
             Value {
                 dim: a.dim.clone(),
-                id: a.id.clone()
+                id: a.id
             }
         }
 
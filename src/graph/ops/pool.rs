@@ -0,0 +1,79 @@
+use crate::{autodiff, Tensor};
+use crate::graph::data::unfold::fill_pad;
+
+/*
+ 2d pooling over the last two dims ([.., H, W]; ex: [N, C, H, W])
+ * windows are the same two unfolds as conv2d's im2col, flattened into a last dim of kh * kw
+ * that last dim is reduced with max (ReduceOp::Max) or mean (ReduceOp::Sum)
+ * the gradient of max pooling is routed to the (first) max of every window through max's backward
+*/
+#[derive(Clone, Copy, Debug)]
+pub enum PoolOp {
+    Max,
+    Avg
+}
+
+impl Tensor {
+    // general sliding window pooling. Max pools pad with -inf, avg pools pad with 0 (padded elements are counted)
+    pub fn pool2d (&self, op: PoolOp, kernel_size: usize, stride: usize, padding: usize, dilation: usize) -> Tensor {
+        let d = self.dim();
+        let rank = d.len();
+        assert!(rank >= 2, "pool2d input must be at least [H, W]");
+        assert!(padding * 2 <= kernel_size, "pool2d padding must be at most half of the kernel size");
+
+        let pad_val = match op {
+            PoolOp::Max => f32::NEG_INFINITY,
+            PoolOp::Avg => 0.0
+        };
+        let x = fill_pad(&fill_pad(self, rank - 2, padding, padding, pad_val), rank - 1, padding, padding, pad_val);
+
+        // [.., Ho, Wo, kh, kw] --> [.., Ho, Wo, kh * kw]
+        let windows = x
+            .unfold(rank as i32 - 2, kernel_size, stride, dilation)
+            .unfold(rank as i32 - 1, kernel_size, stride, dilation);
+        let mut w_dim = windows.dim().iter().map(|v| *v as i32).collect::<Vec<i32>>();
+        w_dim.truncate(rank);
+        w_dim.push(-1);
+        let windows = windows.view(w_dim);
+
+        match op {
+            PoolOp::Max => windows.max(-1),
+            PoolOp::Avg => windows.mean(-1)
+        }
+    }
+
+    pub fn max_pool2d (&self, kernel_size: usize, stride: usize, padding: usize, dilation: usize) -> Tensor {
+        self.pool2d(PoolOp::Max, kernel_size, stride, padding, dilation)
+    }
+
+    pub fn avg_pool2d (&self, kernel_size: usize, stride: usize, padding: usize) -> Tensor {
+        self.pool2d(PoolOp::Avg, kernel_size, stride, padding, 1)
+    }
+
+    // averages [.., H, W] down to [.., out_h, out_w]. Window i along a dim covers [floor(i * L / out), ceil((i + 1) * L / out))
+    pub fn adaptive_avg_pool2d (&self, out_size: (usize, usize)) -> Tensor {
+        let rank = self.dim().len();
+        assert!(rank >= 2, "adaptive_avg_pool2d input must be at least [H, W]");
+        adaptive_avg(&adaptive_avg(self, rank - 2, out_size.0), rank - 1, out_size.1)
+    }
+}
+
+// averages are separable, so each dim is pooled on its own
+fn adaptive_avg (x: &Tensor, dim: usize, out: usize) -> Tensor {
+    let len = x.dim()[dim];
+    assert!(out > 0 && out <= len, "adaptive pooling output must be within 1 and the input size");
+
+    // equal windows: a single unfold
+    if len.is_multiple_of(out) {
+        let k = len / out;
+        return if k == 1 { x.clone() } else { x.unfold(dim as i32, k, k, 1).mean(-1) };
+    }
+
+    autodiff::concat(
+        (0..out).map(|i| {
+            let (start, end) = (i * len / out, ((i + 1) * len).div_ceil(out));
+            x.narrow(dim as i32, start, end - start).mean(dim as i32).unsqueeze(dim as i32)
+        }).collect(),
+        dim as i32
+    )
+}
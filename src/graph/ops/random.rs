@@ -0,0 +1,169 @@
+use crate::{core::{add_to_dep, is_harsh}, ir_b_add, ir_b_id, ir_b_rng_state, IRCmds, NodeTrait, RandDist, Tensor, Value, VarId};
+
+/**
+ * Random numbers are generated on the device (IRCmds::Rand), from a generator state [seed, offset, epoch]
+ * Every draw reads the state and then advances its offset, so the same IR inside a loop
+ * (or a compiled program ran again) gives new numbers each time. There's no gradient
+ *
+ * The state is a f32, so the offset is only exact up to 2^24. It wraps back to 0 before that and the
+ * overflow is carried into the epoch, which is the second word of the key (2^48 draws before it repeats)
+ */
+#[derive(Clone)]
+pub struct Generator {
+    pub state: Value,
+    wrap: Value,
+    step: Value
+}
+
+// last offset before it wraps
+pub const RNG_MAX_OFFSET: u32 = (1 << 24) - 1;
+
+// constants of the state update (see c_rand): wrap is added to the state before the comparison, step is the [3, 3] update matrix
+pub fn rng_consts () -> (Vec<f32>, Vec<f32>) {
+    let max = RNG_MAX_OFFSET as f32;
+    (
+        // seed + 0.5 > 0 is always 1; offset - (max - 1) > 0 once it reaches max; epoch - 2^24 is never > 0
+        vec![0.5, 1.0 - max, -(1 << 24) as f32],
+        vec![
+            0.0, 1.0, 0.0,          // offset += 1
+            0.0, -max - 1.0, 1.0,   // offset -= 2^24; epoch += 1
+            0.0, 0.0, 0.0
+        ]
+    )
+}
+
+impl Generator {
+    // explicit state, declared where it's called. The same seed always gives the same sequence of draws
+    pub fn new (seed: u32) -> Generator {
+        Generator::with_state(seed, 0, 0)
+    }
+
+    // continues a sequence from (offset, epoch); a fresh generator starts at (0, 0)
+    pub fn with_state (seed: u32, offset: u32, epoch: u32) -> Generator {
+        assert!(seed < (1 << 24), "Seed must be smaller than 2^24 (it's stored as a f32)");
+        assert!(offset <= RNG_MAX_OFFSET && epoch < (1 << 24), "Offset and epoch must be smaller than 2^24 (they're stored as a f32)");
+        let state = Value::new(vec![seed as f32, offset as f32, epoch as f32], vec![3]);
+        state.keep();
+
+        let (wrap, step) = rng_consts();
+        Generator {
+            state,
+            wrap: Value::new(wrap, vec![3]),
+            step: Value::new(step, vec![3, 3])
+        }
+    }
+
+    // state shared by every draw that doesn't pass a generator (ex: dropout), declared once at main with a random seed
+    pub fn global () -> Generator {
+        let (state, wrap, step) = ir_b_rng_state();
+        Generator {
+            state: Value { dim: vec![3], id: state },
+            wrap: Value { dim: vec![3], id: wrap },
+            step: Value { dim: vec![3, 3], id: step }
+        }
+    }
+
+    // drawn right away (like autodiff::tensor), so the result can be used as a parameter
+    pub fn sample (&self, dist: RandDist, dim: Vec<usize>) -> Tensor {
+        let v = c_rand(self, dist, &dim, None);
+
+        if !is_harsh() {
+            add_to_dep(v.id);
+        }
+        v.to_node_with_grad()
+    }
+
+    // [0, 1)
+    pub fn rand (&self, dim: Vec<usize>) -> Tensor {
+        self.sample(RandDist::Uniform { low: 0.0, high: 1.0 }, dim)
+    }
+
+    pub fn randn (&self, dim: Vec<usize>) -> Tensor {
+        self.sample(RandDist::Normal { mean: 0.0, std: 1.0 }, dim)
+    }
+
+    pub fn bernoulli (&self, p: f32, dim: Vec<usize>) -> Tensor {
+        self.sample(RandDist::Bernoulli { p }, dim)
+    }
+
+    // [low, high)
+    pub fn randint (&self, low: i32, high: i32, dim: Vec<usize>) -> Tensor {
+        self.sample(RandDist::RandInt { low, high }, dim)
+    }
+}
+
+// Unlike Generator::sample, it's drawn when the graph is forwarded and isn't a leaf (ex: the dropout mask)
+#[derive(Clone)]
+pub struct RandNode {
+    dist: RandDist,
+    dim: Vec<usize>,
+    val: Option<Value>
+}
+
+impl NodeTrait for RandNode {
+    fn forward (&mut self) -> Value {
+        if let Some(v) = self.val.clone() {
+            return v;
+        }
+        let v = c_rand(&Generator::global(), self.dist, &self.dim, None);
+        self.val = Some(v.clone());
+        v
+    }
+
+    fn backward (&mut self, _: Value) {
+        // no backwards for random numbers
+    }
+
+    fn dim (&self) -> Vec<usize> {
+        self.dim.clone()
+    }
+
+    fn val (&self) -> Option<Value> {
+        self.val.clone()
+    }
+
+    fn deep_copy (&self) -> Box<dyn NodeTrait> {
+        Box::new(self.clone())
+    }
+}
+
+pub(crate) fn rand_node (dist: RandDist, dim: Vec<usize>) -> Tensor {
+    Tensor::new(RandNode {
+        dist,
+        dim,
+        val: None
+    })
+}
+
+// ============= Rand Node Core Func ============
+fn c_rand (gen: &Generator, dist: RandDist, dim: &[usize], id: Option<VarId>) -> Value {
+    match dist {
+        RandDist::Uniform { low, high } => assert!(low < high, "Uniform requires low < high"),
+        RandDist::Normal { std, .. } => assert!(std >= 0.0, "Normal requires std >= 0"),
+        RandDist::Bernoulli { p } => assert!((0.0..=1.0).contains(&p), "Bernoulli probability must be in [0, 1]"),
+        RandDist::RandInt { low, high } => assert!(low < high, "RandInt requires low < high")
+    }
+
+    let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
+
+    ir_b_add(IRCmds::Rand {
+        state: gen.state.id,
+        dist,
+        dim: dim.to_vec(),
+        res: id
+    });
+
+    // state += [0, 1, 0], or [0, -max, 1] when the offset wraps: [1, wrapped, 0] x step
+    let (cond, wrapped, wrapped_v, upd_v, upd) = (ir_b_id(), ir_b_id(), ir_b_id(), ir_b_id(), ir_b_id());
+    ir_b_add(IRCmds::ElwAdd { a: gen.state.id, b: gen.wrap.id, res: cond });
+    ir_b_add(IRCmds::MoreZero { a: cond, res: wrapped });
+    ir_b_add(IRCmds::View { a: wrapped, target_dim: vec![1, 3], res: wrapped_v });
+    ir_b_add(IRCmds::DotProduct { a: wrapped_v, b: gen.step.id, res: upd_v });
+    ir_b_add(IRCmds::View { a: upd_v, target_dim: vec![3], res: upd });
+    ir_b_add(IRCmds::ElwAddEq { s: gen.state.id, o: upd });
+
+    Value {
+        dim: dim.to_vec(),
+        id
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{ir_b_add, ir_b_id, IRCmds, NodeTrait, PadMode, Tensor, Value, VarId};
+use super::sum::reduce_along;
+
+/**
+ * Max and product reductions; like SumNode, these reduce the last dim of a 2-dim tensor
+ * min, argmax and argmin are expressed in terms of max
+ */
+#[derive(Clone)]
+pub struct MaxNode {
+    parent: Tensor,
+    val: Option<Value>
+}
+
+impl NodeTrait for MaxNode {
+    fn forward (&mut self) -> Value {
+        if let Some(v) = self.val() { 
+            return v;
+        }       
+        
+        let val = self.parent.forward();
+        assert_eq!(self.parent.dim().len(), 2, "Reduce (max) node needs dim=2");
+
+        let res_val = c_reduce(&val, self.val.as_ref().map(|v| v.id), |a, res| IRCmds::Max { a, res });
+        self.val = Some(res_val.clone());
+        res_val
+    }
+
+    fn dim (&self) -> Vec<usize> {
+        let mut d = self.parent.dim().clone();
+        d.remove(d.len()-1);
+        d
+    }
+
+    // the gradient only goes to the (first) max element
+    fn backward (&mut self, grad: Value) {
+        let p_val = self.parent.val().expect("parent forward").to_node();
+        let m_val = self.val.clone().expect("max forward").to_node();
+
+        let onehot = first_onehot(&p_val, &m_val, 1);
+        let repeat_n = *self.parent.dim().last().unwrap();
+
+        self.parent.n.borrow_mut().backward(
+            (grad.to_node().unsqueeze(-1).broadcast(-1, repeat_n) * onehot).forward()
+        ); 
+    }
+
+    fn val (&self) -> Option<Value> {
+        self.parent.val()?;
+        self.val.clone()
+    }
+
+    fn deep_copy (&self) -> Box<dyn NodeTrait> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone)]
+pub struct ProdNode {
+    parent: Tensor,
+    val: Option<Value>
+}
+
+impl NodeTrait for ProdNode {
+    fn forward (&mut self) -> Value {
+        if let Some(v) = self.val() { 
+            return v;
+        }       
+        
+        let val = self.parent.forward();
+        assert_eq!(self.parent.dim().len(), 2, "Reduce (prod) node needs dim=2");
+
+        let res_val = c_reduce(&val, self.val.as_ref().map(|v| v.id), |a, res| IRCmds::Prod { a, res });
+        self.val = Some(res_val.clone());
+        res_val
+    }
+
+    fn dim (&self) -> Vec<usize> {
+        let mut d = self.parent.dim().clone();
+        d.remove(d.len()-1);
+        d
+    }
+
+    // d prod / d x_i = product of the other elements; not prod / x_i, which breaks when x_i = 0
+    fn backward (&mut self, grad: Value) {
+        let p_val = self.parent.val().expect("parent forward").to_node();
+        let repeat_n = *self.parent.dim().last().unwrap();
+        let others = exclusive_cumprod(&p_val, false) * exclusive_cumprod(&p_val, true);
+
+        self.parent.n.borrow_mut().backward(
+            (grad.to_node().unsqueeze(-1).broadcast(-1, repeat_n) * others).forward()
+        ); 
+    }
+
+    fn val (&self) -> Option<Value> {
+        self.parent.val()?;
+        self.val.clone()
+    }
+
+    fn deep_copy (&self) -> Box<dyn NodeTrait> {
+        Box::new(self.clone())
+    }
+}
+
+// product of the elements before each element along the last dim (rev: after), ex: [a, b, c] --> [1, a, ab]
+// scan of log2(n) steps, each multiplying with the partial products shifted by 1, 2, 4, ...
+fn exclusive_cumprod (x: &Tensor, rev: bool) -> Tensor {
+    let last = x.dim().len() - 1;
+    let n = x.dim()[last];
+
+    // shifted by k, filled with 1
+    let shift = |t: &Tensor, k: usize| if rev {
+        t.pad_dim(last, 0, k, PadMode::Constant(1.0)).narrow(last as i32, k, n)
+    } else {
+        t.pad_dim(last, k, 0, PadMode::Constant(1.0)).narrow(last as i32, 0, n)
+    };
+
+    let mut p = shift(x, 1);
+    let mut k = 1;
+    while k < n {
+        p = p.clone() * shift(&p, k);
+        k *= 2;
+    }
+    p
+}
+
+// [n - i] along dim, broadcasted to the dim of x (constant)
+fn rev_iota (x: &Tensor, dim: usize) -> Tensor {
+    let x_dim = x.dim();
+    let n = x_dim[dim];
+
+    let mut iota_dim = vec![1; x_dim.len()];
+    iota_dim[dim] = n;
+    let mut iota = Value::new((0..n).map(|i| (n - i) as f32).collect(), iota_dim).to_node();
+
+    for (d, &r) in x_dim.iter().enumerate() {
+        if d != dim { iota = iota.broadcast(d as i32, r); }
+    }
+    iota
+}
+
+// one-hot along dim of where x first equals its max m (where m has dim removed)
+// ties are broken by taking the smallest index: the largest n - i within the matches
+fn first_onehot (x: &Tensor, m: &Tensor, dim: usize) -> Tensor {
+    let n = x.dim()[dim];
+    let rev = rev_iota(x, dim);
+
+    let mask = x.equal(&m.unsqueeze(dim as i32).broadcast(dim as i32, n));
+    let first = (mask * rev.clone()).max(dim as i32);
+    rev.equal(&first.unsqueeze(dim as i32).broadcast(dim as i32, n))
+}
+
+impl Tensor {
+    pub fn max (&self, dim: i32) -> Tensor {
+        reduce_along(self, dim, |node| Tensor::new(MaxNode {
+            parent: node,
+            val: None
+        }))
+    }
+
+    pub fn min (&self, dim: i32) -> Tensor {
+        -(-self.clone()).max(dim)
+    }
+
+    pub fn prod (&self, dim: i32) -> Tensor {
+        reduce_along(self, dim, |node| Tensor::new(ProdNode {
+            parent: node,
+            val: None
+        }))
+    }
+
+    // index of the (first) max along dim; there's no gradient
+    pub fn argmax (&self, dim: i32) -> Tensor {
+        let dim = if dim < 0 { self.dim().len() as i32 + dim } else { dim } as usize;
+        let n = self.dim()[dim];
+        let rev = rev_iota(self, dim);
+
+        let mask = self.equal(&self.max(dim as i32).unsqueeze(dim as i32).broadcast(dim as i32, n));
+        n as f32 - (mask * rev).max(dim as i32)
+    }
+
+    pub fn argmin (&self, dim: i32) -> Tensor {
+        (-self.clone()).argmax(dim)
+    }
+}
+
+fn c_reduce<F> (a: &Value, id: Option<VarId>, cmd: F) -> Value 
+where
+    F: FnOnce(VarId, VarId) -> IRCmds
+{
+    let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
+    ir_b_add(cmd(a.id, id));
+
+    let mut d = a.dim.clone();
+    d.remove(d.len()-1);
+
+    Value { 
+        dim: d, 
+        id
+    }
+}
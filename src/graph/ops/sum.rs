@@ -1,4 +1,4 @@
-use crate::{ir_b_add, ir_b_id, Tensor, NodeTrait, Value, IRCmds};
+use crate::{ir_b_add, ir_b_id, Tensor, NodeTrait, Value, IRCmds, VarId};
 
 #[derive(Clone)]
 pub struct SumNode {
@@ -15,7 +15,7 @@ impl NodeTrait for SumNode {
         let val = self.parent.forward();
         assert_eq!(self.parent.dim().len(), 2, "Reduce (sum) node needs dim=2");
 
-        let res_val = c_sum(&val, self.val.as_ref().map(|v| v.id.clone()));
+        let res_val = c_sum(&val, self.val.as_ref().map(|v| v.id));
         self.val = Some(res_val.clone());
         res_val
     }
@@ -47,42 +47,49 @@ impl NodeTrait for SumNode {
     }
 }
 
+// Applies a reduce node (which reduces the last dim of a 2-dim tensor) along any dim
+// 1. permute dim to the last 2. view to (-1, dim size) 3. reduce 4. unview 5. unpermute
+pub fn reduce_along<F> (x: &Tensor, dim: i32, reduce: F) -> Tensor 
+where
+    F: FnOnce(Tensor) -> Tensor
+{
+    let p_dim = x.dim().len();        
+
+    let dim = if dim < 0 { (p_dim as i32 + dim) as usize } else { dim as usize };
+    
+    // ========== permute to last dim ========== 
+    // 0 1 2 3 4 --> 0 1 4 3 2
+    // dim=2
+
+    // 0 1 4 3 2 --> 0 1 3 2
+    let mut permute_to: Vec<usize> = (0..p_dim).collect();
+    *permute_to.last_mut().unwrap() = dim;
+    permute_to[dim] = p_dim-1;
+
+    let node = x.permute(&permute_to);
+
+    // ========== View last dim ========== 
+    let mut new_dim = node.dim();
+    let node = node.view(vec![-1, *new_dim.last().unwrap() as i32]);
+    
+    // ========== Reduce node ========== 
+    let node = reduce(node);
+
+    // ========== Unview ========== 
+    new_dim.remove(new_dim.len()-1);
+    let node = node.view(new_dim.iter().map(|&v| v as i32).collect());
+
+    // ========== Unpermute ========== 
+    permute_to.remove(dim);
+    node.permute(&permute_to)
+}
+
 impl Tensor {
     pub fn sum (&self, dim:i32) -> Tensor {
-        let p_dim = self.dim().len();        
-
-        let dim = if dim < 0 { (p_dim as i32 + dim) as usize } else { dim as usize };
-        
-        // ========== permute to last dim ========== 
-        // 0 1 2 3 4 --> 0 1 4 3 2
-        // dim=2
-
-        // 0 1 4 3 2 --> 0 1 3 2
-        let mut permute_to: Vec<usize> = (0..p_dim).collect();
-        *permute_to.last_mut().unwrap() = dim;
-        permute_to[dim] = p_dim-1;
-
-        let node = self.permute(&permute_to);
-
-        // ========== View last dim ========== 
-        let mut new_dim = node.dim();
-        let node = node.view(vec![-1, *new_dim.last().unwrap() as i32]);
-        
-        // ========== Sum node ========== 
-        let node = Tensor::new(SumNode {
+        reduce_along(self, dim, |node| Tensor::new(SumNode {
             parent: node,
             val: None
-        });
-
-        // ========== Unview ========== 
-        new_dim.remove(new_dim.len()-1);
-        let node = node.view(new_dim.iter().map(|&v| v as i32).collect());
-
-        // ========== Unpermute ========== 
-        permute_to.remove(dim);
-        let node = node.permute(&permute_to);
-
-        node
+        }))
     }
 
     pub fn mean (&self, dim:i32) -> Tensor {
@@ -103,11 +110,11 @@ impl Tensor {
     }
 }
 
-fn c_sum (a: &Value, id: Option<String>) -> Value {
+fn c_sum (a: &Value, id: Option<VarId>) -> Value {
     let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
     ir_b_add(IRCmds::Sum { 
-        a: a.id.clone(), 
-        res: id.clone()
+        a: a.id, 
+        res: id
     });
 
     let mut d = a.dim.clone();
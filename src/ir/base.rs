@@ -1,4 +1,6 @@
-use crate::{IRBase, IRProcedure};
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::{graph::ops::random::rng_consts, IRBase, IRProcedure, VarId};
 use super::IRCmds;
 
 // IRBase to handle IR appending
@@ -10,7 +12,10 @@ impl IRBase {
             id: 0,
             proc_id: 0,
             proc: IRProcedure::new(main_str),
-            temp_proc: vec![]
+            temp_proc: vec![],
+            placeholders: HashMap::new(),
+            exited: false,
+            rng: None
         }
     }
 
@@ -30,8 +35,8 @@ impl IRBase {
         result.chars().rev().collect() 
     }
 
-    pub fn unique_id (&mut self) -> String {
-        let res = IRBase::unique_id_idx(self.id);
+    pub fn unique_id (&mut self) -> VarId {
+        let res = VarId(self.id);
         self.id += 1;
         res
     }
@@ -52,10 +57,48 @@ impl IRBase {
         c 
     }
 
+    // replaces the contents of the placeholder's CreateMat (wherever it is declared)
+    // if the placeholder is unused, it may have been removed by the IR optimizations; then there's nothing to replace
+    pub fn feed (&mut self, id: VarId, data: Vec<f32>) {
+        let data = Arc::new(data);
+        self.proc.apply(&mut |proc| {
+            for cmd in proc.iter_mut() {
+                if let IRCmds::CreateMat { contents, id: c_id, .. } = cmd {
+                    if *c_id == id { *contents = data.clone(); }
+                }
+            }
+        });
+    }
+
+    // declares the random generator state at main (even while building a block), before anything that uses it
+    // seeds are kept under 2^24, so they are exact as f32
+    pub fn rng_state (&mut self) -> (VarId, VarId, VarId) {
+        if let Some(rng) = self.rng { return rng; }
+
+        let (state, wrap, step) = (self.unique_id(), self.unique_id(), self.unique_id());
+        let (wrap_c, step_c) = rng_consts();
+        let seed = rand::random_range(1..(1 << 24)) as f32;
+        if self.exited {
+            self.proc.main.pop();
+            self.exited = false;
+        }
+        self.proc.push(IRCmds::CreateMat { contents: Arc::new(vec![seed, 0.0, 0.0]), dim: vec![3], id: state });
+        self.proc.push(IRCmds::CreateMat { contents: Arc::new(wrap_c), dim: vec![3], id: wrap });
+        self.proc.push(IRCmds::CreateMat { contents: Arc::new(step_c), dim: vec![3, 3], id: step });
+
+        self.rng = Some((state, wrap, step));
+        (state, wrap, step)
+    }
+
     pub fn add_cmd (&mut self, cmd: IRCmds) {
         if let Some(proc) = self.temp_proc.last_mut() { 
             proc.push(cmd);
         } else {
+            // commands added after an execution go before its EX
+            if self.exited {
+                self.proc.main.pop();
+                self.exited = false;
+            }
             self.proc.push(cmd);
         }
     }
@@ -145,6 +145,9 @@ impl Display for IRCmds {
             IRCmds::Contigious { a, res } => {
                 write!(f, "{} = {}.contigious()", res, a)
             }
+            IRCmds::Rand { state, dist, dim, res } => {
+                write!(f, "{} = rand(state={}, dist={:?}, dim={:?})", res, state, dist, dim)
+            }
             IRCmds::Exp2 { a, res } => {
                 write!(f, "{} = {}.exp2()", res, a)
             },
@@ -163,9 +166,21 @@ impl Display for IRCmds {
             IRCmds::Sum { a, res } => {
                 write!(f, "{} = sum({}, dim=-1)", res, a)
             },
+            IRCmds::Max { a, res } => {
+                write!(f, "{} = max({}, dim=-1)", res, a)
+            },
+            IRCmds::Prod { a, res } => {
+                write!(f, "{} = prod({}, dim=-1)", res, a)
+            },
             IRCmds::Broadcast { a, dim, r, res } => {
                 write!(f, "{} = {}.broadcast(dim={}, r={})", res, a, dim, r)
             },
+            IRCmds::Unfold { a, dim, size, step, dilation, res } => {
+                write!(f, "{} = {}.unfold(dim={}, size={}, step={}, dilation={})", res, a, dim, size, step, dilation)
+            },
+            IRCmds::Pad { a, dim, before, after, mode, res } => {
+                write!(f, "{} = {}.pad(dim={}, before={}, after={}, mode={:?})", res, a, dim, before, after, mode)
+            },
             IRCmds::Heading { cmt } => {
                 write!(f, "{}", format!("\n=== {} ===", cmt).purple().blue())
             },
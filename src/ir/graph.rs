@@ -0,0 +1,227 @@
+// Graph form of the HLIR
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+use crate::{ir::helper::{ir_to_dep, ir_to_res}, IRCmds, IRProcedure, VarId};
+
+pub type NodeId = usize;
+
+/*
+ Every command is a node, with explicit def-use edges:
+ * deps[i] is the node that wrote the i-th dependency (same order as ir_to_dep)
+ * users are the nodes reading the result
+ While/If bodies are regions of the graph; the node only keeps the (emptied) procedures for their ids.
+
+ A read links to the last write before it in program order (writes inside a While/If count); a read before
+ any write links to the last write of the variable.
+ Limitation: loop-carried and multi-write definitions aren't modeled. A variable that changes throughout the
+ program (var_changed) has several reaching writes (the one before the loop, the previous iteration, either
+ branch of an If...), but deps only has the last one. Its writes are never removed (see remove)
+*/
+#[derive(Clone, Debug)]
+pub struct IRNode {
+    pub cmd: IRCmds,
+    pub region: usize,              // region this node is in
+    pub regions: Vec<usize>,        // nested regions: While --> [block]; If --> [conditions..., else]
+    pub deps: Vec<Option<NodeId>>,  // None if the dependency is never written
+    pub users: Vec<NodeId>,
+    pub removed: bool
+}
+
+#[derive(Clone, Debug)]
+pub struct IRRegion {
+    pub id: String,             // procedure id
+    pub nodes: Vec<NodeId>,     // program order
+    pub parent: Option<NodeId>  // While/If owning the region; None for main
+}
+
+#[derive(Clone, Debug)]
+pub struct IRGraph {
+    pub nodes: Vec<IRNode>,
+    pub regions: Vec<IRRegion>,  // regions[0] is main
+    pub changed: HashSet<VarId>  // var_changed; their def-use edges are incomplete
+}
+
+pub fn to_graph (procedure: &IRProcedure, var_changed: &[VarId]) -> IRGraph {
+    let mut graph = IRGraph { nodes: vec![], regions: vec![], changed: var_changed.iter().copied().collect() };
+    let mut writes: HashMap<VarId, NodeId> = HashMap::new();
+    let mut unresolved: Vec<(NodeId, usize, VarId)> = vec![];
+
+    graph.add_region(procedure, None, &mut writes, &mut unresolved);
+
+    // writes is now the last write of every variable
+    for (node, dep_idx, var) in unresolved {
+        if let Some(&w) = writes.get(&var) {
+            graph.nodes[node].deps[dep_idx] = Some(w);
+            graph.nodes[w].users.push(node);
+        }
+    }
+
+    graph
+}
+
+impl IRGraph {
+    fn add_region (
+        &mut self,
+        proc: &IRProcedure,
+        parent: Option<NodeId>,
+        writes: &mut HashMap<VarId, NodeId>,
+        unresolved: &mut Vec<(NodeId, usize, VarId)>
+    ) -> usize {
+        let region = self.regions.len();
+        self.regions.push(IRRegion { id: proc.id.clone(), nodes: vec![], parent });
+
+        for cmd in proc.iter() {
+            let n = self.nodes.len();
+
+            let mut deps = vec![];
+            for (dep_idx, d) in ir_to_dep(cmd).into_iter().enumerate() {
+                let w = writes.get(&d).copied();
+                match w {
+                    Some(w) => self.nodes[w].users.push(n),
+                    None => unresolved.push((n, dep_idx, d))
+                }
+                deps.push(w);
+            }
+
+            // the bodies are moved to the regions
+            let mut node_cmd = cmd.clone();
+            match &mut node_cmd {
+                IRCmds::While { block, .. } => { block.main.clear(); },
+                IRCmds::If { conditions, else_proc } => {
+                    for (_, c_proc) in conditions.iter_mut() { c_proc.main.clear(); }
+                    if let Some(e_proc) = else_proc { e_proc.main.clear(); }
+                },
+                _ => {}
+            }
+
+            self.nodes.push(IRNode { cmd: node_cmd, region, regions: vec![], deps, users: vec![], removed: false });
+            self.regions[region].nodes.push(n);
+
+            let mut nested: Vec<&IRProcedure> = vec![];
+            match cmd {
+                IRCmds::While { block, .. } => nested.push(block),
+                IRCmds::If { conditions, else_proc } => {
+                    nested.extend(conditions.iter().map(|(_, c_proc)| c_proc));
+                    nested.extend(else_proc.iter());
+                },
+                _ => {}
+            }
+            for p in nested {
+                let r = self.add_region(p, Some(n), writes, unresolved);
+                self.nodes[n].regions.push(r);
+            }
+
+            if let Some(res) = ir_to_res(cmd) {
+                writes.insert(res, n);
+            }
+        }
+
+        region
+    }
+
+    // back to a linear procedure, without the removed nodes
+    pub fn flatten (&self) -> IRProcedure {
+        self.flatten_region(0)
+    }
+
+    fn flatten_region (&self, region: usize) -> IRProcedure {
+        let r = &self.regions[region];
+        let mut proc = IRProcedure::new(r.id.clone());
+
+        for &n in r.nodes.iter() {
+            let node = &self.nodes[n];
+            if node.removed { continue; }
+
+            let mut cmd = node.cmd.clone();
+            let mut nested = node.regions.iter().map(|&c| self.flatten_region(c));
+            match &mut cmd {
+                IRCmds::While { block, .. } => { *block = nested.next().unwrap(); },
+                IRCmds::If { conditions, else_proc } => {
+                    for (_, c_proc) in conditions.iter_mut() { *c_proc = nested.next().unwrap(); }
+                    if let Some(e_proc) = else_proc { *e_proc = nested.next().unwrap(); }
+                },
+                _ => {}
+            }
+            proc.push(cmd);
+        }
+
+        proc
+    }
+
+    // variable written by the node (see ir_to_res)
+    pub fn res (&self, n: NodeId) -> Option<VarId> {
+        ir_to_res(&self.nodes[n].cmd)
+    }
+
+    // removes a node (and anything nested in it) from the users of its dependencies
+    // writes of a changed variable can't be removed: users only has the reads linked to this write
+    pub fn remove (&mut self, n: NodeId) {
+        if self.nodes[n].removed { return; }
+        assert!(
+            !self.res(n).is_some_and(|res| self.changed.contains(&res)),
+            "Can't remove %{}, it writes a variable that changes throughout the program", n
+        );
+        self.nodes[n].removed = true;
+
+        for w in self.nodes[n].deps.clone().into_iter().flatten() {
+            self.nodes[w].users.retain(|&u| u != n);
+        }
+        for r in self.nodes[n].regions.clone() {
+            for c in self.regions[r].nodes.clone() { self.remove(c); }
+        }
+    }
+
+    // removes every node whose result is never read, unless kept or changed; returns the # of removed nodes
+    // nodes only read by removed nodes are removed as well, so there's no need to run it until nothing changes
+    pub fn remove_unused<F> (&mut self, keep: F) -> usize
+    where
+        F: Fn(VarId) -> bool
+    {
+        let is_unused = |graph: &IRGraph, n: NodeId| {
+            let node = &graph.nodes[n];
+            !node.removed && node.users.is_empty() && graph.res(n).is_some_and(|res| !graph.changed.contains(&res) && !keep(res))
+        };
+
+        let mut removed: usize = 0;
+        let mut stack: Vec<NodeId> = (0..self.nodes.len()).rev().collect();
+
+        while let Some(n) = stack.pop() {
+            if !is_unused(self, n) { continue; }
+
+            self.remove(n);
+            removed += 1;
+            stack.extend(self.nodes[n].deps.iter().flatten());
+        }
+
+        removed
+    }
+}
+
+impl Display for IRGraph {
+    fn fmt (&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn print_region (graph: &IRGraph, f: &mut Formatter<'_>, region: usize, indent: usize) -> std::fmt::Result {
+            for &n in graph.regions[region].nodes.iter() {
+                let node = &graph.nodes[n];
+                if node.removed { continue; }
+
+                let deps: Vec<String> = node.deps.iter().map(|d| d.map_or("?".to_string(), |d| format!("%{}", d))).collect();
+                let pad = "    ".repeat(indent);
+                match &node.cmd {
+                    IRCmds::While { conditional_var, .. } => writeln!(f, "{}%{}: while ({} != 0) <- [{}]", pad, n, conditional_var, deps.join(", "))?,
+                    IRCmds::If { .. } => writeln!(f, "{}%{}: if <- [{}]", pad, n, deps.join(", "))?,
+                    cmd => writeln!(f, "{}%{}: {} <- [{}]", pad, n, cmd, deps.join(", "))?
+                }
+
+                for &r in node.regions.iter() {
+                    writeln!(f, "{}  {{ {}", pad, graph.regions[r].id)?;
+                    print_region(graph, f, r, indent + 1)?;
+                    writeln!(f, "{}  }}", pad)?;
+                }
+            }
+            Ok(())
+        }
+
+        print_region(self, f, 0, 0)
+    }
+}
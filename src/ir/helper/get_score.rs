@@ -4,7 +4,7 @@ We prioritize the length of the chain over the number of chains there are.
 However, the less the number of chains, the better (thus the - chain_len)
 */
 
-use crate::IRProcedure;
+use crate::{IRProcedure, VarId};
 use super::ir_to_res;
 
 fn number_of_digits(n: usize) -> u32 {
@@ -22,7 +22,7 @@ pub fn get_score (proc: &mut IRProcedure) -> usize {
 
     let mut func = |proc: &mut IRProcedure| {
         let mut chain: Vec<(usize, usize)> = vec![];
-        let mut init_ch: Option<(String, usize)> = None;
+        let mut init_ch: Option<(VarId, usize)> = None;
         
         if proc.len() > max_instr_len { max_instr_len = proc.len(); }
 
@@ -30,12 +30,12 @@ pub fn get_score (proc: &mut IRProcedure) -> usize {
             if let Some(result) = ir_to_res(cmd) {
                 match &init_ch {
                     None => { 
-                        init_ch = Some((result.clone(), idx));
+                        init_ch = Some((result, idx));
                     },
                     Some((start_res, start_idx)) => {
-                        if *result != *start_res {
+                        if result != *start_res {
                             if *start_idx != idx-1 { chain.push((*start_idx, idx-1)); }
-                            init_ch = Some((result.clone(), idx));
+                            init_ch = Some((result, idx));
                         }
                     }
                 } 
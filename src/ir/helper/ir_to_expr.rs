@@ -2,6 +2,7 @@
 // basically the print string without the result id embedded
 // This is only used for repeat_opt, where if the same calculation is repeated, then it removes the repeated op.
 // If there's no expression, then it returns None
+// Rand has no expression on purpose: two draws from the same state are never the same calculation
 
 use crate::IRCmds;
 
@@ -68,9 +69,21 @@ pub fn ir_to_expr (cmd: &IRCmds) -> Option<String> {
         IRCmds::Sum { a, .. } => {
             Some(format!("sum({}, dim=-1)", a))
         },
+        IRCmds::Max { a, .. } => {
+            Some(format!("max({}, dim=-1)", a))
+        },
+        IRCmds::Prod { a, .. } => {
+            Some(format!("prod({}, dim=-1)", a))
+        },
         IRCmds::Broadcast { a, dim, r, .. } => {
             Some(format!("{}.broadcast(dim={}, r={})", a, dim, r))
         },
+        IRCmds::Unfold { a, dim, size, step, dilation, .. } => {
+            Some(format!("{}.unfold(dim={}, size={}, step={}, dilation={})", a, dim, size, step, dilation))
+        },
+        IRCmds::Pad { a, dim, before, after, mode, .. } => {
+            Some(format!("{}.pad(dim={}, before={}, after={}, mode={:?})", a, dim, before, after, mode))
+        },
         _ => { None }
     }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
-use crate::IRCmds;
+use crate::{IRCmds, VarId};
 
-pub fn replace_res_cmd (cmd: &mut IRCmds, replace_to: String) {
+pub fn replace_res_cmd (cmd: &mut IRCmds, replace_to: VarId) {
     match cmd {
         IRCmds::CreateMat { id, .. } => { *id = replace_to; },
         IRCmds::CreateConstant { id, .. } => { *id = replace_to; }
@@ -16,6 +16,8 @@ pub fn replace_res_cmd (cmd: &mut IRCmds, replace_to: String) {
         IRCmds::LessZero { res, .. } => { *res = replace_to; },
 
         IRCmds::Sum { res, .. } => { *res = replace_to; },
+        IRCmds::Max { res, .. } => { *res = replace_to; },
+        IRCmds::Prod { res, .. } => { *res = replace_to; },
         IRCmds::DotProduct { res, .. } => { *res = replace_to; },
 
         IRCmds::View { res, .. } => { *res = replace_to; },
@@ -23,7 +25,10 @@ pub fn replace_res_cmd (cmd: &mut IRCmds, replace_to: String) {
         IRCmds::Concat { res, .. } => { *res = replace_to; },
         IRCmds::Permute { res, .. } => { *res = replace_to; },
         IRCmds::Broadcast { res, .. } => { *res = replace_to; },
+        IRCmds::Unfold { res, .. } => { *res = replace_to; },
+        IRCmds::Pad { res, .. } => { *res = replace_to; },
         IRCmds::Contigious { res, .. } => { *res = replace_to; },
+        IRCmds::Rand { res, .. } => { *res = replace_to; },
 
         IRCmds::Exp2 { res, .. } => { *res = replace_to; },
         IRCmds::Log2 { res, .. } => { *res = replace_to; },
@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::{IRCmds, IRProcedure, PadMode, ValueData, VarId};
+use crate::graph::data::unfold::unfold_windows;
+use crate::devices::cpu::rand::philox_sample;
+
+/*
+Direct interpreter over the IR. Doesn't go through `to_kernel` at all; every variable is a plain row-major `Vec<f32>`.
+It's slow and dumb on purpose. Running the same program before and after `ir_optimize()` (or comparing against a device) tells us which layer broke the semantics.
+
+Note that unary functions follow the same conventions as the kernel backends (ex: sqrt is taken over the absolute value)
+*/
+#[derive(Clone, Debug)]
+pub struct InterpBuffer {
+    pub dim: Vec<usize>,
+    pub data: Vec<f32>
+}
+
+pub struct IRInterp {
+    pub vars: HashMap<VarId, InterpBuffer>
+}
+
+impl IRInterp {
+    pub fn new () -> IRInterp {
+        IRInterp { vars: HashMap::new() }
+    }
+
+    // runs the procedure. Returns true if EX is reached
+    pub fn run (&mut self, proc: &IRProcedure) -> bool {
+        for cmd in proc.iter() {
+            match cmd {
+                IRCmds::EX => { return true; },
+                IRCmds::If { conditions, else_proc } => {
+                    let mut run_cond = false;
+                    let mut exit = false;
+                    for (cond, c_proc) in conditions.iter() {
+                        if self.get_buffer(*cond).data[0] == 1.0 {
+                            exit = self.run(c_proc);
+                            run_cond = true;
+                            break;
+                        }
+                    }
+
+                    if let Some(e_proc) = else_proc {
+                        if !run_cond { exit = self.run(e_proc); }
+                    }
+
+                    if exit { return true; }
+                },
+                IRCmds::While { conditional_var, block } => {
+                    while self.get_buffer(*conditional_var).data[0] != 0.0 {
+                        if self.run(block) { return true; }
+                    }
+                },
+                _ => { self.step(cmd); }
+            }
+        }
+
+        false
+    }
+
+    pub fn get_buffer (&self, id: VarId) -> &InterpBuffer {
+        self.vars.get(&id).unwrap_or_else(|| panic!("Unable to get \"{}\" at interpreter", id))
+    }
+
+    // same interface as `Device::get_tensor`
+    pub fn get_tensor (&self, id: VarId) -> ValueData {
+        if let Some(buf) = self.vars.get(&id) {
+            ValueData {
+                id,
+                dim: buf.dim.clone(),
+                data: Arc::new(buf.data.clone()),
+                is_none: false
+            }
+        } else {
+            ValueData::none()
+        }
+    }
+
+    fn step (&mut self, cmd: &IRCmds) {
+        match cmd {
+            IRCmds::CreateMat { contents, dim, id } => {
+                assert_eq!(contents.len(), dim.iter().product::<usize>(), "Contents size does not match dim at CreateMat");
+                self.set(*id, dim.clone(), contents.as_ref().clone());
+            },
+            IRCmds::CreateConstant { contents, id, dim } => {
+                self.set(*id, dim.clone(), vec![*contents; dim.iter().product()]);
+            },
+
+            IRCmds::ElwMultiply { a, b, res } => { self.binary(*a, *b, *res, |x, y| x * y); },
+            IRCmds::ElwAdd { a, b, res } => { self.binary(*a, *b, *res, |x, y| x + y); },
+            IRCmds::ElwMultiplyEq { s, o } => { self.binary(*s, *o, *s, |x, y| x * y); },
+            IRCmds::ElwAddEq { s, o } => { self.binary(*s, *o, *s, |x, y| x + y); },
+
+            IRCmds::EqualZero { a, res } => { self.unary(*a, *res, |x| if x == 0.0 { 1.0 } else { 0.0 }); },
+            IRCmds::MoreZero { a, res } => { self.unary(*a, *res, |x| if x > 0.0 { 1.0 } else { 0.0 }); },
+            IRCmds::LessZero { a, res } => { self.unary(*a, *res, |x| if x < 0.0 { 1.0 } else { 0.0 }); },
+
+            IRCmds::Exp2 { a, res } => { self.unary(*a, *res, |x| x.exp2()); },
+            IRCmds::Log2 { a, res } => { self.unary(*a, *res, |x| x.log2()); },
+            IRCmds::Sin { a, res } => { self.unary(*a, *res, |x| x.sin()); },
+            IRCmds::Recip { a, res } => { self.unary(*a, *res, |x| 1.0 / x); },
+            IRCmds::Sqrt { a, res } => { self.unary(*a, *res, |x| x.abs().sqrt()); },
+            IRCmds::Contigious { a, res } => { self.unary(*a, *res, |x| x); },
+            IRCmds::Rand { state, dist, dim, res } => {
+                let s = &self.get_buffer(*state).data;
+                let (seed, offset, epoch) = (s[0] as u32, s[1] as u32, s[2] as u32);
+                let data = (0..dim.iter().product::<usize>()).map(|i| philox_sample(dist, seed, offset, epoch, i as u32)).collect();
+                self.set(*res, dim.clone(), data);
+            },
+
+            IRCmds::Sum { a, res } => { self.reduce(*a, *res, 0.0, |acc, v| acc + v); },
+            IRCmds::Max { a, res } => { self.reduce(*a, *res, f32::NEG_INFINITY, f32::max); },
+            IRCmds::Prod { a, res } => { self.reduce(*a, *res, 1.0, |acc, v| acc * v); },
+            IRCmds::DotProduct { a, b, res } => {
+                let a = self.get_buffer(*a);
+                let b = self.get_buffer(*b);
+                assert!((2..=3).contains(&a.dim.len()) && (2..=3).contains(&b.dim.len()), "Dot product must be 2-dim or batched 3-dim");
+                let (m, k) = (a.dim[a.dim.len()-2], a.dim[a.dim.len()-1]);
+                let n = *b.dim.last().unwrap();
+                assert_eq!(k, b.dim[b.dim.len()-2], "Invalid shapes at dot product");
+
+                // a 2-dim side is shared across the batch (stride of 0)
+                let batch = if a.dim.len() == 3 { a.dim[0] } else if b.dim.len() == 3 { b.dim[0] } else { 1 };
+                let a_stride = if a.dim.len() == 3 { m * k } else { 0 };
+                let b_stride = if b.dim.len() == 3 { k * n } else { 0 };
+                if a.dim.len() == 3 && b.dim.len() == 3 {
+                    assert_eq!(a.dim[0], b.dim[0], "Batch sizes must match at dot product");
+                }
+
+                let mut data = vec![0.0; batch * m * n];
+                for bt in 0..batch {
+                    let (a_off, b_off) = (bt * a_stride, bt * b_stride);
+                    for i in 0..m {
+                        for j in 0..n {
+                            data[bt*m*n + i*n + j] = (0..k).map(|l| a.data[a_off + i*k + l] * b.data[b_off + l*n + j]).sum();
+                        }
+                    }
+                }
+
+                let mut dim = if a.dim.len() == 3 || b.dim.len() == 3 { vec![batch] } else { vec![] };
+                dim.extend([m, n]);
+                self.set(*res, dim, data);
+            },
+
+            IRCmds::View { a, target_dim, res } => {
+                let a = self.get_buffer(*a);
+                assert_eq!(a.data.len(), target_dim.iter().product::<usize>(), "Invalid target dim at view");
+                let data = a.data.clone();
+                self.set(*res, target_dim.clone(), data);
+            },
+            IRCmds::Index { a, index, dim, res } => {
+                let a = self.get_buffer(*a);
+                assert!(*index < a.dim[*dim], "Index out of bounds");
+                let outer: usize = a.dim[..*dim].iter().product();
+                let inner: usize = a.dim[dim+1..].iter().product();
+
+                let mut data = Vec::with_capacity(outer * inner);
+                for o in 0..outer {
+                    let start = (o * a.dim[*dim] + index) * inner;
+                    data.extend_from_slice(&a.data[start..start + inner]);
+                }
+
+                let mut res_dim = a.dim.clone();
+                res_dim.remove(*dim);
+                self.set(*res, res_dim, data);
+            },
+            IRCmds::Concat { a, b, dim, res } => {
+                let a = self.get_buffer(*a);
+                let b = self.get_buffer(*b);
+                assert_eq!(a.dim.len(), b.dim.len(), "Concat must have same # of dims");
+                let outer: usize = a.dim[..*dim].iter().product();
+                let a_chunk: usize = a.dim[*dim..].iter().product();
+                let b_chunk: usize = b.dim[*dim..].iter().product();
+
+                let mut data = Vec::with_capacity(a.data.len() + b.data.len());
+                for o in 0..outer {
+                    data.extend_from_slice(&a.data[o*a_chunk..(o+1)*a_chunk]);
+                    data.extend_from_slice(&b.data[o*b_chunk..(o+1)*b_chunk]);
+                }
+
+                let mut res_dim = a.dim.clone();
+                res_dim[*dim] += b.dim[*dim];
+                self.set(*res, res_dim, data);
+            },
+            IRCmds::Permute { a, p, res } => {
+                let a = self.get_buffer(*a);
+                let res_dim: Vec<usize> = p.iter().map(|&i| a.dim[i]).collect();
+                let a_strides = strides(&a.dim);
+
+                let data = (0..a.data.len()).map(|g| {
+                    // res index i maps to a index p[i]
+                    let a_idx: usize = unravel(g, &res_dim)
+                        .iter()
+                        .enumerate()
+                        .map(|(i, r)| r * a_strides[p[i]])
+                        .sum();
+                    a.data[a_idx]
+                }).collect();
+
+                self.set(*res, res_dim, data);
+            },
+            IRCmds::Unfold { a, dim, size, step, dilation, res } => {
+                let a = self.get_buffer(*a);
+                let mut res_dim = a.dim.clone();
+                res_dim[*dim] = unfold_windows(a.dim[*dim], *size, *step, *dilation);
+                res_dim.push(*size);
+                let a_strides = strides(&a.dim);
+
+                let data = (0..res_dim.iter().product()).map(|g| {
+                    // window w, offset k --> a index w * step + k * dilation along dim
+                    let mut idx = unravel(g, &res_dim);
+                    let k = idx.pop().unwrap();
+                    idx[*dim] = idx[*dim] * step + k * dilation;
+                    a.data[idx.iter().zip(a_strides.iter()).map(|(i, s)| i * s).sum::<usize>()]
+                }).collect();
+
+                self.set(*res, res_dim, data);
+            },
+            IRCmds::Pad { a, dim, before, after, mode, res } => {
+                let a = self.get_buffer(*a);
+                let len = a.dim[*dim] as i64;
+                let mut res_dim = a.dim.clone();
+                res_dim[*dim] += before + after;
+                let a_strides = strides(&a.dim);
+
+                let data = (0..res_dim.iter().product()).map(|g| {
+                    let mut idx = unravel(g, &res_dim);
+                    let j = idx[*dim] as i64 - *before as i64;
+                    let j = match mode {
+                        PadMode::Constant(v) => {
+                            if j < 0 || j >= len { return *v }
+                            j
+                        },
+                        PadMode::Reflect => if j < 0 { -j } else if j >= len { 2 * (len - 1) - j } else { j },
+                        PadMode::Replicate => j.clamp(0, len - 1)
+                    };
+                    idx[*dim] = j as usize;
+                    a.data[idx.iter().zip(a_strides.iter()).map(|(i, s)| i * s).sum::<usize>()]
+                }).collect();
+
+                self.set(*res, res_dim, data);
+            },
+            IRCmds::Broadcast { a, dim, r, res } => {
+                let a = self.get_buffer(*a);
+                assert_eq!(a.dim[*dim], 1, "Broadcasting dim must be 1");
+                let outer: usize = a.dim[..*dim].iter().product();
+                let inner: usize = a.dim[dim+1..].iter().product();
+
+                let mut data = Vec::with_capacity(a.data.len() * r);
+                for o in 0..outer {
+                    for _ in 0..*r {
+                        data.extend_from_slice(&a.data[o*inner..(o+1)*inner]);
+                    }
+                }
+
+                let mut res_dim = a.dim.clone();
+                res_dim[*dim] = *r;
+                self.set(*res, res_dim, data);
+            },
+
+            IRCmds::Heading { .. } => {},
+            IRCmds::While { .. } | IRCmds::If { .. } | IRCmds::EX => {} // handled by run
+        }
+    }
+
+    fn set (&mut self, id: VarId, dim: Vec<usize>, data: Vec<f32>) {
+        self.vars.insert(id, InterpBuffer { dim, data });
+    }
+
+    fn unary<F: Fn(f32) -> f32> (&mut self, a: VarId, res: VarId, f: F) {
+        let a = self.get_buffer(a);
+        let dim = a.dim.clone();
+        let data = a.data.iter().map(|&x| f(x)).collect();
+        self.set(res, dim, data);
+    }
+
+    fn binary<F: Fn(f32, f32) -> f32> (&mut self, a: VarId, b: VarId, res: VarId, f: F) {
+        let a = self.get_buffer(a);
+        let b = self.get_buffer(b);
+        assert_eq!(a.data.len(), b.data.len(), "Elementwise operation must have same size");
+        let data = a.data.iter().zip(b.data.iter()).map(|(&x, &y)| f(x, y)).collect();
+        // same as the graph: the result (or s for +=, *=) has a's dim
+        self.set(res, a.dim.clone(), data);
+    }
+
+    // reduces the last dim of a 2-dim buffer
+    fn reduce<F: Fn(f32, f32) -> f32> (&mut self, a: VarId, res: VarId, init: f32, f: F) {
+        let a = self.get_buffer(a);
+        assert_eq!(a.dim.len(), 2, "Reduce must be 2-dim");
+        let (x, y) = (a.dim[0], a.dim[1]);
+        let data = (0..x).map(|i| a.data[i*y..(i+1)*y].iter().fold(init, |acc, &v| f(acc, v))).collect();
+        self.set(res, vec![x], data);
+    }
+}
+
+impl Default for IRInterp {
+    fn default () -> Self {
+        Self::new()
+    }
+}
+
+// row-major strides of dim
+fn strides (dim: &[usize]) -> Vec<usize> {
+    let mut st = vec![1; dim.len()];
+    for i in (0..dim.len().saturating_sub(1)).rev() {
+        st[i] = st[i+1] * dim[i+1];
+    }
+    st
+}
+
+// global idx --> idx per dim
+fn unravel (mut g: usize, dim: &[usize]) -> Vec<usize> {
+    let mut idx = vec![0; dim.len()];
+    for i in (0..dim.len()).rev() {
+        idx[i] = g % dim[i];
+        g /= dim[i];
+    }
+    idx
+}
+
+// Interprets the procedure from a clean state
+pub fn interp (proc: &IRProcedure) -> IRInterp {
+    let mut it = IRInterp::new();
+    it.run(proc);
+    it
+}
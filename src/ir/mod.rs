@@ -2,7 +2,9 @@ pub mod debug;
 pub mod base;
 pub mod helper;
 pub mod opts;
+pub mod graph;
 pub mod optimize;
+pub mod interp;
 
 pub use crate::Device;
 pub use crate::IRCmds;
@@ -1,7 +1,7 @@
 // use crate::ir_print;
-use crate::{core::env_flags::disable_ir_opt,  IRB};
+use crate::{core::env_flags::disable_ir_opt, DEVICE, IRB};
 use crate::ir::opts::{
-    const_begin, dep_opt, repeat_opt, track_var_changed
+    const_begin, const_fold, dep_opt, movement_opt, repeat_opt, track_var_changed
 };
 
 
@@ -14,12 +14,24 @@ pub fn ir_optimize () {
     let mut guard = IRB.lock().unwrap();
     let irb = guard.as_mut().expect("Can't unpack IRBuilder");
     let var_changed = track_var_changed(&mut irb.proc);
-    
-    // Dept optimizations --> deletes unused variables
+
+    // Constant folding --> simplifies operations over constants; leaves unused variables behind for dep_opt
     loop {
-        let deleted = dep_opt(&mut irb.proc, &var_changed);
-        if deleted == 0 { break; }
-    }   
+        let folded = const_fold(&mut irb.proc, &var_changed);
+        if folded == 0 { break; }
+    }
+
+    // Movement canonicalization --> skips no-op views/permutes/contigious; needs the shapes (dot product shape is device specific)
+    let device_guard = DEVICE.lock().unwrap();
+    let device = device_guard.as_ref().expect("Can't unpack device");
+    loop {
+        let changed = movement_opt(&mut irb.proc, &var_changed, device.as_ref());
+        if changed == 0 { break; }
+    }
+    drop(device_guard);
+    
+    // Dept optimizations --> deletes unused variables (single pass, see IRGraph::remove_unused)
+    dep_opt(&mut irb.proc, &var_changed);
 
     // Repeat optimizations --> deletes repetitive operations
     loop {
@@ -31,15 +43,10 @@ pub fn ir_optimize () {
     const_begin(&mut irb.proc);
 
     // also do graph optimizations here for nicer simplification
-    // constant simplification 
-    // *= opts
     // probably more ideas in TODO
     // etc.
-    // multiple views
     // contigious at the end of every dep var (detect if need to be contigious)
     //     then you can unignore view test
-    // multiple contigious
-    // if we use a reduce on constant, then just evaluate it here
     
     drop(guard);
 }
\ No newline at end of file
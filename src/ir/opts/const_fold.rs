@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use crate::{core::ret_dep_list, ir::helper::{ir_to_res, replace_ref}, IRCmds, IRProcedure, VarId};
+
+// what a command folds into
+enum Fold {
+    Const (f32, Vec<usize>), // res = constant
+    Alias (VarId),          // res is the same as another variable
+    Remove                   // x *= 1, x += 0
+}
+
+/*
+ Constant evaluator: uses the values of CreateConstant to simplify the commands reading them
+ * constant (+, *) constant, f(constant) and reductions over a constant --> constant
+ * 0 * x --> 0
+ * 1 * x --> x, 0 + x --> x, 1 / (1 / x) --> x
+ * x *= 1, x += 0 --> removed
+ Variables that change throughout the program are never treated as constants or aliased.
+ The constants left unused are deleted by dep_opt
+*/
+pub fn const_fold (procedure: &mut IRProcedure, var_changed: &[VarId]) -> usize {
+    let dep_list = ret_dep_list();
+
+    // ids are unique, so a constant (or a reciprocal) has the same value everywhere it's read
+    let mut consts: HashMap<VarId, (f32, Vec<usize>)> = HashMap::new();
+    let mut recips: HashMap<VarId, VarId> = HashMap::new();
+    procedure.apply(&mut |proc| {
+        for cmd in proc.iter() {
+            match cmd {
+                IRCmds::CreateConstant { contents, id, dim } if !var_changed.contains(id) => {
+                    consts.insert(*id, (*contents, dim.clone()));
+                },
+                IRCmds::Recip { a, res } if !var_changed.contains(res) => {
+                    recips.insert(*res, *a);
+                },
+                _ => {}
+            }
+        }
+    });
+
+    // aliasing deletes res, so it must not be read by the user or written again
+    let can_alias = |res: &VarId, to: &VarId| {
+        !var_changed.contains(res) && !dep_list.contains(res) && !var_changed.contains(to)
+    };
+
+    let fold = |cmd: &IRCmds| -> Option<Fold> {
+        let c = |id: &VarId| consts.get(id).cloned();
+
+        match cmd {
+            IRCmds::ElwMultiply { a, b, res } | IRCmds::ElwAdd { a, b, res } => {
+                let is_mult = matches!(cmd, IRCmds::ElwMultiply { .. });
+                let identity = if is_mult { 1.0 } else { 0.0 };
+
+                match (c(a), c(b)) {
+                    (Some((va, da)), Some((vb, db))) => {
+                        // one of them may be a single value that isn't broadcasted
+                        let dim = if da.iter().product::<usize>() >= db.iter().product::<usize>() { da } else { db };
+                        Some(Fold::Const(if is_mult { va * vb } else { va + vb }, dim))
+                    },
+                    (Some((v, dim)), None) | (None, Some((v, dim))) => {
+                        let x = if c(a).is_some() { b } else { a };
+                        if v == identity && can_alias(res, x) {
+                            Some(Fold::Alias(*x))
+                        }
+                        // a single value constant doesn't tell the dim of the result
+                        else if is_mult && v == 0.0 && dim.iter().product::<usize>() > 1 {
+                            Some(Fold::Const(0.0, dim))
+                        }
+                        else { None }
+                    },
+                    _ => None
+                }
+            },
+            IRCmds::ElwMultiplyEq { o, .. } => c(o).filter(|(v, _)| *v == 1.0).map(|_| Fold::Remove),
+            IRCmds::ElwAddEq { o, .. } => c(o).filter(|(v, _)| *v == 0.0).map(|_| Fold::Remove),
+
+            IRCmds::Recip { a, res } => {
+                if let Some((v, dim)) = c(a) { return Some(Fold::Const(1.0 / v, dim)); }
+                recips.get(a)
+                    .filter(|x| !var_changed.contains(a) && can_alias(res, x))
+                    .map(|x| Fold::Alias(*x))
+            },
+            IRCmds::Exp2 { a, .. } => c(a).map(|(v, dim)| Fold::Const(v.exp2(), dim)),
+            IRCmds::Log2 { a, .. } => c(a).map(|(v, dim)| Fold::Const(v.log2(), dim)),
+            IRCmds::Sin { a, .. } => c(a).map(|(v, dim)| Fold::Const(v.sin(), dim)),
+            IRCmds::Sqrt { a, .. } => c(a).map(|(v, dim)| Fold::Const(v.abs().sqrt(), dim)), // sqrt(|x|), same as the devices
+            IRCmds::EqualZero { a, .. } => c(a).map(|(v, dim)| Fold::Const((v == 0.0) as i32 as f32, dim)),
+            IRCmds::MoreZero { a, .. } => c(a).map(|(v, dim)| Fold::Const((v > 0.0) as i32 as f32, dim)),
+            IRCmds::LessZero { a, .. } => c(a).map(|(v, dim)| Fold::Const((v < 0.0) as i32 as f32, dim)),
+
+            // reduces along the last dim (see IRCmds); a single value constant doesn't tell how many values are reduced
+            IRCmds::Sum { a, .. } | IRCmds::Max { a, .. } | IRCmds::Prod { a, .. } => {
+                let (v, mut dim) = c(a).filter(|(_, dim)| dim.iter().product::<usize>() > 1)?;
+                let n = dim.pop().unwrap();
+
+                let v = match cmd {
+                    IRCmds::Sum { .. } => v * n as f32,
+                    IRCmds::Prod { .. } => v.powi(n as i32),
+                    _ => v
+                };
+                Some(Fold::Const(v, dim))
+            },
+            _ => None
+        }
+    };
+
+    let mut total_changed: usize = 0;
+    let mut aliases: HashMap<VarId, VarId> = HashMap::new();
+
+    procedure.apply(&mut |proc| {
+        let mut idx = 0;
+        while idx < proc.main.len() {
+            let cmd = proc.get(idx).unwrap();
+            let f = match fold(cmd) {
+                Some(f) => f,
+                None => { idx += 1; continue; }
+            };
+            let res = ir_to_res(cmd);
+
+            match f {
+                // const_begin moves constants to the front, which is only right if it's the only write
+                Fold::Const(..) if var_changed.contains(&res.unwrap()) => { idx += 1; continue; },
+                Fold::Const(contents, dim) => {
+                    proc.main[idx] = IRCmds::CreateConstant { contents, id: res.unwrap(), dim };
+                    idx += 1;
+                },
+                Fold::Alias(to) => {
+                    aliases.insert(res.unwrap(), to);
+                    proc.remove(idx);
+                },
+                Fold::Remove => { proc.remove(idx); }
+            }
+            total_changed += 1;
+        }
+    });
+
+    // follow chains (ex: 1 * (0 + x)), as the variable in between is deleted as well
+    for (res, to) in aliases.iter() {
+        let mut to = to;
+        while let Some(next) = aliases.get(to) { to = next; }
+        replace_ref(procedure, *res, *to);
+    }
+
+    total_changed
+}
@@ -2,10 +2,14 @@ pub mod repeat_opt;
 pub mod dep_opt;
 pub mod var_changed;
 pub mod const_begin;
+pub mod const_fold;
+pub mod movement_opt;
 
 pub use repeat_opt::*;
 pub use dep_opt::*;
 pub use var_changed::*;
 pub use const_begin::*;
+pub use const_fold::*;
+pub use movement_opt::*;
 
 pub use super::*;
\ No newline at end of file
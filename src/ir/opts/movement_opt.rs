@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use crate::{core::ret_dep_list, ir::helper::{ir_to_res, replace_ref}, trackers::ShapeTracker, Device, IRCmds, IRProcedure, VarId};
+
+/*
+ Canonicalizes chains of data movement
+ * view(view(a)) --> view(a); a view to the shape a already has --> a
+ * permute(permute(a)) --> one permute; identity permutes (ex: t().t()) --> a
+ * contigious(contigious(a)) --> contigious(a)
+ ex: a reduce along the last dim of a 2-dim tensor is permute -> view -> sum -> view -> permute, all of which are no-ops
+ The skipped movements are left unused, and deleted by dep_opt
+*/
+pub fn movement_opt (procedure: &mut IRProcedure, var_changed: &[VarId], device: &dyn Device) -> usize {
+    let dep_list = ret_dep_list();
+
+    // shapes, and the movements that produce each variable (ids are unique unless changed)
+    let mut shape_tracker = ShapeTracker::new();
+    let mut producers: HashMap<VarId, IRCmds> = HashMap::new();
+    procedure.step_cmd(&mut |proc, idx| {
+        if let Some(cmd) = proc.get(*idx) {
+            shape_tracker.step(device, cmd);
+
+            // reading the source directly later is only right if it doesn't change in between
+            if let IRCmds::View { a, res, .. } | IRCmds::Permute { a, res, .. } | IRCmds::Contigious { a, res } = cmd {
+                if !var_changed.contains(res) && !var_changed.contains(a) { producers.insert(*res, cmd.clone()); }
+            }
+        }
+        true
+    });
+
+    // aliasing deletes res, so it must not be read by the user (it's materialized) or written again
+    let can_alias = |res: &VarId, to: &VarId| {
+        !var_changed.contains(res) && !dep_list.contains(res) && !var_changed.contains(to)
+    };
+
+    let mut total_changed: usize = 0;
+    let mut aliases: HashMap<VarId, VarId> = HashMap::new();
+
+    procedure.apply(&mut |proc| {
+        let mut idx = 0;
+        while idx < proc.main.len() {
+            let res = ir_to_res(proc.get(idx).unwrap()).unwrap_or(VarId::NONE);
+            let mut alias: Option<VarId> = None;
+
+            match proc.get_mut(idx).unwrap() {
+                IRCmds::View { a, target_dim, .. } => {
+                    if let Some(IRCmds::View { a: src, .. }) = producers.get(a) {
+                        *a = *src;
+                        total_changed += 1;
+                    }
+                    if shape_tracker.shape.get(a) == Some(target_dim) && can_alias(&res, a) {
+                        alias = Some(*a);
+                    }
+                },
+                IRCmds::Permute { a, p, .. } => {
+                    if let Some(IRCmds::Permute { a: src, p: src_p, .. }) = producers.get(a) {
+                        // res[i] = a[p[i]] = src[src_p[p[i]]]
+                        *p = p.iter().map(|&i| src_p[i]).collect();
+                        *a = *src;
+                        total_changed += 1;
+                    }
+                    if p.iter().enumerate().all(|(i, &v)| i == v) && can_alias(&res, a) {
+                        alias = Some(*a);
+                    }
+                },
+                IRCmds::Contigious { a, .. } => {
+                    if let Some(IRCmds::Contigious { .. }) = producers.get(a) {
+                        if can_alias(&res, a) { alias = Some(*a); }
+                    }
+                },
+                _ => {}
+            }
+
+            if let Some(to) = alias {
+                aliases.insert(res, to);
+                proc.remove(idx);
+                total_changed += 1;
+            } else {
+                idx += 1;
+            }
+        }
+    });
+
+    // follow chains (ex: identity view of an identity permute), as the variable in between is deleted as well
+    for (res, to) in aliases.iter() {
+        let mut to = to;
+        while let Some(next) = aliases.get(to) { to = next; }
+        replace_ref(procedure, *res, *to);
+    }
+
+    total_changed
+}
@@ -1,71 +1,143 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::{ir::helper::{ir_to_expr, ir_to_res, replace_ref_cmd}, IRProcedure};
+use crate::{core::ret_dep_list, ir::helper::{ir_to_dep, ir_to_expr, ir_to_res, replace_ref}, IRCmds, IRProcedure, VarId};
 
-pub fn repeat_opt (procedure: &mut IRProcedure, var_changed: &Vec<String>) -> usize {
-    
-    let mut total_changed: usize = 0;
-    
-    let mut func = |proc: &mut IRProcedure| {
-        let mut tracker: HashMap<String, Vec<usize>> = HashMap::new();  
-        
-        // get the exprs that are the exact same and track location
-        for (idx, cmd) in proc.iter().enumerate() {
-            let expr = ir_to_expr(cmd).unwrap_or("".to_string());
-            if expr.len() == 0 { continue; }
-            
-            tracker.entry(expr)
-                .and_modify(|v| v.push(idx))
-                .or_insert(vec![idx]);
-        }
+/*
+ Removes repeated calculations
+    1. loop invariant computations (not reading anything written in a While body) are moved before the loop
+    2. the same expression over the same values is only calculated once. What is calculated in a procedure
+       is also reused by the If/While blocks nested after it (ex: x.t() before the loop and in the loop body)
+*/
+pub fn repeat_opt (procedure: &mut IRProcedure, var_changed: &[VarId]) -> usize {
+    let dep_list = ret_dep_list();
 
-        // filter variables that are only two or more locations
-        let tracker: Vec<(&String, &Vec<usize>)> = tracker.iter().filter(|&(_, v)| v.len() > 1).collect();
-        
-        // track what variables to replace with and what idxs to delete
-        let mut replace_to_res: Vec<(String, String)> = vec![];
-        let mut delete_idxs: Vec<usize> = vec![];
-        
-        for (_, locs) in tracker {
-            let mut first_res: Option<&String> = None;
-            for i in 0..locs.len() {
-                let idx = locs[i];
-                let current_res = ir_to_res(proc.get(idx).unwrap()).unwrap();
-                
-                if i > 0 {
-                    if var_changed.contains(&current_res) {
-                        continue;  // don't add to delete idxs or replace
-                    }
-                    replace_to_res.push((current_res.clone(), first_res.unwrap().clone()));
-                    delete_idxs.push(idx);
-                } else {
-                    first_res = Some(current_res);
-
-                    if var_changed.contains(&current_res) { 
-                        break; 
-                        // don't change any of the variables that changes throughout the program
-                        // go on to the next var to replace
+    let mut total_changed = hoist_invariant(procedure, var_changed);
+
+    let mut replace_to_res: Vec<(VarId, VarId)> = vec![];
+    total_changed += dedup_proc(procedure, var_changed, &dep_list, HashMap::new(), HashMap::new(), &mut replace_to_res);
+
+    // replace variables; a result deleted in a block may still be read after the block
+    for (to_search, to_replace) in replace_to_res {
+        replace_ref(procedure, to_search, to_replace);
+    }
+
+    total_changed
+}
+
+// moves the loop invariant cmds of each While body right before the loop
+// nested loops move up one level per call (ir_optimize calls repeat_opt until nothing changes)
+fn hoist_invariant (procedure: &mut IRProcedure, var_changed: &[VarId]) -> usize {
+    let mut total_hoisted: usize = 0;
+
+    procedure.apply(&mut |proc| {
+        let mut idx = 0;
+        while idx < proc.len() {
+            let mut hoisted: Vec<IRCmds> = vec![];
+
+            if let IRCmds::While { conditional_var, block } = proc.get_mut(idx).unwrap() {
+                // everything written in the body (and in its nested blocks) changes between iterations
+                let mut written: HashSet<VarId> = HashSet::new();
+                block.apply(&mut |p| {
+                    written.extend(p.iter().filter_map(ir_to_res));
+                });
+
+                let mut i = 0;
+                while i < block.len() {
+                    let cmd = block.get(i).unwrap();
+
+                    // only pure calculations (there's an expr) assigned once; Rand and the control flow have no expr
+                    let res = ir_to_res(cmd).filter(|r| !var_changed.contains(r) && r != conditional_var);
+                    let invariant = ir_to_expr(cmd).is_some() && ir_to_dep(cmd).iter().all(|d| !written.contains(d));
+
+                    match res {
+                        Some(res) if invariant => {
+                            // cmds after it reading res may be invariant as well
+                            written.remove(&res);
+                            hoisted.push(block.remove(i));
+                        },
+                        _ => { i += 1; }
                     }
                 }
             }
+
+            total_hoisted += hoisted.len();
+            for cmd in hoisted {
+                proc.insert(idx, cmd);
+                idx += 1;
+            }
+            idx += 1;
         }
+    });
 
-        // delete idxs
-        delete_idxs.sort();
-        for (i, idx) in delete_idxs.iter().enumerate() {
-            proc.main.remove(idx - i);
-            total_changed += 1;
+    total_hoisted
+}
+
+// seen: expr (with the versions of the changed variables it reads) --> first result calculating it
+fn dedup_proc (
+    proc: &mut IRProcedure,
+    var_changed: &[VarId],
+    dep_list: &HashSet<VarId>,
+    mut seen: HashMap<String, VarId>,
+    mut versions: HashMap<VarId, usize>,
+    replace_to_res: &mut Vec<(VarId, VarId)>
+) -> usize {
+    let mut total_changed: usize = 0;
+
+    // number of times each changed variable was written so far
+    // the same expr over a variable that was written in between isn't the same value
+    let bump_all = |versions: &mut HashMap<VarId, usize>| {
+        for v in var_changed.iter() { *versions.entry(*v).or_insert(0) += 1; }
+    };
+
+    let mut idx = 0;
+    while idx < proc.len() {
+        let cmd = proc.get_mut(idx).unwrap();
+
+        if let IRCmds::If { conditions, else_proc } = cmd {
+            // a branch runs (at most) once, right after the cmds before it
+            for (_, branch) in conditions.iter_mut() {
+                total_changed += dedup_proc(branch, var_changed, dep_list, seen.clone(), versions.clone(), replace_to_res);
+            }
+            if let Some(e_proc) = else_proc {
+                total_changed += dedup_proc(e_proc, var_changed, dep_list, seen.clone(), versions.clone(), replace_to_res);
+            }
+
+            // anything could be written inside
+            bump_all(&mut versions);
+        }
+        else if let IRCmds::While { block, .. } = cmd {
+            // the body also sees the values written by the previous iteration
+            bump_all(&mut versions);
+            total_changed += dedup_proc(block, var_changed, dep_list, seen.clone(), versions.clone(), replace_to_res);
         }
+        else if let (Some(expr), Some(res)) = (ir_to_expr(cmd), ir_to_res(cmd)) {
+            let expr = ir_to_dep(cmd).iter()
+                .filter_map(|d| versions.get(d).map(|ver| format!(" {}@{}", d, ver)))
+                .fold(expr, |acc, v| acc + &v);
 
-        // replace variables
-        for cmd in proc.iter_mut() {
-            for (to_search, to_replace) in replace_to_res.iter() {
-                replace_ref_cmd(cmd, to_search, to_replace.clone());
+            if var_changed.contains(&res) {
+                // don't change any of the variables that changes throughout the program
+                *versions.entry(res).or_insert(0) += 1;
+            }
+            else if let Some(first_res) = seen.get(&expr) {
+                // kept variables must still be computed under their own id
+                if !dep_list.contains(&res) {
+                    replace_to_res.push((res, *first_res));
+                    proc.remove(idx);
+                    total_changed += 1;
+                    continue;
+                }
+            }
+            else {
+                seen.insert(expr, res);
             }
         }
-    };
+        else if let Some(res) = ir_to_res(cmd) {
+            if var_changed.contains(&res) { *versions.entry(res).or_insert(0) += 1; }
+        }
 
-    procedure.apply(&mut func);
+        idx += 1;
+    }
 
     total_changed
-}
\ No newline at end of file
+}
@@ -33,14 +33,15 @@ We don't have to allocate a gradient variable (32768-long allocation!). As soon
 use std::collections::HashMap;
 
 use crate::kernel_decl::{Expression, KernelProcedure, Kernels};
+use crate::VarId;
 
 pub fn alloc_temp_opt (kernel_proc: &mut KernelProcedure) {
     let f = |v: &mut Vec<Kernels>| {
-        let mut pot_temps: HashMap<String, bool> = HashMap::new();
+        let mut pot_temps: HashMap<VarId, bool> = HashMap::new();
         for cmd in v.iter() {
             match cmd {
                 Kernels::Alloc { id, .. } => {
-                    pot_temps.insert(id.clone(), false);
+                    pot_temps.insert(*id, false);
                 },
                 Kernels::Dealloc { id, .. }  => {
                     pot_temps.get_mut(id).map(|f| { *f = true; } );
@@ -49,37 +50,40 @@ pub fn alloc_temp_opt (kernel_proc: &mut KernelProcedure) {
             }
         }
 
-        let pot_temps: Vec<_> = pot_temps.iter()
+        let mut pot_temps: Vec<_> = pot_temps.iter()
             .filter(|f| *f.1)
-            .map(|f| f.0.clone())
+            .map(|f| *f.0)
             .collect();
 
         if pot_temps.len() == 0 { return; }        
-        
-        // I have absolutely no cases where there might be multiple temporary variables in one kernel 
-        // So far, haven't seen an example of this. However, if it does happen, then I will know ;)
-        if pot_temps.len() > 1 { println!("Multiple temporary variables? Implement this") }
+        pot_temps.sort(); // deterministic pick if there are multiple
+
+        // There's only one temporary storage per kernel, so pick the first var satisfying 2. and 3.
+        // Note that a var can be alloc + dealloc within the fusion without being written in it (ex: only read) --> not a temp
+        let var = pot_temps.into_iter().find(|var| {
+            let mut result_expr: Vec<&Expression> = vec![];
+            let mut dep_expr: Vec<&Expression> = vec![];
+            for cmd in v.iter() {
+                if cmd.get_res().is_some_and(|f| f == var) {
+                    if let Some(r) = cmd.get_res_access_expr() { result_expr.push(r); }
+                }
+                dep_expr.extend(cmd.get_dep_access_expr(var))
+            }
 
-        let var = pot_temps.get(0).unwrap().clone();
+            if result_expr.is_empty() { return false; }
 
-        // check 2. and 3.
-        let mut result_expr: Vec<&Expression> = vec![];
-        let mut dep_expr: Vec<&Expression> = vec![];
-        for cmd in v.iter() {
-            if cmd.get_res().is_some_and(|f| *f == var) {
-                if let Some(r) = cmd.get_res_access_expr() { result_expr.push(r); }
-            }
-            dep_expr.extend(cmd.get_dep_access_expr(&var))
-        }
+            let all_dep_global = dep_expr.iter().all(|f| f.is_global());
 
-        let all_dep_global = dep_expr.iter().all(|f| f.is_global());
+            result_expr.remove(0);
+            let all_res_global = result_expr.iter().all(|f| f.is_global());
 
-        result_expr.remove(0);
-        let all_res_global = result_expr.iter().all(|f| f.is_global());
+            all_dep_global && all_res_global
+        });
 
-        if !all_dep_global || !all_res_global {
-            return; // doesn't satisfy condition 2 or 3
-        }
+        let var = match var {
+            Some(var) => var,
+            None => { return; } // doesn't satisfy condition 2 or 3
+        };
 
         // Replace all references of var to temporary
         for cmd in v.iter_mut() {
@@ -1,6 +1,6 @@
 // insert allocations and deallocations accordant to alloc tracker
 use std::collections::HashMap;
-use crate::{core::ret_dep_list, kernel_decl::{KernelProcedure, Kernels}, trackers::{AllocTracker, Location}, Device};
+use crate::{core::ret_dep_list, kernel_decl::{KernelProcedure, Kernels}, trackers::{AllocTracker, Location}, Device, VarId};
 
 pub fn step_procedure<'a> (device: &dyn Device, proc: &'a KernelProcedure, alloc_tracker: &mut AllocTracker<'a>) {
     for (idx, cmd) in proc.iter().enumerate() {
@@ -40,7 +40,7 @@ pub fn step_procedure<'a> (device: &dyn Device, proc: &'a KernelProcedure, alloc
     } 
 }
 
-pub fn insert_alloc<'a> (device: &dyn Device, kernel_proc: &mut KernelProcedure, var_changed: &Vec<String>) {
+pub fn insert_alloc<'a> (device: &dyn Device, kernel_proc: &mut KernelProcedure, var_changed: &[VarId]) {
     let dep_vars = ret_dep_list();
     let mut alloc_tracker = AllocTracker::new(&dep_vars, var_changed);
     
@@ -50,7 +50,7 @@ pub fn insert_alloc<'a> (device: &dyn Device, kernel_proc: &mut KernelProcedure,
     // ====================== Insert Allocations! ====================== 
     let mut total_list: Vec<_> = alloc_tracker.vars.iter()
         .map(|(_, v)| {
-            (v.id.clone(), v.size, v.alloc_loc.clone(), true, v.initial_content.clone(), v.alloc_defined)
+            (v.id, v.size, v.alloc_loc.clone(), true, v.initial_content.clone(), v.alloc_defined)
         })
         .filter(|v| !v.5)
         .map(|v| (v.0, v.1, v.2, v.3, v.4))
@@ -58,7 +58,7 @@ pub fn insert_alloc<'a> (device: &dyn Device, kernel_proc: &mut KernelProcedure,
 
     let deallocations: Vec<_> = alloc_tracker.vars.iter()
         .map(|(_, v)| {
-            (v.id.clone(), v.size, v.dealloc_loc.clone(), false, None)
+            (v.id, v.size, v.dealloc_loc.clone(), false, None)
         })
         .filter(|v| v.2.is_some())
         .map(|v| (v.0, v.1, v.2.unwrap(), v.3, v.4)) 
@@ -78,11 +78,11 @@ pub fn insert_alloc<'a> (device: &dyn Device, kernel_proc: &mut KernelProcedure,
                     .or_insert(0);
 
                 let k = if *is_alloc { Kernels::Alloc { 
-                    id: var_id.clone(),
+                    id: *var_id,
                     size: *size,
                     content: content.clone(),
                 } } else { Kernels::Dealloc { 
-                    id: var_id.clone(),
+                    id: *var_id,
                     size: *size 
                 } };
 
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::{core::ret_dep_list, kernel_decl::{Expression, KernelProcedure, Kernels, Matrix}};
+use crate::{core::ret_dep_list, kernel_decl::{Expression, KernelProcedure, Kernels, Matrix}, VarId};
 
 // honestly, you need to fix this and metainfo
 impl Kernels {
@@ -8,7 +8,7 @@ impl Kernels {
 
 #[derive(Debug, Clone)]
 struct AllocEntry {
-    pub id: String,
+    pub id: VarId,
     pub start_loc: usize,
     pub end_loc: usize,
     pub size: usize,
@@ -26,9 +26,9 @@ pub fn max (a: usize, b: usize) -> usize {
     if b > a { b } else { a }
 }
 
-pub fn tetris_opt (kernel_proc: &mut KernelProcedure, var_changed: &Vec<String>) {
+pub fn tetris_opt (kernel_proc: &mut KernelProcedure, var_changed: &[VarId]) {
     let list = ret_dep_list();
-    let mut entries: HashMap<String, AllocEntry> = HashMap::new();
+    let mut entries: HashMap<VarId, AllocEntry> = HashMap::new();
 
     // ===================== Track entries ===================== 
     let mut loc = 0;
@@ -37,8 +37,8 @@ pub fn tetris_opt (kernel_proc: &mut KernelProcedure, var_changed: &Vec<String>)
 
         if let Kernels::Alloc { id, size, content } = cmd {
             if content.is_none() && !list.contains(id) && !var_changed.contains(id) {
-                entries.insert(id.clone(), AllocEntry {
-                    id: id.clone(),
+                entries.insert(*id, AllocEntry {
+                    id: *id,
                     start_loc: loc,
                     end_loc: loc,
                     size: *size,
@@ -48,7 +48,7 @@ pub fn tetris_opt (kernel_proc: &mut KernelProcedure, var_changed: &Vec<String>)
         }
 
         if let Kernels::Dealloc { id, .. } = cmd {
-            entries.entry(id.clone())
+            entries.entry(*id)
                 .and_modify(|v| v.end_loc = loc);
         }
 
@@ -85,12 +85,12 @@ pub fn tetris_opt (kernel_proc: &mut KernelProcedure, var_changed: &Vec<String>)
 
         let change_mat = |m: &mut Matrix| {
             for entries in offsetted_entries.iter() {
-                if *entries.id == *m.id {
+                if entries.id == m.id {
                     if entries.offset > 0 {
                         let new_expr = Expression::make_add(m.access.clone(), Expression::make_const(entries.offset as i32));
                         m.access = new_expr; 
                     }
-                    m.id = "_temp".to_string()
+                    m.id = VarId::TEMP
                 }
             }
         };
@@ -121,7 +121,7 @@ pub fn tetris_opt (kernel_proc: &mut KernelProcedure, var_changed: &Vec<String>)
     });
 
     // ======================== Remove all allocs and deallocs assoacited with the entries ======================== 
-    let ids: Vec<String> = offsetted_entries.iter().map(|v| v.id.clone()).collect();
+    let ids: Vec<VarId> = offsetted_entries.iter().map(|v| v.id).collect();
     let mut id_proc = 0;
     let mut did_filter: HashSet<i32> = HashSet::new();
 
@@ -152,7 +152,7 @@ pub fn tetris_opt (kernel_proc: &mut KernelProcedure, var_changed: &Vec<String>)
 
     // =================== Insert temp allocation  =================== 
     if max_temp_size > 0 {
-        kernel_proc.insert(0, Kernels::Alloc { id: "_temp".to_string(), size: max_temp_size, content: None });
-        kernel_proc.insert(kernel_proc.len()-1, Kernels::Dealloc { id: "_temp".to_string(), size: max_temp_size });
+        kernel_proc.insert(0, Kernels::Alloc { id: VarId::TEMP, size: max_temp_size, content: None });
+        kernel_proc.insert(kernel_proc.len()-1, Kernels::Dealloc { id: VarId::TEMP, size: max_temp_size });
     }
 }
\ No newline at end of file
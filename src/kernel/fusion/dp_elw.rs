@@ -1,4 +1,5 @@
 use crate::kernel_decl::{KernelProcedure, Kernels};
+use super::fusion_conflicts;
 
 #[derive(Clone, Debug)]
 pub struct DpExprKernelInfo {
@@ -7,7 +8,15 @@ pub struct DpExprKernelInfo {
 
     pub a_shape: Option<(usize, usize)>,
     pub b_shape: Option<(usize, usize)>,
-    pub out_shape: Option<(usize, usize)>
+    pub out_shape: Option<(usize, usize)>,
+    pub batch_size: Option<usize>
+}
+
+impl DpExprKernelInfo {
+    // number of elements of the dot product's result (over all batches)
+    fn out_size (&self) -> Option<usize> {
+        Some(self.out_shape?.0 * self.out_shape?.1 * self.batch_size?)
+    }
 }
 
 pub fn fuse_dp_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize) {
@@ -17,13 +26,14 @@ pub fn fuse_dp_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize) {
         let mut in_kernel: Option<DpExprKernelInfo> = None; 
         
         for (i, cmd) in proc.iter().enumerate() {
-            if let Kernels::DotProd { a_shape, b_shape, res_shape: out_shape, .. } = cmd {
+            if let Kernels::DotProd { a_shape, b_shape, res_shape: out_shape, batch_size, .. } = cmd {
                 let new_kernel_info = DpExprKernelInfo { 
                     start_loc: i,
                     end_loc: i,
                     a_shape: Some(*a_shape),
                     b_shape: Some(*b_shape),
-                    out_shape: Some(*out_shape) 
+                    out_shape: Some(*out_shape),
+                    batch_size: Some(*batch_size)
                 };
 
                 if let Some(kernel_info) = &mut in_kernel {
@@ -32,6 +42,7 @@ pub fn fuse_dp_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize) {
                         kernel_info.a_shape = Some(*a_shape);
                         kernel_info.b_shape = Some(*b_shape);
                         kernel_info.out_shape = Some(*out_shape);
+                        kernel_info.batch_size = Some(*batch_size);
                         kernel_info.end_loc += 1;
                     } else {
                         // Dot product followed by another dot product (update to latest dp)
@@ -46,8 +57,8 @@ pub fn fuse_dp_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize) {
             else if let Kernels::ElwExpr { size, .. } = cmd {
                 // in current kernel with dot product previously
                 if let Some(kernel_info) = &mut in_kernel {
-                    // elw matches dot product dimensions
-                    if kernel_info.out_shape.is_some_and(|f| f.0 * f.1 == *size) {
+                    // elw matches dot product dimensions, and doesn't access the dot product's buffers across threads
+                    if kernel_info.out_size() == Some(*size) && !fusion_conflicts(&proc.kernels[kernel_info.start_loc..i], cmd) {
                         // push to elw ks 
                         kernel_info.end_loc += 1;
                         elw_ks.push(kernel_info.clone())
@@ -60,8 +71,8 @@ pub fn fuse_dp_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize) {
             else if let Kernels::Binary { size, .. } = cmd {
                 // in current kernel with dot product previously
                 if let Some(kernel_info) = &mut in_kernel {
-                    // elw matches dot product dimensions
-                    if kernel_info.out_shape.is_some_and(|f| f.0 * f.1 == *size) {
+                    // elw matches dot product dimensions, and doesn't access the dot product's buffers across threads
+                    if kernel_info.out_size() == Some(*size) && !fusion_conflicts(&proc.kernels[kernel_info.start_loc..i], cmd) {
                         // push to elw ks 
                         kernel_info.end_loc += 1;
                         elw_ks.push(kernel_info.clone())
@@ -74,8 +85,8 @@ pub fn fuse_dp_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize) {
             else if let Kernels::Unary { size, .. } = cmd {
                 // in current kernel with dot product previously
                 if let Some(kernel_info) = &mut in_kernel {
-                    // elw matches dot product dimensions
-                    if kernel_info.out_shape.is_some_and(|f| f.0 * f.1 == *size) {
+                    // elw matches dot product dimensions, and doesn't access the dot product's buffers across threads
+                    if kernel_info.out_size() == Some(*size) && !fusion_conflicts(&proc.kernels[kernel_info.start_loc..i], cmd) {
                         // push to elw ks 
                         kernel_info.end_loc += 1;
                         elw_ks.push(kernel_info.clone())
@@ -94,7 +105,8 @@ pub fn fuse_dp_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize) {
                         end_loc: i,
                         a_shape: None,
                         b_shape: None,
-                        out_shape: None 
+                        out_shape: None,
+                        batch_size: None
                     })
                 }
             }
@@ -107,7 +119,8 @@ pub fn fuse_dp_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize) {
                         end_loc: i,
                         a_shape: None,
                         b_shape: None,
-                        out_shape: None 
+                        out_shape: None,
+                        batch_size: None
                     })
                 }
             }
@@ -2,6 +2,7 @@
 // This could be unary, binary, etc. etc.
 
 use crate::kernel_decl::{KernelProcedure, Kernels};
+use super::FusionHazard;
 
 #[derive(Clone, Debug)]
 pub struct ElwExprKernelInfo {
@@ -9,6 +10,7 @@ pub struct ElwExprKernelInfo {
     pub start_loc: usize,
     pub end_loc: usize,
     pub num_cmds: usize,
+    pub hazard: FusionHazard
 }
 
 // fuse operations heavily rely on prox_rev_opt and prox_opt and grouping operations together!
@@ -22,16 +24,17 @@ pub fn fuse_elw_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize)
             let mut append = false;
             let mut create: Option<usize> = None;
 
-            let mut add_to_kernel = |size: &usize| {
+            let mut add_to_kernel = |size: &usize, cmd: &Kernels| {
                 if let Some(current_kernel) = in_kernel.as_mut() {
                         // in a potential elw kernel 
                     if let Some(kernel_size) = current_kernel.size {
-                        // same kernel size --> append
-                        if kernel_size == *size {
+                        // same kernel size and no cross-thread access to the buffers of the kernel --> append
+                        if kernel_size == *size && !current_kernel.hazard.conflicts(cmd) {
                             current_kernel.end_loc += 1;
                             current_kernel.num_cmds += 1;
+                            current_kernel.hazard.add(cmd);
                         }
-                        // different kernel size than the current pot elw kernel --> try to append to elw_ks and restart
+                        // different kernel size than the current pot elw kernel (or hazard) --> try to append to elw_ks and restart
                         else {
                             append = true;
 
@@ -44,26 +47,30 @@ pub fn fuse_elw_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize)
                         current_kernel.end_loc += 1;
                         current_kernel.num_cmds += 1;
                         current_kernel.size = Some(*size);
+                        current_kernel.hazard.add(cmd);
                     }
                 } else {
                     // not in pot kernel, create
+                    let mut hazard = FusionHazard::new();
+                    hazard.add(cmd);
                     in_kernel = Some(ElwExprKernelInfo { 
                         size: Some(*size), 
                         start_loc: i,
                         end_loc: i,
-                        num_cmds: 1
+                        num_cmds: 1,
+                        hazard
                     });
                 }
             };
 
             if let Kernels::Binary { size, .. } = cmd {
-                add_to_kernel(size);
+                add_to_kernel(size, cmd);
             }
             else if let Kernels::Unary { size, .. } = cmd {
-                add_to_kernel(size);
+                add_to_kernel(size, cmd);
             }
             else if let Kernels::Movement { size, .. } = cmd {
-                add_to_kernel(size);
+                add_to_kernel(size, cmd);
             }
             // try to encapsulate any allocs or deallocs as well
             // it's possible that we can apply any allocs/deallocs optimizations inside these fused kernels
@@ -78,6 +85,7 @@ pub fn fuse_elw_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize)
                         start_loc: i,
                         end_loc: i,
                         num_cmds: 0,
+                        hazard: FusionHazard::new()
                     });
                 }
             }
@@ -91,6 +99,7 @@ pub fn fuse_elw_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize)
                         start_loc: i,
                         end_loc: i,
                         num_cmds: 0,
+                        hazard: FusionHazard::new()
                     });
                 }
             } 
@@ -105,11 +114,14 @@ pub fn fuse_elw_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize)
                     }
                 }
                 in_kernel = if let Some(s) = create {
+                    let mut hazard = FusionHazard::new();
+                    hazard.add(cmd);
                     Some(ElwExprKernelInfo { 
                         size: Some(s), 
                         start_loc: i, 
                         end_loc: i, 
                         num_cmds: 1,
+                        hazard
                     })
                 } else {
                     None
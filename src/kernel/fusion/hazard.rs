@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+use crate::kernel_decl::{Input, Kernels};
+use crate::VarId;
+
+/*
+Every kernel within a fused kernel is executed by the same thread for the same #global index.
+So any buffer written inside the fusion must only be accessed at #global (the element owned by the thread). Otherwise:
+    * a thread could read an element that another thread hasn't computed yet
+    * a thread could overwrite an element that another thread still needs to read
+
+Ex (transpose of a result computed within the same fusion):
+    M (id: av, access: #global)  =  M (id: c, access: #global)  Multiply (9)  M (id: au, access: #global)
+    M (id: aw, access: #global)  <-(Move 9)-  M (id: av, access: (((#global % 3) * 3) + ((#global / 3) % 3)))
+
+Dot product and reduce read their inputs across threads (#x and #y), so their inputs are never considered #global
+Neither is the state of Rand (every thread reads the same seed and offset)
+*/
+#[derive(Clone, Debug, Default)]
+pub struct FusionHazard {
+    written: HashSet<VarId>,
+    read_non_global: HashSet<VarId>
+}
+
+fn get_reads<'a> (inp: &'a Input, reads: &mut Vec<(&'a VarId, bool)>) {
+    match inp {
+        Input::Mat { mat } => { reads.push((&mat.id, mat.access.is_global())); },
+        Input::ConcatMatrix { id_one, id_two, .. } => {
+            get_reads(id_one, reads);
+            get_reads(id_two, reads);
+        },
+        _ => {}
+    }
+}
+
+impl FusionHazard {
+    pub fn new () -> FusionHazard {
+        FusionHazard::default()
+    }
+
+    // returns (id, is accessed at #global)
+    fn reads (cmd: &Kernels) -> Vec<(&VarId, bool)> {
+        let mut reads = vec![];
+        for inp in cmd.get_inputs() {
+            get_reads(inp, &mut reads);
+        }
+
+        if let Kernels::DotProd { .. } | Kernels::Reduce { .. } | Kernels::Rand { .. } = cmd {
+            for r in reads.iter_mut() { r.1 = false; }
+        }
+
+        reads
+    }
+
+    fn kernels (cmd: &Kernels) -> Vec<&Kernels> {
+        match cmd.fus_get_kernels() {
+            Some(kernels) => kernels.iter().collect(),
+            None => vec![cmd]
+        }
+    }
+
+    fn conflicts_single (&self, cmd: &Kernels) -> bool {
+        let res = cmd.get_res();
+        let read_conflict = Self::reads(cmd).iter()
+            .any(|(id, is_global)| !is_global && self.written.contains(*id));
+        let write_conflict = res.is_some_and(|r| self.read_non_global.contains(r));
+
+        read_conflict || write_conflict
+    }
+
+    fn add_single (&mut self, cmd: &Kernels) {
+        for (id, is_global) in Self::reads(cmd) {
+            if !is_global { self.read_non_global.insert(*id); }
+        }
+
+        if let Some(res) = cmd.get_res() {
+            self.written.insert(*res);
+        }
+    }
+
+    // whether cmd can't be fused after the kernels added so far. cmd can be a fused kernel itself
+    pub fn conflicts (&self, cmd: &Kernels) -> bool {
+        let mut state = self.clone();
+        for k in Self::kernels(cmd) {
+            if state.conflicts_single(k) { return true; }
+            state.add_single(k);
+        }
+
+        false
+    }
+
+    pub fn add (&mut self, cmd: &Kernels) {
+        for k in Self::kernels(cmd) {
+            self.add_single(k);
+        }
+    }
+}
+
+// whether cmd can't be fused into the kernels of a pot fused kernel
+pub fn fusion_conflicts (kernels: &[Kernels], cmd: &Kernels) -> bool {
+    let mut hazard = FusionHazard::new();
+    for k in kernels {
+        hazard.add(k);
+    }
+
+    hazard.conflicts(cmd)
+}
+
+// ids that cmd reads at anything other than #global (ex: transposed)
+pub fn get_non_global_reads (cmd: &Kernels) -> Vec<&VarId> {
+    FusionHazard::reads(cmd).into_iter()
+        .filter(|(_, is_global)| !is_global)
+        .map(|(id, _)| id)
+        .collect()
+}
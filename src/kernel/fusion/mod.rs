@@ -1,7 +1,9 @@
 pub mod elw_expr;
 pub mod dp_elw;
 pub mod reduce_elw;
+pub mod hazard;
 
 pub use elw_expr::*;
 pub use dp_elw::*;
 pub use reduce_elw::*;
+pub use hazard::*;
@@ -1,4 +1,5 @@
 use crate::kernel_decl::{KernelProcedure, Kernels};
+use super::fusion_conflicts;
 
 #[derive(Clone, Debug)]
 pub struct ReduceExprKernelInfo {
@@ -43,8 +44,8 @@ pub fn fuse_rd_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize) {
             else if let Kernels::ElwExpr { size, .. } = cmd {
                 // in current kernel with reduce previously
                 if let Some(kernel_info) = &mut in_kernel {
-                    // elw matches reduce dimensions
-                    if kernel_info.vec_size.is_some() && kernel_info.vec_size.unwrap() == *size {
+                    // elw matches reduce dimensions, and doesn't access the reduce's buffers across threads
+                    if kernel_info.vec_size.is_some() && kernel_info.vec_size.unwrap() == *size && !fusion_conflicts(&proc.kernels[kernel_info.start_loc..i], cmd) {
                         // push to elw ks 
                         kernel_info.end_loc += 1;
                         elw_ks.push(kernel_info.clone())
@@ -57,8 +58,8 @@ pub fn fuse_rd_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize) {
             else if let Kernels::Binary { size, .. } = cmd {
                 // in current kernel with reduce previously
                 if let Some(kernel_info) = &mut in_kernel {
-                    // elw matches reduce dimensions
-                    if kernel_info.vec_size.is_some() && kernel_info.vec_size.unwrap() == *size {
+                    // elw matches reduce dimensions, and doesn't access the reduce's buffers across threads
+                    if kernel_info.vec_size.is_some() && kernel_info.vec_size.unwrap() == *size && !fusion_conflicts(&proc.kernels[kernel_info.start_loc..i], cmd) {
                         // push to elw ks 
                         kernel_info.end_loc += 1;
                         elw_ks.push(kernel_info.clone())
@@ -71,8 +72,8 @@ pub fn fuse_rd_expr (kernel_proc: &mut KernelProcedure, kernel_id: &mut usize) {
             else if let Kernels::Unary { size, .. } = cmd {
                 // in current kernel with reduce previously
                 if let Some(kernel_info) = &mut in_kernel {
-                    // elw matches reduce dimensions
-                    if kernel_info.vec_size.is_some() && kernel_info.vec_size.unwrap() == *size {
+                    // elw matches reduce dimensions, and doesn't access the reduce's buffers across threads
+                    if kernel_info.vec_size.is_some() && kernel_info.vec_size.unwrap() == *size && !fusion_conflicts(&proc.kernels[kernel_info.start_loc..i], cmd) {
                         // push to elw ks 
                         kernel_info.end_loc += 1;
                         elw_ks.push(kernel_info.clone())
@@ -161,16 +161,20 @@ impl Display for Kernels {
             Kernels::Reduce { a, res, op, vec_size, reduce_size, ..} => {
                 let _ = write!(f, "{} {} {} ({})", res, " = ".on_blue(), format!(" {:#?} (Vec/X: {}, Reduce/Y: {}) ", op, vec_size.to_string().yellow(), reduce_size.to_string().yellow()).bold(), a);
             },
-            Kernels::DotProd { a, b, res, a_shape, b_shape, ..} => {
+            Kernels::DotProd { a, b, res, a_shape, b_shape, batch_size, ..} => {
                 let a_one = a_shape.0.to_string().yellow();
                 let a_two = a_shape.1.to_string().yellow();
                 let b_one = b_shape.0.to_string().yellow();
                 let b_two = b_shape.1.to_string().yellow();
-                let _ = write!(f, "{} {} {} {} {}", res, " = ".on_blue(), a, format!(" ({}x{} DP {}x{})", a_one, a_two, b_one, b_two).bold(), b);
+                let batch = if *batch_size > 1 { format!("{}x ", batch_size.to_string().yellow()) } else { "".to_string() };
+                let _ = write!(f, "{} {} {} {} {}", res, " = ".on_blue(), a, format!(" ({}{}x{} DP {}x{})", batch, a_one, a_two, b_one, b_two).bold(), b);
             },
             Kernels::Movement { a, res , size, .. } => {
                 let _ = write!(f, "{} {} {}", res, format!(" <-(Move {})- ", size.to_string().yellow()).bold(), a);
             },
+            Kernels::Rand { state, res, dist, size, .. } => {
+                let _ = write!(f, "{} {} {} ({})", res, " = ".on_blue(), format!(" Rand {:?} ({}) ", dist, size.to_string().yellow()).bold(), state);
+            },
             Kernels::While { .. } => {
                 print_while(f, self, 0);
             },
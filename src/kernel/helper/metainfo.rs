@@ -1,10 +1,11 @@
 use std::collections::HashMap;
 use crate::kernel_decl::{Expression, Input, KernelProcedure, Kernels, Matrix, Output};
+use crate::VarId;
 
 // split this up when you have the chance
 
 impl Input {
-    pub fn get_id (&self) -> Vec<&String> {
+    pub fn get_id (&self) -> Vec<&VarId> {
         match self {
             Input::ConcatMatrix { id_one, id_two, .. } => {
                 vec![id_one.get_id(), id_two.get_id()].concat()
@@ -16,10 +17,10 @@ impl Input {
         }
     }
 
-    pub fn change_id (&mut self, match_id: &String, to_change: String) {
+    pub fn change_id (&mut self, match_id: &VarId, to_change: VarId) {
         match self {
             Input::ConcatMatrix { id_one, id_two, .. } => {
-                id_one.change_id(match_id, to_change.clone()); 
+                id_one.change_id(match_id, to_change); 
                 id_two.change_id(match_id, to_change); 
             },
             Input::Mat { mat } => {
@@ -29,7 +30,7 @@ impl Input {
         }
     }
 
-    pub fn get_mat_id (&self) -> Option<&String> {
+    pub fn get_mat_id (&self) -> Option<&VarId> {
         match self {
             Input::Mat { mat } => {
                 Some(&mat.id)
@@ -68,7 +69,7 @@ impl Input {
 }
 
 
-fn filter_access_expr<'a> (v: Vec<&'a Input>, id: &String) -> Vec<&'a Expression> {
+fn filter_access_expr<'a> (v: Vec<&'a Input>, id: &VarId) -> Vec<&'a Expression> {
     let res: Vec<_> = v.iter()
         .filter(|&&f| f.get_mat_id().is_some_and(|v| *v == *id))
         .map(|&f| f.get_access_expr().unwrap())
@@ -94,7 +95,7 @@ fn get_any_expr<'a> (v: Vec<&'a mut Input>) -> Vec<&'a mut Expression> {
 // you can abstract pretty much this entire thing into mat info or something like that
 impl Kernels {
     // get dependency access expressions
-    pub fn get_dep_access_expr (&self, id: &String) -> Vec<&Expression> {
+    pub fn get_dep_access_expr (&self, id: &VarId) -> Vec<&Expression> {
         match self {
             Kernels::Binary { a, b, .. } => {
                 filter_access_expr(vec![a, b], id)
@@ -105,6 +106,7 @@ impl Kernels {
             Kernels::Unary { a, .. }  => { filter_access_expr(vec![a], id) },
             Kernels::Reduce { a, .. }  => {  filter_access_expr(vec![a], id) },
             Kernels::Movement { a, .. }  => {  filter_access_expr(vec![a], id) },
+            Kernels::Rand { state, .. }  => {  filter_access_expr(vec![state], id) },
             // Kernel fusion operations are created only after memory optimization 
             _ => { vec![] }
         }
@@ -121,13 +123,14 @@ impl Kernels {
             Kernels::Unary { a, .. }  => { get_any_expr(vec![a]) },
             Kernels::Reduce { a, .. }  => {  get_any_expr(vec![a]) },
             Kernels::Movement { a, .. }  => {  get_any_expr(vec![a]) },
+            Kernels::Rand { state, .. }  => {  get_any_expr(vec![state]) },
             // Kernel fusion operations are created only after memory optimization 
             _ => { vec![] }
         }
     }
 
     // change all the dependencies if satisfies id to temp
-    pub fn change_dep_to_temp (&mut self, id: &String) {
+    pub fn change_dep_to_temp (&mut self, id: &VarId) {
         match self {
             Kernels::Binary { a, b, .. } => {
                 if a.get_mat_id().is_some_and(|f| *f == *id) { *a = Input::Temp; }
@@ -151,7 +154,7 @@ impl Kernels {
     }
 
     // Get dependencies of the command
-    pub fn get_dep_id (&self) -> Vec<&String> {
+    pub fn get_dep_id (&self) -> Vec<&VarId> {
         match self {
             Kernels::Binary { a, b, .. } => {
                 vec![a.get_id(), b.get_id()].concat()
@@ -168,6 +171,9 @@ impl Kernels {
             Kernels::Movement { a, .. }  => { 
                 a.get_id()
             },
+            Kernels::Rand { state, .. }  => { 
+                state.get_id()
+            },
             Kernels::While { conditional_var, .. } => {
                 vec![conditional_var]
             },
@@ -184,14 +190,14 @@ impl Kernels {
         }
     }
 
-    pub fn change_dep_id (&mut self, match_id: &String, to_change: String) {
+    pub fn change_dep_id (&mut self, match_id: &VarId, to_change: VarId) {
         match self {
             Kernels::Binary { a, b, .. } => {
-                a.change_id(match_id, to_change.clone());
+                a.change_id(match_id, to_change);
                 b.change_id(match_id, to_change);
             },
             Kernels::DotProd { a, b, .. } => {
-                a.change_id(match_id, to_change.clone());
+                a.change_id(match_id, to_change);
                 b.change_id(match_id, to_change);
             },
             Kernels::Unary { a, .. } => {   
@@ -203,6 +209,9 @@ impl Kernels {
             Kernels::Movement { a, .. } => {   
                 a.change_id(match_id, to_change);
             },
+            Kernels::Rand { state, .. } => {   
+                state.change_id(match_id, to_change);
+            },
             Kernels::While { conditional_var, .. } => {   
                 if *conditional_var == *match_id {
                     *conditional_var = to_change
@@ -211,7 +220,7 @@ impl Kernels {
             Kernels::If { conditions, .. } => {
                 for (cond, _) in conditions.iter_mut() {
                     if *cond == *match_id {
-                        *cond = match_id.clone();
+                        *cond = *match_id;
                     }
                 }
             },
@@ -227,12 +236,13 @@ impl Kernels {
             Kernels::Unary { res, .. } => Some(&res.access()),
             Kernels::Reduce { res, .. } => Some(&res.access()),
             Kernels::Movement { res, .. } => Some(&res.access()),
+            Kernels::Rand { res, .. } => Some(res.access()),
             _ => { None }
         }
     }
 
     // Get resultant of the command
-    pub fn get_res (&self) -> Option<&String> {
+    pub fn get_res (&self) -> Option<&VarId> {
         match self {
             Kernels::Alloc { id, .. } => Some(&id),
             Kernels::Binary { res, .. } => Some(&res.id()),
@@ -240,12 +250,27 @@ impl Kernels {
             Kernels::Unary { res, .. } => Some(&res.id()),
             Kernels::Reduce { res, .. } => Some(&res.id()),
             Kernels::Movement { res, .. } => Some(&res.id()),
+            Kernels::Rand { res, .. } => Some(res.id()),
             _ => { None } // kernel fusion operations are only created after memory opt
         }
     }
 
+    // Get number of elements of the resultant of the command
+    pub fn get_res_size (&self) -> Option<usize> {
+        match self {
+            Kernels::Alloc { size, .. } => Some(*size),
+            Kernels::Binary { size, .. } => Some(*size),
+            Kernels::DotProd { res_shape, batch_size, .. } => Some(batch_size * res_shape.0 * res_shape.1),
+            Kernels::Unary { size, .. } => Some(*size),
+            Kernels::Reduce { vec_size, .. } => Some(*vec_size),
+            Kernels::Movement { size, .. } => Some(*size),
+            Kernels::Rand { size, .. } => Some(*size),
+            _ => { None }
+        }
+    }
+
     // Changed resultant id of the command
-    pub fn change_res_id (&mut self, b: String) {
+    pub fn change_res_id (&mut self, b: VarId) {
         match self {
             Kernels::Alloc { id, .. } => { *id = b; }
             Kernels::Binary { res, .. } => { *res.mut_id() = b; },
@@ -253,6 +278,7 @@ impl Kernels {
             Kernels::Unary { res, .. } => { *res.mut_id() = b; },
             Kernels::Reduce { res, .. } => { *res.mut_id() = b; },
             Kernels::Movement { res, .. } => { *res.mut_id() = b; },
+            Kernels::Rand { res, .. } => { *res.mut_id() = b; },
             _ => {  } // kernel fusion operations are only created after memory opt
         }       
     }
@@ -301,9 +327,9 @@ impl Kernels {
 
 impl KernelProcedure {
     // Get total vars changed
-    pub fn get_var_changed (&mut self) -> Vec<String> {
-        let mut var_changed: Vec<String> = vec![];
-        let mut counter: HashMap<String, usize> = HashMap::new();
+    pub fn get_var_changed (&mut self) -> Vec<VarId> {
+        let mut var_changed: Vec<VarId> = vec![];
+        let mut counter: HashMap<VarId, usize> = HashMap::new();
 
         let mut func = |proc: &mut KernelProcedure| {
             for cmd in proc.iter() {
@@ -312,7 +338,7 @@ impl KernelProcedure {
 
                 let r = cmd.get_res();
                 if let Some(res) = r {
-                    counter.entry(res.clone())
+                    counter.entry(*res)
                         .and_modify(|v| *v += 1)
                         .or_insert(1);
                 }
@@ -320,7 +346,7 @@ impl KernelProcedure {
                 // *= or += ops
                 if let Kernels::Binary { a, b, res, .. } = cmd {
                     if a.get_mat_id().is_some_and(|f| *f == *res.id()) || b.get_mat_id().is_some_and(|f| *f == *res.id()) {
-                        var_changed.push(res.id().clone());
+                        var_changed.push(*res.id());
                     }
                 }
             }
@@ -328,8 +354,7 @@ impl KernelProcedure {
 
         self.apply(&mut func);
 
-        let to_append: Vec<(&String, &usize)> = counter.iter().filter(|&(_, v)| *v > 1).collect();
-        let to_append: Vec<String> = to_append.iter().map(|&(i, _)| i.clone()).collect();
+        let to_append: Vec<VarId> = counter.iter().filter(|&(_, v)| *v > 1).map(|(&i, _)| i).collect();
         
         for i in to_append {
             if !var_changed.contains(&i) {
@@ -340,9 +365,9 @@ impl KernelProcedure {
         var_changed
     }
 
-    pub fn replace_ref (&mut self, match_id: &String, to_change: String) {
+    pub fn replace_ref (&mut self, match_id: &VarId, to_change: VarId) {
         self.step_cmd(&mut |proc, idx| {
-            proc.get_mut(*idx).unwrap().change_dep_id(match_id, to_change.clone());
+            proc.get_mut(*idx).unwrap().change_dep_id(match_id, to_change);
             true
         });        
     }
@@ -25,6 +25,7 @@ impl Kernels {
             Kernels::Unary { a, .. } => vec![a],
             Kernels::Reduce { a, .. } => vec![a],
             Kernels::Movement { a, .. } => vec![a],
+            Kernels::Rand { state, .. } => vec![state],
             Kernels::Binary { a, b, .. } => vec![a, b],
             Kernels::DotProd { a, b, .. } => vec![a, b],
             
@@ -42,6 +43,7 @@ impl Kernels {
             Kernels::Unary { res, .. } => vec![res],
             Kernels::Reduce { res, .. } => vec![res],
             Kernels::Movement { res, .. } => vec![res],
+            Kernels::Rand { res, .. } => vec![res],
             Kernels::Binary { res, .. } => vec![res],
             Kernels::DotProd { res, .. } => vec![res],
 
@@ -1,15 +1,16 @@
 use crate::kernel_decl::{Expression, Matrix, Output};
+use crate::VarId;
 
 
 impl Output {
-    pub fn id (&self) -> &String {
+    pub fn id (&self) -> &VarId {
         match self {
             Output::Mat { mat } => { &mat.id },
             Output::Temp => panic!("Calling id() of temp")
         }
     }
 
-    pub fn mut_id (&mut self) -> &mut String {
+    pub fn mut_id (&mut self) -> &mut VarId {
         match self {
             Output::Mat { mat } => { &mut mat.id },
             Output::Temp => panic!("Calling id() of temp")
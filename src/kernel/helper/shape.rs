@@ -1,4 +1,4 @@
-use crate::{helper::debug::display_vec, kernel_decl::Expression, trackers::DataCmds};
+use crate::{helper::debug::display_vec, kernel_decl::Expression, trackers::DataCmds, PadMode};
 
 fn calc_stride (shape: &Vec<usize>) -> Vec<Expression> {
     let n = shape.len();
@@ -48,22 +48,55 @@ pub fn ndim_to_global (dim: &Vec<Expression>, shape: &Vec<usize>) -> Expression
     global_expr
 }
 
-pub fn ndim_change_datacmds (ndim: &mut Vec<Expression>, data_cmds: &Vec<DataCmds>) {
+// XY access of a (batched) matrix: X walks the rows of every leading dim flattened together, Y walks the last dim
+pub fn xy_to_ndim (shape: &Vec<usize>) -> Vec<Expression> {
+    if shape.len() == 2 {
+        return vec![Expression::make_x(), Expression::make_y()];
+    }
+
+    let mut ndim = global_to_ndim(Expression::make_x(), &shape[..shape.len()-1].to_vec());
+    ndim.push(Expression::make_y());
+    ndim
+}
+
+// shape as seen by XY access: (product of the leading dims, last dim) for batched matrices
+pub fn xy_shape (shape: &Vec<usize>) -> Vec<usize> {
+    if shape.len() <= 2 {
+        return shape.clone();
+    }
+
+    vec![shape[..shape.len()-1].iter().product(), *shape.last().unwrap()]
+}
+
+// maps the sink coords back to the source coords
+// returns the constant pads on the way as (out of bounds condition, fill value); outermost pad first
+pub fn ndim_change_datacmds (ndim: &mut Vec<Expression>, data_cmds: &Vec<DataCmds>) -> Vec<(Expression, f32)> {
+    let mut pad_masks = vec![];
     for cmd in data_cmds.iter().rev() { 
         match cmd { 
             DataCmds::Broadcast { dim, .. } => {
                 ndim[*dim] = Expression::make_const(0);
             },
             DataCmds::Index { index, dim } => {
-                ndim[*dim] = Expression::make_const(*index as i32);
+                // index removes the dim from the source; add it back
+                ndim.insert(*dim, Expression::make_const(*index as i32));
             },
             DataCmds::Permute { p } => {
+                // dim i of the sink is dim p[i] of the source
                 let mut new_dim = vec![Expression::make_const(0); ndim.len()];
                 for i in 0..ndim.len() {
-                    new_dim[i] = ndim[p[i]].clone()
+                    new_dim[p[i]] = ndim[i].clone()
                 }
                 *ndim = new_dim;
             },
+            DataCmds::Unfold { dim, step, dilation } => {
+                // window w, offset k --> w * step + k * dilation
+                let k = ndim.pop().unwrap();
+                ndim[*dim] = Expression::make_add(
+                    Expression::make_mult(ndim[*dim].clone(), Expression::make_const(*step as i32)),
+                    Expression::make_mult(k, Expression::make_const(*dilation as i32))
+                );
+            },
             DataCmds::View { sink_dim, source_dim } => {
                 let global = ndim_to_global(ndim, sink_dim);
                 *ndim = global_to_ndim(
@@ -71,6 +104,47 @@ pub fn ndim_change_datacmds (ndim: &mut Vec<Expression>, data_cmds: &Vec<DataCmd
                     source_dim
                 );
             },
+            DataCmds::Pad { dim, before, len, mode } => {
+                // comparisons are 0 or 1, so the remaps are branchless
+                let j = Expression::make_minus(ndim[*dim].clone(), Expression::make_const(*before as i32));
+                let last = Expression::make_const(*len as i32 - 1);
+                let zero = Expression::make_const(0);
+
+                ndim[*dim] = match mode {
+                    PadMode::Constant(val) => {
+                        // j < 0 and j > len - 1 can't both hold; their sum is the condition
+                        pad_masks.push((
+                            Expression::simplify(Expression::make_add(
+                                Expression::make_less_than(j.clone(), zero),
+                                Expression::make_more_than(j.clone(), last)
+                            )),
+                            *val
+                        ));
+                        j
+                    },
+                    PadMode::Reflect => {
+                        // |j|, then 2 * (len - 1) - j past the end
+                        let a = Expression::make_minus(
+                            j.clone(),
+                            Expression::make_mult(Expression::make_add(j.clone(), j.clone()), Expression::make_less_than(j, zero))
+                        );
+                        let over = Expression::make_minus(a.clone(), last.clone());
+                        Expression::make_minus(
+                            a.clone(),
+                            Expression::make_mult(Expression::make_add(over.clone(), over), Expression::make_more_than(a, last))
+                        )
+                    },
+                    PadMode::Replicate => {
+                        // clamp(j, 0, len - 1)
+                        let l = Expression::make_mult(j.clone(), Expression::make_more_than(j, zero));
+                        Expression::make_minus(
+                            l.clone(),
+                            Expression::make_mult(Expression::make_minus(l.clone(), last.clone()), Expression::make_more_than(l, last))
+                        )
+                    }
+                };
+            },
         }
     }
+    pad_masks
 }
\ No newline at end of file
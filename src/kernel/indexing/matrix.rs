@@ -1,6 +1,6 @@
 use crate::{
-    helper::shape::{global_to_ndim, ndim_change_datacmds, ndim_to_global}, 
-    kernel_decl::{Expression, Input, Matrix}
+    helper::shape::{global_to_ndim, ndim_change_datacmds, ndim_to_global, xy_shape, xy_to_ndim}, 
+    kernel_decl::{Expression, Input, Matrix}, VarId
 };
 use crate::trackers::{
     KernelTracker,
@@ -8,10 +8,10 @@ use crate::trackers::{
 };
 
 impl KernelTracker {    
-    pub fn get_inp_dep (&self, id: &String, ndim: &mut Vec<Expression>) -> Input {
+    pub fn get_inp_dep (&self, id: &VarId, ndim: &mut Vec<Expression>) -> Input {
         // is vars concat
         if let Some(result) = self.vars_concat.get(id) {
-            ndim_change_datacmds(ndim, &result.data_cmds);
+            let pad_masks = ndim_change_datacmds(ndim, &result.data_cmds);
 
             let mut ndim_two = ndim.clone();
             ndim_two[result.source.dim] = Expression::make_minus(
@@ -19,13 +19,19 @@ impl KernelTracker {
                 Expression::make_const(result.source.idx_end as i32)
             );
 
-            Input::ConcatMatrix { 
-                id_one: Box::new(self.get_inp_dep(&result.source.a, ndim)), 
-                id_two: Box::new(self.get_inp_dep(&result.source.b, &mut ndim_two)), 
-                conditional: Expression::simplify(
-                    Expression::make_more_than(ndim[result.source.dim].clone(), Expression::make_const(result.source.idx_end as i32 - 1))
-                )
-            }
+            // conditional must be computed before recursing, as the recursion changes ndim to the coords of the source
+            let conditional = Expression::simplify(
+                Expression::make_more_than(ndim[result.source.dim].clone(), Expression::make_const(result.source.idx_end as i32 - 1))
+            );
+
+            with_pad_masks(
+                Input::ConcatMatrix { 
+                    id_one: Box::new(self.get_inp_dep(&result.source.a, ndim)), 
+                    id_two: Box::new(self.get_inp_dep(&result.source.b, &mut ndim_two)), 
+                    conditional
+                },
+                pad_masks
+            )
         }
         // is sources concat
         else if let Some(result) = self.sources_concat.get(id) {
@@ -35,34 +41,39 @@ impl KernelTracker {
                 Expression::make_const(result.idx_end as i32)
             );
 
+            let conditional = Expression::simplify(
+                Expression::make_more_than(ndim[result.dim].clone(), Expression::make_const(result.idx_end as i32 - 1))
+            );
+
             Input::ConcatMatrix { 
                 id_one: Box::new(self.get_inp_dep(&result.a, ndim)), 
                 id_two: Box::new(self.get_inp_dep(&result.b, &mut ndim_two)), 
-                conditional: Expression::simplify(
-                    Expression::make_more_than(ndim[result.dim].clone(), Expression::make_const(result.idx_end as i32 - 1))
-                )
+                conditional
             }
         } 
 
         // dependency on var
         else if let Some(var_dep) = self.vars.get(id) {
-            ndim_change_datacmds(ndim, &var_dep.data_cmds);
+            let pad_masks = ndim_change_datacmds(ndim, &var_dep.data_cmds);
 
-            Input::Mat { 
-                mat: Matrix { 
-                    id: var_dep.id.clone(), 
-                    access: Expression::simplify(
-                        ndim_to_global(ndim, &var_dep.source_dims)
-                    ) 
-                }
-            }
+            with_pad_masks(
+                Input::Mat { 
+                    mat: Matrix { 
+                        id: var_dep.id, 
+                        access: Expression::simplify(
+                            ndim_to_global(ndim, &var_dep.source_dims)
+                        ) 
+                    }
+                },
+                pad_masks
+            )
         } 
         
         // on source var
         else if let Some(source_res) = self.sources.get(id) {
             Input::Mat { 
                 mat: Matrix { 
-                    id: source_res.id.clone(), 
+                    id: source_res.id, 
                     access: Expression::simplify(
                         ndim_to_global(ndim, &source_res.dim)
                     )
@@ -80,7 +91,7 @@ impl KernelTracker {
         }
     }
 
-    pub fn get_input (&self, id: &String, access_type: AccessType) -> Input {
+    pub fn get_input (&self, id: &VarId, access_type: AccessType) -> Input {
         let sink_shape = self.shape_tracker.get_shape(id);
 
         let mut ndim = match access_type {
@@ -91,8 +102,8 @@ impl KernelTracker {
                 )
             },
             AccessType::XY => {
-                assert_eq!(sink_shape.len(), 2, "Access type XY but concat result has result of 2");
-                vec![Expression::make_x(), Expression::make_y()]
+                assert!(sink_shape.len() == 2 || sink_shape.len() == 3, "Access type XY but the matrix isn't 2-dim (or batched 3-dim)");
+                xy_to_ndim(sink_shape)
             },
         };
 
@@ -102,23 +113,23 @@ impl KernelTracker {
             match access_type {
                 AccessType::Global => {
                     Input::Mat { mat: Matrix { 
-                        id: source_res.id.clone(),
+                        id: source_res.id,
                         access: Expression::make_global()
                     } }
                 },
                 AccessType::XY => {
                     let d = &source_res.dim;
                     assert!(
-                        d.len() == 2, 
-                        "Access type is XY, but the matrix isn't 2-dim"
+                        d.len() == 2 || d.len() == 3, 
+                        "Access type is XY, but the matrix isn't 2-dim (or batched 3-dim)"
                     );   
 
                     Input::Mat { mat: Matrix { 
-                        id: source_res.id.clone(),
+                        id: source_res.id,
                         access: Expression::simplify(
                             ndim_to_global(
                                 &vec![Expression::make_x(), Expression::make_y()],
-                                d
+                                &xy_shape(d)
                             )
                         )
                     } }
@@ -130,4 +141,16 @@ impl KernelTracker {
             self.get_inp_dep(id, &mut ndim)
         }
     }
-}
\ No newline at end of file
+}
+
+// constant pads select the fill value when out of bounds (id_two), so no padded copy is made
+// the outermost pad is checked first
+fn with_pad_masks (inp: Input, pad_masks: Vec<(Expression, f32)>) -> Input {
+    pad_masks.into_iter().rev().fold(inp, |inp, (conditional, val)| {
+        Input::ConcatMatrix {
+            id_one: Box::new(inp),
+            id_two: Box::new(Input::Constant { val }),
+            conditional
+        }
+    })
+}
@@ -1,4 +1,5 @@
-use crate::helper::shape::ndim_to_global;
+use crate::helper::shape::{ndim_to_global, xy_shape};
+use crate::VarId;
 use crate::kernel_decl::{Expression, Matrix, Output};
 use crate::trackers::{AccessType, KernelTracker};
 
@@ -14,21 +15,21 @@ However, we still need to know the dimensions. This is calculated manually witho
 */ 
 
 impl KernelTracker {
-    pub fn get_res (&self, id: &String, access_type: AccessType, expected_shape: &Vec<usize>) -> Output {
+    pub fn get_res (&self, id: &VarId, access_type: AccessType, expected_shape: &Vec<usize>) -> Output {
         return match access_type {
             AccessType::XY => {
                 Output::Mat { mat: Matrix { 
-                    id: id.clone(), 
+                    id: *id, 
                     access: Expression::simplify(ndim_to_global(
                         &vec![Expression::make_x(), Expression::make_y()], 
-                        &expected_shape
+                        &xy_shape(expected_shape)
                     ))
                 }
             } },
             AccessType::Global => {
                 Output::Mat { mat: 
                     Matrix { 
-                        id: id.clone(), 
+                        id: *id, 
                         access: Expression::make_global() 
                     }
                 }
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use crate::{RandDist, VarId};
 
 #[derive(Clone, Debug)]
 pub enum Value {
@@ -27,7 +28,7 @@ pub enum Expression {
 
 #[derive(Clone, Debug)]
 pub struct Matrix {
-    pub id: String,        // id of the alloc (we replace this id with a pointer at device)
+    pub id: VarId,         // id of the alloc (we replace this id with a pointer at device)
     pub access: Expression // note that access expressions can vary between the type of kernels. (sum vs. elw)
     // we use row-major (C++ like) and zero-indexing (C++ like)
 }
@@ -74,7 +75,8 @@ pub enum BinaryOp {
 #[derive(Clone, Debug)]
 pub enum ReduceOp {
     Sum,
-    Max 
+    Max,
+    Prod
 }
 
 #[derive(Clone, Debug)]
@@ -119,6 +121,11 @@ pub enum Kernels {
         a_shape: (usize, usize),
         b_shape: (usize, usize),
         res_shape: (usize, usize),
+
+        // batched dot product: the X access of a, b and res are rows of the batch flattened into the matrix
+        // batch i of a starts at row i * batch_stride.0 (and b at i * batch_stride.1). A stride of 0 shares the matrix across the batch
+        batch_size: usize,
+        batch_stride: (usize, usize)
     }, 
 
     // kernels that turns movement / permutations / concatenation of tensors into single contigious tensor.  
@@ -131,21 +138,31 @@ pub enum Kernels {
         size: usize,   // size of the result kernel
     },
 
+    // counter-based random numbers (Philox4x32-10); res at #global is sampled from dist
+    // every thread reads the seed, offset and epoch from state at #global = 0, 1 and 2, so it's never fused
+    Rand {
+        id: usize,
+        state: Input,
+        res: Output,
+        dist: RandDist,
+        size: usize    // size of the result kernel
+    },
+
     // Kernels related to allocation + deallocation
     Alloc {
-        id: String,
+        id: VarId,
         size: usize,
         content: Option<Arc<Vec<f32>>>
     },
     
     Dealloc {
-        id: String,
+        id: VarId,
         size: usize
     },
 
     // Control functions
-    While {conditional_var: String, block: KernelProcedure},
-    If {conditions: Vec<(String, KernelProcedure)>, else_proc: Option<KernelProcedure>},
+    While {conditional_var: VarId, block: KernelProcedure},
+    If {conditions: Vec<(VarId, KernelProcedure)>, else_proc: Option<KernelProcedure>},
     EX,
 
     // ================ Kernel Fusion ================ 
@@ -178,10 +195,9 @@ pub enum Kernels {
 }
 
 // list the procedure of kernels to declare
-// A light wrapper for hashmap<string, Vec<ComputeInstr>>
 // Implementations defined in procedure.rs
-// String in HashMap<String, ...> represents the block name
-// Vec<ComputeInstr> is the order of kernels to be executed
+// id is the name of the block (ex: main, or the procedure of a While / If)
+// kernels are executed in order; nested blocks are inside the While / If kernels
 #[derive(Clone, Debug)]
 pub struct KernelProcedure {
     pub kernels: Vec<Kernels>,
@@ -4,7 +4,7 @@ We prioritize the length of the chain over the number of chains there are.
 However, the less the number of chains, the better (thus the - chain_len)
 */
 
-use crate::kernel_decl::KernelProcedure;
+use crate::{kernel_decl::KernelProcedure, VarId};
 
 fn number_of_digits(n: usize) -> u32 {
     if n == 0 {
@@ -21,7 +21,7 @@ pub fn get_score (proc: &mut KernelProcedure) -> usize {
 
     let mut func = |proc: &mut KernelProcedure| {
         let mut chain: Vec<(usize, usize)> = vec![];
-        let mut init_ch: Option<(String, usize)> = None;
+        let mut init_ch: Option<(VarId, usize)> = None;
         
         if proc.len() > max_instr_len { max_instr_len = proc.len(); }
 
@@ -29,12 +29,12 @@ pub fn get_score (proc: &mut KernelProcedure) -> usize {
             if let Some(result) = cmd.get_res() {
                 match &init_ch {
                     None => { 
-                        init_ch = Some((result.clone(), idx));
+                        init_ch = Some((*result, idx));
                     },
                     Some((start_res, start_idx)) => {
                         if *result != *start_res {
                             if *start_idx != idx-1 { chain.push((*start_idx, idx-1)); }
-                            init_ch = Some((result.clone(), idx));
+                            init_ch = Some((*result, idx));
                         }
                     }
                 } 
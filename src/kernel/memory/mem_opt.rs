@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use crate::{core::ret_dep_list, kernel_decl::{KernelProcedure, Kernels}};
+use crate::{core::ret_dep_list, fusion::get_non_global_reads, kernel_decl::{KernelProcedure, Kernels}, VarId};
 
 #[derive(Debug, PartialEq)]
 pub struct RefLocation {
@@ -14,7 +14,7 @@ however, we anyways globalize all ids into one at a future optimization ("tetris
 So why mem opt? It actually helps a lot with grouping like operations together
 This massively helps kernel fusion, which is extremely important
 */
-pub fn mem_opt (proc: &mut KernelProcedure, var_changed: &Vec<String>) {
+pub fn mem_opt (proc: &mut KernelProcedure, var_changed: &[VarId]) {
     let mut dep_list = ret_dep_list();
 
     // ===================== Track variables that are from while or if statements ===================== 
@@ -23,17 +23,18 @@ pub fn mem_opt (proc: &mut KernelProcedure, var_changed: &Vec<String>) {
 
         if let Kernels::If { conditions, .. } = cmd {
             for (c, _) in conditions.iter() {
-                dep_list.insert(c.clone());
+                dep_list.insert(*c);
             }
         }
         else if let Kernels::While { conditional_var, .. } = cmd {
-            dep_list.insert(conditional_var.clone());
+            dep_list.insert(*conditional_var);
         }
     });
 
     // ========== get metadata about cmds ========== 
-    let mut res_to_procid: HashMap<String, String> = HashMap::new();
-    let mut res_ref_location: HashMap<String, Vec<RefLocation>> = HashMap::new();
+    let mut res_to_procid: HashMap<VarId, String> = HashMap::new();
+    let mut res_ref_location: HashMap<VarId, Vec<RefLocation>> = HashMap::new();
+    let mut res_size: HashMap<VarId, usize> = HashMap::new();
 
     let mut func_track = |proc: &mut KernelProcedure, idx: &mut usize| {
         let cmd = proc.get(*idx).unwrap();
@@ -43,14 +44,17 @@ pub fn mem_opt (proc: &mut KernelProcedure, var_changed: &Vec<String>) {
 
         if let Some(result) = res {
             if !deps.contains(&result) {
-                res_to_procid.insert(result.clone(), proc.id.clone());
-                res_ref_location.insert(result.clone(), vec![]); 
+                res_to_procid.insert(*result, proc.id.clone());
+                res_ref_location.insert(*result, vec![]); 
+                if let Some(size) = cmd.get_res_size() {
+                    res_size.insert(*result, size);
+                }
             }
         }
 
         for d in deps {
             res_ref_location
-                .entry(d.clone())
+                .entry(*d)
                 .and_modify(|x| x.push(RefLocation {
                     proc_id: proc.id.clone(),
                     idx: *idx
@@ -71,7 +75,7 @@ pub fn mem_opt (proc: &mut KernelProcedure, var_changed: &Vec<String>) {
         let cmd = kernels.get_mut(idx).unwrap();
         let deps = cmd.get_dep_id();
         let res = cmd.get_res();
-        let mut replace_var: Option<(String, String)> = None;
+        let mut replace_var: Option<(VarId, VarId)> = None;
 
         if let Some(result) = res {
             for &dep in deps.iter() {
@@ -98,6 +102,13 @@ pub fn mem_opt (proc: &mut KernelProcedure, var_changed: &Vec<String>) {
                 // check whether result and dep and not the same (ex: l = e * l after first round of opimization)
                 if result == dep || dep_list.contains(result) { continue; }
 
+                // the buffer of dep must be able to hold the result (ex: dep is broadcasted within the cmd)
+                if res_size.get(dep) != cmd.get_res_size().as_ref() { continue; }
+
+                // every thread must read dep at the same element it writes to, otherwise other threads could read an already overwritten element
+                // (ex: M (id: f, access: #global) = CS (V: -1) Multiply (4) M (id: f, access: (((#global % 2) << 1) + ((#global >> 1) % 2))))
+                if get_non_global_reads(cmd).contains(&dep) { continue; }
+
                 // We require these 3 commands to have unique result + input IDs as well.
                 // If we allow same ids, then we can get a result like this: by = sum(by, dim=-1)
                 // If you look at an access expression of something like this: 
@@ -107,10 +118,11 @@ pub fn mem_opt (proc: &mut KernelProcedure, var_changed: &Vec<String>) {
                 if let Kernels::DotProd { .. } = cmd { break; }
                 if let Kernels::Reduce { .. } = cmd { break; }
                 if let Kernels::Movement { .. } = cmd { break; }
+                if let Kernels::Rand { .. } = cmd { break; }
 
                 // Data manipulation 
                 // if so, set replace var
-                replace_var = Some( (result.clone(), dep.clone()) );
+                replace_var = Some( (*result, *dep) );
                 break;
             }
         }
@@ -118,18 +130,19 @@ pub fn mem_opt (proc: &mut KernelProcedure, var_changed: &Vec<String>) {
         // =================== Replace ================
         if let Some( (a, b) ) = replace_var {
             // replace result
-            cmd.change_res_id(b.clone());
+            cmd.change_res_id(b);
 
             // replace all references from a to b
             // re-iterating O(n^2) <-- could be faster since we already have the locations
-            proc.replace_ref(&a, b.clone());
+            proc.replace_ref(&a, b);
 
             // update metadata
             let v = res_to_procid.remove(&a);
             let ref_loc = res_ref_location.remove(&a);
+            res_size.remove(&a);
 
             if let Some(v) = v {
-                res_to_procid.insert(b.clone(), v);
+                res_to_procid.insert(b, v);
             }
 
             if let Some(ref_loc) = ref_loc {
@@ -4,14 +4,15 @@
 use std::collections::HashMap;
 
 use crate::kernel_decl::{KernelProcedure, Kernels};
+use crate::VarId;
 
 pub fn prox_opt (proc: &mut KernelProcedure) {
     let mut f = |proc: &mut KernelProcedure, c_idx: &mut usize| {
         let cmd_idx = *c_idx;
 
         // ============ find latest definitions locations ============
-        let mut res_loc: HashMap<String, usize> = HashMap::new();
-        let mut dep_loc: HashMap<String, usize> = HashMap::new(); 
+        let mut res_loc: HashMap<VarId, usize> = HashMap::new();
+        let mut dep_loc: HashMap<VarId, usize> = HashMap::new(); 
         for i in 0..cmd_idx {
             let i_cmd = proc.get(i).unwrap();
             let res = i_cmd.get_res();
@@ -19,13 +20,13 @@ pub fn prox_opt (proc: &mut KernelProcedure) {
 
             if let Some(result) = res {
                 res_loc
-                    .entry(result.clone()) 
+                    .entry(*result) 
                     .and_modify(|f| *f = i)
                     .or_insert(i);
             }
             for d in deps {
                 dep_loc
-                    .entry(d.clone()) 
+                    .entry(*d) 
                     .and_modify(|f| *f = i)
                     .or_insert(i);
             }
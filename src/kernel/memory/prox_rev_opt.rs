@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use crate::kernel_decl::{KernelProcedure, Kernels};
+use crate::{kernel_decl::{KernelProcedure, Kernels}, VarId};
 
 pub fn prox_rev_opt (proc: &mut KernelProcedure) {
     let mut swap_tracker: HashMap<(String, usize, usize), usize> = HashMap::new();
@@ -11,17 +11,17 @@ pub fn prox_rev_opt (proc: &mut KernelProcedure) {
         // =================== Check for chain ================
         // However, there is a seperate optimization for chains alone, we are doing just a prelim check.
         let mut chain: Vec<(usize, usize)> = vec![];
-        let mut init_ch: Option<(String, usize)> = None;
+        let mut init_ch: Option<(VarId, usize)> = None;
         for (idx, cmd) in proc.iter().enumerate() {
             if let Some(result) = cmd.get_res() {
                 match &init_ch {
                     None => { 
-                        init_ch = Some((result.clone(), idx));
+                        init_ch = Some((*result, idx));
                     },
                     Some((start_res, start_idx)) => {
                         if *result != *start_res {
                             if *start_idx != idx-1 { chain.push((*start_idx, idx-1)); }
-                            init_ch = Some((result.clone(), idx));
+                            init_ch = Some((*result, idx));
                         }
                     }
                 } 
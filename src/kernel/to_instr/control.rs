@@ -1,5 +1,5 @@
 use crate::{
-    kernel_decl::{KernelProcedure, Kernels}, to_kernel::convert_to_proc, trackers::KernelTracker, Device, IRCmds, IRProcedure
+    kernel_decl::{KernelProcedure, Kernels}, to_kernel::convert_to_proc, trackers::KernelTracker, Device, IRCmds, IRProcedure, VarId
 };
 
 fn handle_embed_proc (device: &dyn Device, mat_tracker: &mut KernelTracker, p: &IRProcedure, kernel_id: &mut usize) -> KernelProcedure {
@@ -19,8 +19,8 @@ fn handle_embed_proc (device: &dyn Device, mat_tracker: &mut KernelTracker, p: &
 pub fn to_control (device: &dyn Device, cmd: &IRCmds, instr: &mut Vec<Kernels>, mat_tracker: &mut KernelTracker, kernel_id: &mut usize) {
     match cmd {
         IRCmds::If { conditions, else_proc } => {
-            let conditions: Vec<(String, KernelProcedure)> = conditions.iter().map(|(cond, p)| {
-                (cond.clone(), handle_embed_proc(device, mat_tracker, p, kernel_id))
+            let conditions: Vec<(VarId, KernelProcedure)> = conditions.iter().map(|(cond, p)| {
+                (*cond, handle_embed_proc(device, mat_tracker, p, kernel_id))
             }).collect();
 
             let else_proc = else_proc.as_ref().map(|p| {
@@ -34,7 +34,7 @@ pub fn to_control (device: &dyn Device, cmd: &IRCmds, instr: &mut Vec<Kernels>,
         },
         IRCmds::While { conditional_var, block } => {
             instr.push(Kernels::While { 
-                conditional_var: conditional_var.clone(), 
+                conditional_var: *conditional_var, 
                 block: handle_embed_proc(device, mat_tracker, block, kernel_id) 
             });
         }
@@ -1,4 +1,4 @@
-// Dot product, movement, and sum are considered "special" kernels
+// Dot product, movement, sum, and rand are considered "special" kernels
 // The reason why is because they require different access expressions than most other kernels
 // If you notice at mem_opt, we skip these special kernels because their access expressions can be intrude one another
 // This is not the case for Element-wise, comparison, or unary operations
@@ -12,7 +12,13 @@ use crate::{
 
 pub fn to_special (device: &dyn Device, cmd: &IRCmds, instr: &mut Vec<Kernels>, mat_tracker: &KernelTracker, kernel_id: &mut usize) {
     match cmd {
-        IRCmds::Sum { a, res } => {
+        IRCmds::Sum { a, res } | IRCmds::Max { a, res } | IRCmds::Prod { a, res } => {
+            let op = match cmd {
+                IRCmds::Sum { .. } => ReduceOp::Sum,
+                IRCmds::Max { .. } => ReduceOp::Max,
+                _ => ReduceOp::Prod
+            };
+
             let mut exp_dim = mat_tracker.get_shape(a).clone();
             let vec_size = exp_dim.first().unwrap().clone();
             let reduce_size = exp_dim.last().unwrap().clone();
@@ -21,7 +27,7 @@ pub fn to_special (device: &dyn Device, cmd: &IRCmds, instr: &mut Vec<Kernels>,
             instr.push(Kernels::Reduce { 
                 a: mat_tracker.get_input(a, AccessType::XY),
                 res: mat_tracker.get_res(res, AccessType::XY, &exp_dim),
-                op: ReduceOp::Sum,
+                op,
                 vec_size,
                 reduce_size,
                 id: *kernel_id
@@ -34,21 +40,47 @@ pub fn to_special (device: &dyn Device, cmd: &IRCmds, instr: &mut Vec<Kernels>,
             let b_shape = mat_tracker.get_shape(b); 
             let res_shape = device.dot_prod_shape(a_shape, b_shape);
             
+            // last two dims; the leading dim (if any) is the batch
             let convert_tuple = |v: &Vec<usize>| { 
-                assert_eq!(v.len(), 2, "convert tuple to length 2 invalid");
-                (v[0], v[1])
+                assert!(v.len() == 2 || v.len() == 3, "convert tuple to length 2 invalid");
+                (v[v.len()-2], v[v.len()-1])
             };
             
-            assert!(a_shape.len() == 2 && b_shape.len() == 2, "dot prod at kernel lowering has wrong dimensions");
+            assert!(
+                (2..=3).contains(&a_shape.len()) && (2..=3).contains(&b_shape.len()), 
+                "dot prod at kernel lowering has wrong dimensions"
+            );
+            if a_shape.len() == 3 && b_shape.len() == 3 {
+                assert_eq!(a_shape[0], b_shape[0], "dot prod at kernel lowering has mismatched batch sizes");
+            }
+
+            let batch_size = if res_shape.len() == 3 { res_shape[0] } else { 1 };
+            let batch_stride = (
+                if a_shape.len() == 3 { a_shape[1] } else { 0 },
+                if b_shape.len() == 3 { b_shape[1] } else { 0 }
+            );
 
             // Dot product instruction
             instr.push(Kernels::DotProd { 
                 a: mat_tracker.get_input(a, AccessType::XY), 
                 b: mat_tracker.get_input(b, AccessType::XY), 
                 res: mat_tracker.get_res(res, AccessType::XY, &res_shape),
-                a_shape: convert_tuple(&a_shape),
-                b_shape: convert_tuple(&b_shape),
+                a_shape: convert_tuple(a_shape),
+                b_shape: convert_tuple(b_shape),
                 res_shape: convert_tuple(&res_shape),
+                batch_size,
+                batch_stride,
+                id: *kernel_id
+            });
+
+            *kernel_id += 1;
+        },
+        IRCmds::Rand { state, dist, dim, res } => {
+            instr.push(Kernels::Rand {
+                state: mat_tracker.get_input(state, AccessType::Global),
+                res: mat_tracker.get_res(res, AccessType::Global, dim),
+                dist: *dist,
+                size: dim.iter().product(),
                 id: *kernel_id
             });
 
@@ -71,7 +103,7 @@ pub fn to_special (device: &dyn Device, cmd: &IRCmds, instr: &mut Vec<Kernels>,
         // this exists for allocations
         IRCmds::CreateMat { contents, dim, id } => {
             instr.push(Kernels::Alloc { 
-                id: id.clone(), 
+                id: *id, 
                 size: dim.iter().product::<usize>(), 
                 content: Some(contents.clone())
             })
@@ -6,8 +6,10 @@ use crate::{
     memory::{get_score, mem_opt, prox_opt, prox_rev_opt}, 
     to_instr::{to_comp, to_control, to_elw, to_special, to_unary}, 
     Device, 
+    IRCmds,
     IRProcedure
 };
+use crate::core::{env_flags::disable_kernel_opt, ret_dep_list};
 use super::trackers::KernelTracker;
 
 pub fn convert_to_proc (device: &dyn Device, kernel_tracker: &mut KernelTracker, proc: &IRProcedure, kernel_id: &mut usize) -> KernelProcedure {
@@ -23,6 +25,13 @@ pub fn convert_to_proc (device: &dyn Device, kernel_tracker: &mut KernelTracker,
 
         // step kernel tracker
         kernel_tracker.step(device, cmd);
+
+        // data manipulation is 0-cost, so the result is never allocated.
+        // If the user wants to read it, then we have to make it contigious
+        if let Some(contigious) = materialize_dep(kernel_tracker, cmd) {
+            to_special(device, &contigious, &mut kernels, kernel_tracker, kernel_id);
+            kernel_tracker.step(device, &contigious);
+        }
     }
 
     KernelProcedure::new(
@@ -31,15 +40,48 @@ pub fn convert_to_proc (device: &dyn Device, kernel_tracker: &mut KernelTracker,
     )
 }
 
+fn materialize_dep (kernel_tracker: &KernelTracker, cmd: &IRCmds) -> Option<IRCmds> {
+    let res = match cmd {
+        IRCmds::View { res, .. } => res,
+        IRCmds::Index { res, .. } => res,
+        IRCmds::Concat { res, .. } => res,
+        IRCmds::Permute { res, .. } => res,
+        IRCmds::Broadcast { res, .. } => res,
+        IRCmds::Unfold { res, .. } => res,
+        IRCmds::Pad { res, .. } => res,
+        _ => { return None; }
+    };
+
+    if !ret_dep_list().contains(res) { return None; }
+
+    // can't read and write to the same source with different access expressions (ex: l = l.view(...))
+    let is_diff_source = match kernel_tracker.vars.get(res) {
+        Some(var) => var.id != *res,
+        None => kernel_tracker.vars_concat.contains_key(res) || kernel_tracker.sources_concat.contains_key(res)
+    };
+
+    if is_diff_source {
+        Some(IRCmds::Contigious { a: *res, res: *res })
+    } else {
+        None
+    }
+}
+
 pub fn to_kernel (device: &dyn Device, proc: &IRProcedure) -> (KernelProcedure, KernelTracker) {
     let mut kernel_id: usize = 0;
 
     // ========== Create initial procedure with kernel tracker ========== 
     let mut kernel_tracker = KernelTracker::new();
     let mut kernel_proc = convert_to_proc(device, &mut kernel_tracker, proc, &mut kernel_id);
+    let var_changed = kernel_proc.get_var_changed(); 
+
+    // skip opt if we don't want it (only allocations are inserted)
+    if disable_kernel_opt() {
+        insert_alloc(device, &mut kernel_proc, &var_changed);
+        return (kernel_proc, kernel_tracker);
+    }
 
     // ========= Memory Optimization ==========
-    let var_changed = kernel_proc.get_var_changed(); 
     mem_opt(&mut kernel_proc, &var_changed);
 
     let mut prev_max_val: Option<usize> = None;
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use std::collections::{HashMap, HashSet};
 use crate::kernel_decl::{Kernels};
+use crate::VarId;
 use crate::trackers::ShapeTrackerKernel;
 use crate::Device;
 
@@ -12,7 +13,7 @@ pub struct Location {
 
 #[derive(Clone, Debug)]
 pub struct AllocEntry {
-    pub id: String,
+    pub id: VarId,
     pub size: usize,
 
     // if there's data needed to be allocation before running program, then specify what content this is
@@ -31,14 +32,14 @@ pub struct AllocEntry {
 pub struct AllocTracker<'a> {
     // hlir id is same as the alloc id
     // However, not all hlir id will be in vars (ex: referencing source vars)
-    pub vars: HashMap<String, AllocEntry>,  
-    pub dep_vars: &'a HashSet<String>, // ideally, combine dep vars and var changed
-    pub var_changed: &'a Vec<String>,
+    pub vars: HashMap<VarId, AllocEntry>,  
+    pub dep_vars: &'a HashSet<VarId>, // ideally, combine dep vars and var changed
+    pub var_changed: &'a [VarId],
     pub shape_tracker: ShapeTrackerKernel
 }
 
 impl<'a> AllocTracker<'a> {
-    pub fn new (dep_vars: &'a HashSet<String>, var_changed: &'a Vec<String>) -> AllocTracker<'a> {
+    pub fn new (dep_vars: &'a HashSet<VarId>, var_changed: &'a [VarId]) -> AllocTracker<'a> {
         AllocTracker {  
             vars: HashMap::new(),
             var_changed,
@@ -47,16 +48,16 @@ impl<'a> AllocTracker<'a> {
         }
     }
     
-    pub fn update_vars (&mut self, ir_id: &String, total_dim: usize, loc: Location) {
+    pub fn update_vars (&mut self, ir_id: &VarId, total_dim: usize, loc: Location) {
         if self.vars.contains_key(ir_id) {
-            self.vars.entry(ir_id.clone()).and_modify(|f| {
+            self.vars.entry(*ir_id).and_modify(|f| {
                 f.size = f.size.max(total_dim)
             });
         } else {
             self.vars.insert(
-                ir_id.clone(), 
+                *ir_id, 
                 AllocEntry { 
-                    id: ir_id.clone(), 
+                    id: *ir_id, 
                     size: total_dim, 
                     initial_content: None,
                     alloc_loc: 
@@ -82,9 +83,9 @@ impl<'a> AllocTracker<'a> {
             // ignore all data manipulation cmds
             Kernels::Alloc { content, size, id, .. } => {
                 self.vars.insert(
-                    id.clone(),
+                    *id,
                     AllocEntry { 
-                        id: id.clone(), 
+                        id: *id, 
                         size: *size,
                         initial_content: content.clone(),
                         alloc_loc: loc.clone(), 
@@ -126,7 +127,7 @@ impl<'a> AllocTracker<'a> {
         }
     }
 
-    pub fn get_alloc (&self, id: &String) -> &AllocEntry {
+    pub fn get_alloc (&self, id: &VarId) -> &AllocEntry {
         self.vars.get(id).unwrap()
     }
 }
\ No newline at end of file
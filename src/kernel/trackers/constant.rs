@@ -1,10 +1,11 @@
 use std::collections::HashMap;
 
 use crate::IRCmds;
+use crate::VarId;
 
 #[derive(Clone)]
 pub struct ConstantTracker {
-    pub vars: HashMap<String, f32>
+    pub vars: HashMap<VarId, f32>
 }
 
 impl ConstantTracker {
@@ -15,7 +16,7 @@ impl ConstantTracker {
     pub fn step (&mut self, cmd: &IRCmds) { 
         // we ignore the dim
         if let IRCmds::CreateConstant { contents, id, .. } = cmd {
-            self.vars.insert(id.clone(), *contents);
+            self.vars.insert(*id, *contents);
         }
 
         if let IRCmds::ElwMultiplyEq { s, o } = cmd {
@@ -55,7 +56,7 @@ impl ConstantTracker {
         // any other way or method?
     }
 
-    pub fn get_f32 (&self, id: &String) -> Option<f32> {
+    pub fn get_f32 (&self, id: &VarId) -> Option<f32> {
         match self.vars.get(id) {
             None => None,
             Some(v) => Some(*v)
@@ -1,7 +1,7 @@
 
 use std::collections::HashMap;
 use crate::{
-    ir::helper::ir_to_res, trackers::ConstantTracker, Device, IRCmds
+    ir::helper::ir_to_res, trackers::ConstantTracker, Device, IRCmds, PadMode, VarId
 };
 use super::ShapeTracker;
 
@@ -11,26 +11,28 @@ pub enum DataCmds {
     View { source_dim: Vec<usize>, sink_dim: Vec<usize> },
     Index { index: usize, dim: usize },
     Permute { p: Vec<usize> }, 
-    Broadcast { dim: usize, r: usize }
+    Broadcast { dim: usize, r: usize },
+    Unfold { dim: usize, step: usize, dilation: usize },
+    Pad { dim: usize, before: usize, len: usize, mode: PadMode } // len: source length along dim
 }
 
 #[derive(Clone, Debug)]
 pub struct VarDependency {
-    pub id: String,
+    pub id: VarId,
     pub source_dims: Vec<usize>,
     pub data_cmds: Vec<DataCmds>
 }
 
 #[derive(Clone, Debug)]
 pub struct VarSource {
-    pub id: String,
+    pub id: VarId,
     pub dim: Vec<usize>
 }
 
 #[derive(Clone, Debug)]
 pub struct VarConcat {
-    pub a: String,
-    pub b: String,
+    pub a: VarId,
+    pub b: VarId,
     pub dim: usize,
     pub idx_end: usize,
 }
@@ -45,10 +47,10 @@ pub struct VarConcatDep {
 pub struct KernelTracker {
     // given sink variable, get source variable and the steps to reach to sink var
     // Vars and sources hashmap keys must always come from alloc tracker's id (except for concat vars)
-    pub sources: HashMap<String, VarSource>,                // tracks source variables (no var dependency)
-    pub vars: HashMap<String, VarDependency>,               // tracks the dependency of variables that are related to source (list of DataCmds)
-    pub sources_concat: HashMap<String, VarConcat>,         // tracks concat variables 
-    pub vars_concat: HashMap<String, VarConcatDep>,         // tracks variables that references concat variables
+    pub sources: HashMap<VarId, VarSource>,                // tracks source variables (no var dependency)
+    pub vars: HashMap<VarId, VarDependency>,               // tracks the dependency of variables that are related to source (list of DataCmds)
+    pub sources_concat: HashMap<VarId, VarConcat>,         // tracks concat variables 
+    pub vars_concat: HashMap<VarId, VarConcatDep>,         // tracks variables that references concat variables
 
     pub shape_tracker: ShapeTracker,                        // tracks the shape of variables
     pub constant_tracker: ConstantTracker,                  // tracks constant tracker
@@ -75,7 +77,7 @@ impl KernelTracker {
 
     pub fn step (&mut self, device: &dyn Device, cmd: &IRCmds) {
         let mut prev_dim: Vec<usize> = vec![];
-        if let IRCmds::View { a, .. } = cmd {
+        if let IRCmds::View { a, .. } | IRCmds::Pad { a, .. } = cmd {
             prev_dim = self.shape_tracker.get_shape(&a).clone();
         }
 
@@ -84,41 +86,51 @@ impl KernelTracker {
         self.constant_tracker.step(cmd);
 
         // track the sources and the variables
-        let mut dep_cmp: String = "".to_string();
-        let mut res_cmp: String = "".to_string();        
-        let mut data_clone: Option<(String, VarConcat)> = None;
+        let mut dep_cmp: VarId = VarId::NONE;
+        let mut res_cmp: VarId = VarId::NONE;        
+        let mut data_clone: Option<(VarId, VarConcat)> = None;
         let mut data_cmd: Option<DataCmds> = None;
         
         if let IRCmds::View { a, res, .. } = cmd {
             let sink_dim = self.shape_tracker.get_shape(&res).clone();
-            dep_cmp = a.clone();
-            res_cmp = res.clone();
+            dep_cmp = *a;
+            res_cmp = *res;
             data_cmd = Some(DataCmds::View { source_dim: prev_dim, sink_dim });
         }
         else if let IRCmds::Index { a, index, dim, res } = cmd {
-            dep_cmp = a.clone();
-            res_cmp = res.clone();
+            dep_cmp = *a;
+            res_cmp = *res;
             data_cmd = Some(DataCmds::Index { index: index.clone(), dim: dim.clone() });
         }
         else if let IRCmds::Concat { a, b, dim, res } = cmd {
             assert!(res != a && res != b, "Res id can't be the same as a and b id at Concat");
             let idx_end = self.shape_tracker.get_shape(a)[*dim].clone();
-            data_clone = Some((res.clone(), VarConcat {
-                a: a.clone(), 
-                b: b.clone(), 
+            data_clone = Some((*res, VarConcat {
+                a: *a, 
+                b: *b, 
                 dim: *dim,
                 idx_end,
             }));
         }
         else if let IRCmds::Permute { a, p, res } = cmd {
-            dep_cmp = a.clone();
-            res_cmp = res.clone();
+            dep_cmp = *a;
+            res_cmp = *res;
             data_cmd = Some(DataCmds::Permute { p: p.clone() });
         }
         else if let IRCmds::Broadcast { a, dim, r, res } = cmd {
-            dep_cmp = a.clone();
-            res_cmp = res.clone();
+            dep_cmp = *a;
+            res_cmp = *res;
             data_cmd = Some(DataCmds::Broadcast { dim: *dim, r: *r });
+        }
+        else if let IRCmds::Unfold { a, dim, step, dilation, res, .. } = cmd {
+            dep_cmp = *a;
+            res_cmp = *res;
+            data_cmd = Some(DataCmds::Unfold { dim: *dim, step: *step, dilation: *dilation });
+        }
+        else if let IRCmds::Pad { a, dim, before, mode, res, .. } = cmd {
+            dep_cmp = *a;
+            res_cmp = *res;
+            data_cmd = Some(DataCmds::Pad { dim: *dim, before: *before, len: prev_dim[*dim], mode: *mode });
         } else {
             if let Some(id) = ir_to_res(cmd) {
                 // only cmd satisfying this is CreateMat. If it's a constant, then skip
@@ -127,13 +139,13 @@ impl KernelTracker {
                 }
 
                 // if we are redefining a source, then remove from self.vars (which tracks broadcasting, view, etc.)
-                self.vars.remove_entry(id);
+                self.vars.remove_entry(&id);
 
                 let shape = self.shape_tracker.get_shape(&id).clone();
                 self.sources.insert(
-                    id.clone(), 
+                    id, 
                     VarSource { 
-                        id: id.clone(),
+                        id: id,
                         dim: shape
                     }
                 );
@@ -153,9 +165,9 @@ impl KernelTracker {
              */
             if let Some(var_source) = self.sources.get(&dep_cmp) {
                 self.vars.insert(
-                    res_cmp.clone(),
+                    res_cmp,
                     VarDependency {
-                        id: var_source.id.clone(),
+                        id: var_source.id,
                         source_dims: var_source.dim.clone(),
                         data_cmds: vec![cmds],
                     }
@@ -186,7 +198,7 @@ impl KernelTracker {
                     let mut dep = dep.clone();
                     dep.data_cmds.push(cmds);
                     self.vars.insert(
-                        res_cmp.clone(),
+                        res_cmp,
                         dep
                     );
                 }
@@ -199,7 +211,7 @@ impl KernelTracker {
             */
             else if let Some(res) = self.sources_concat.get(&dep_cmp) {
                 self.vars_concat.insert(
-                    res_cmp.clone(),
+                    res_cmp,
                     VarConcatDep {
                         source: res.clone(),
                         data_cmds: vec![cmds],
@@ -220,7 +232,7 @@ impl KernelTracker {
                     let mut dep = dep.clone();
                     dep.data_cmds.push(cmds);
                     self.vars_concat.insert(
-                        res_cmp.clone(),
+                        res_cmp,
                         dep
                     );
                 }
@@ -232,12 +244,12 @@ impl KernelTracker {
     }
 
     // wrapper over shape tracker
-    pub fn get_shape (&self, id: &String) -> &Vec<usize> {
+    pub fn get_shape (&self, id: &VarId) -> &Vec<usize> {
         self.shape_tracker.get_shape(id)
     }
 
     // wrapper over constant tracker
-    pub fn get_constant (&self, id: &String) -> Option<f32> {
+    pub fn get_constant (&self, id: &VarId) -> Option<f32> {
         self.constant_tracker.get_f32(id)
     }
 }
\ No newline at end of file
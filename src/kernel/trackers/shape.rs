@@ -1,9 +1,9 @@
 use std::collections::HashMap;
-use crate::{Device, IRCmds};
+use crate::{graph::data::unfold::unfold_windows, Device, IRCmds, VarId};
 
 #[derive(Clone, Debug)]
 pub struct ShapeTracker {
-    pub shape: HashMap<String, Vec<usize>>
+    pub shape: HashMap<VarId, Vec<usize>>
 }
 
 impl ShapeTracker {
@@ -15,32 +15,32 @@ impl ShapeTracker {
         match cmd {
             IRCmds::CreateMat { dim, id, .. } => {
                 self.shape.insert(
-                    id.clone(),
+                    *id,
                     dim.clone()
                 );
             },
             IRCmds::CreateConstant { id, dim, .. } => {
                 self.shape.insert(
-                    id.clone(),
+                    *id,
                     dim.clone()
                 );
             },
             
-            IRCmds::ElwMultiply { a, res, .. } => { self.shape.insert( res.clone(), self.shape.get(a).unwrap().clone() ); },
-            IRCmds::ElwAdd { a, res, .. } => { self.shape.insert( res.clone(), self.shape.get(a).unwrap().clone() ); },
-            IRCmds::ElwMultiplyEq { s, o, .. } => { self.shape.insert( s.clone(), self.shape.get(o).unwrap().clone() ); },
-            IRCmds::ElwAddEq { s, o, .. } => { self.shape.insert( s.clone(), self.shape.get(o).unwrap().clone() ); },
+            IRCmds::ElwMultiply { a, res, .. } => { self.shape.insert( *res, self.shape.get(a).unwrap().clone() ); },
+            IRCmds::ElwAdd { a, res, .. } => { self.shape.insert( *res, self.shape.get(a).unwrap().clone() ); },
+            IRCmds::ElwMultiplyEq { s, o, .. } => { self.shape.insert( *s, self.shape.get(o).unwrap().clone() ); },
+            IRCmds::ElwAddEq { s, o, .. } => { self.shape.insert( *s, self.shape.get(o).unwrap().clone() ); },
 
-            IRCmds::EqualZero { a, res, .. } => { self.shape.insert( res.clone(), self.shape.get(a).unwrap().clone() ); },
-            IRCmds::MoreZero { a, res, .. } => { self.shape.insert( res.clone(), self.shape.get(a).unwrap().clone() ); },
-            IRCmds::LessZero { a, res, .. } => { self.shape.insert( res.clone(), self.shape.get(a).unwrap().clone() ); },
+            IRCmds::EqualZero { a, res, .. } => { self.shape.insert( *res, self.shape.get(a).unwrap().clone() ); },
+            IRCmds::MoreZero { a, res, .. } => { self.shape.insert( *res, self.shape.get(a).unwrap().clone() ); },
+            IRCmds::LessZero { a, res, .. } => { self.shape.insert( *res, self.shape.get(a).unwrap().clone() ); },
 
-            IRCmds::Sum { a, res} => {
+            IRCmds::Sum { a, res } | IRCmds::Max { a, res } | IRCmds::Prod { a, res } => {
                 let mut copy_shape = self.shape.get(a).unwrap().clone();
                 copy_shape.remove(copy_shape.len()-1);
 
                 self.shape.insert( 
-                    res.clone(),
+                    *res,
                     copy_shape
                 );
             },
@@ -50,13 +50,13 @@ impl ShapeTracker {
                 let res_shape = device.dot_prod_shape(a_shape, b_shape);
 
                 self.shape.insert(
-                    res.clone(),
+                    *res,
                     res_shape
                 );
             },
             IRCmds::View { target_dim, res, .. } => {
                 self.shape.insert(
-                    res.clone(),
+                    *res,
                     target_dim.clone()
                 );
             },
@@ -65,7 +65,7 @@ impl ShapeTracker {
                 copy_shape.remove(*dim);
 
                 self.shape.insert( 
-                    res.clone(),
+                    *res,
                     copy_shape
                 );   
             },
@@ -75,7 +75,7 @@ impl ShapeTracker {
                 res_clone[*dim] += b_shape[*dim];
 
                 self.shape.insert(
-                    res.clone(), 
+                    *res, 
                     res_clone
                 );
             },
@@ -87,7 +87,7 @@ impl ShapeTracker {
                 }
 
                 self.shape.insert(
-                    res.clone(),
+                    *res,
                     dim
                 );
             },
@@ -96,16 +96,36 @@ impl ShapeTracker {
                 res_shape[*dim] = *r;
 
                 self.shape.insert(
-                    res.clone(),
+                    *res,
                     res_shape
                 );
             },
-            IRCmds::Exp2 { a, res } => { self.shape.insert(res.clone(), self.shape.get(a).unwrap().clone() ); }
-            IRCmds::Log2 { a, res } => { self.shape.insert(res.clone(), self.shape.get(a).unwrap().clone() ); }
-            IRCmds::Sin { a, res } => { self.shape.insert(res.clone(), self.shape.get(a).unwrap().clone() ); }
-            IRCmds::Recip { a, res } => { self.shape.insert(res.clone(), self.shape.get(a).unwrap().clone() ); }
-            IRCmds::Sqrt { a, res } => { self.shape.insert(res.clone(), self.shape.get(a).unwrap().clone() ); }
-            IRCmds::Contigious { a, res } => { self.shape.insert(res.clone(), self.shape.get(a).unwrap().clone() ); },
+            IRCmds::Unfold { a, dim, size, step, dilation, res } => {
+                let mut res_shape = self.shape.get(a).unwrap().clone();
+                res_shape[*dim] = unfold_windows(res_shape[*dim], *size, *step, *dilation);
+                res_shape.push(*size);
+
+                self.shape.insert(
+                    *res,
+                    res_shape
+                );
+            },
+            IRCmds::Pad { a, dim, before, after, res, .. } => {
+                let mut res_shape = self.shape.get(a).unwrap().clone();
+                res_shape[*dim] += before + after;
+
+                self.shape.insert(
+                    *res,
+                    res_shape
+                );
+            },
+            IRCmds::Exp2 { a, res } => { self.shape.insert(*res, self.shape.get(a).unwrap().clone() ); }
+            IRCmds::Log2 { a, res } => { self.shape.insert(*res, self.shape.get(a).unwrap().clone() ); }
+            IRCmds::Sin { a, res } => { self.shape.insert(*res, self.shape.get(a).unwrap().clone() ); }
+            IRCmds::Recip { a, res } => { self.shape.insert(*res, self.shape.get(a).unwrap().clone() ); }
+            IRCmds::Sqrt { a, res } => { self.shape.insert(*res, self.shape.get(a).unwrap().clone() ); }
+            IRCmds::Contigious { a, res } => { self.shape.insert(*res, self.shape.get(a).unwrap().clone() ); },
+            IRCmds::Rand { dim, res, .. } => { self.shape.insert(*res, dim.clone()); },
             _ => {}
         }
     }
@@ -114,7 +134,7 @@ impl ShapeTracker {
     // it tracks the general shape of the tensor, but the tensor may have a size of 1 nonetheless
     // take, for example, a A(B,M) + B(1,M). We are broadcasting from (1,M) to (B,M)
     // the shape of B will be broadcasted to (B,M) to fit A. However, the allocation will still be (1,M)
-    pub fn get_shape (&self, id: &String) -> &Vec<usize> {
+    pub fn get_shape (&self, id: &VarId) -> &Vec<usize> {
         self.shape.get(id).expect("Unable to get shape at shape tracker")
     }
 }
\ No newline at end of file
@@ -1,9 +1,9 @@
 use std::collections::HashMap;
-use crate::{kernel_decl::Kernels, Device};
+use crate::{kernel_decl::Kernels, Device, VarId};
 
 #[derive(Clone, Debug)]
 pub struct ShapeTrackerKernel {
-    shape: HashMap<String, usize>
+    shape: HashMap<VarId, usize>
 }
 
 impl ShapeTrackerKernel {
@@ -14,18 +14,18 @@ impl ShapeTrackerKernel {
     pub fn step (&mut self, device: &dyn Device, cmd: &Kernels) {
         match cmd {
             Kernels::Alloc { id, size, .. } => {
-                self.shape.insert(id.clone(), *size);
+                self.shape.insert(*id, *size);
             },
             Kernels::Unary { res, size, .. } => {
-                self.shape.insert(res.id().clone(), *size);
+                self.shape.insert(*res.id(), *size);
             },
             Kernels::Binary { res, size, .. } => {
-                self.shape.insert(res.id().clone(), *size);
+                self.shape.insert(*res.id(), *size);
             },
             Kernels::Reduce { res, vec_size, .. } => {
-                self.shape.insert(res.id().clone(), *vec_size);
+                self.shape.insert(*res.id(), *vec_size);
             },
-            Kernels::DotProd { res, a_shape, b_shape, .. }  => {
+            Kernels::DotProd { res, a_shape, b_shape, batch_size, .. }  => {
                 let a_shape_vec = vec![a_shape.0, a_shape.1];
                 let b_shape_vec = vec![b_shape.0, b_shape.1];
 
@@ -33,10 +33,13 @@ impl ShapeTrackerKernel {
                     &a_shape_vec,
                     &b_shape_vec
                 );
-                self.shape.insert(res.id().clone(),  out_shape.iter().product::<usize>());
+                self.shape.insert(*res.id(),  batch_size * out_shape.iter().product::<usize>());
             },
             Kernels::Movement { res, size, ..} => {
-                self.shape.insert(res.id().clone(), *size);
+                self.shape.insert(*res.id(), *size);
+            },
+            Kernels::Rand { res, size, ..} => {
+                self.shape.insert(*res.id(), *size);
             },
             _ => {} 
         }
@@ -46,7 +49,7 @@ impl ShapeTrackerKernel {
     // it tracks the general shape of the tensor, but the tensor may have a size of 1 nonetheless
     // take, for example, a A(B,M) + B(1,M). We are broadcasting from (1,M) to (B,M)
     // the shape of B will be broadcasted to (B,M) to fit A. However, the allocation will still be (1,M)
-    pub fn get_shape (&self, id: &String) -> &usize {
+    pub fn get_shape (&self, id: &VarId) -> &usize {
         self.shape.get(id).expect("Unable to get shape at shape tracker alloc")
     }
 }
\ No newline at end of file
@@ -13,6 +13,8 @@ pub use core::autodiff::{*, self};
 pub use core::tensor::*;
 pub use core::value_data::*; 
 pub use core::ir::*;
+pub use core::program::*;
+pub use core::var_id::*;
 pub use nn::*;
 pub use kernel::*;
 pub use experiments::*;
\ No newline at end of file
@@ -1,7 +1,7 @@
 use std::time::Instant;
 use autodiffv2::autodiff;
 use autodiffv2::devices::{OpenCL, CLDeviceType};
-use autodiffv2::nn::{self, SeqF, Module};
+use autodiffv2::nn::{self, optimizers::Optimizer, SeqF, Module};
 
 // in the future, probably migrate to tests()
 
@@ -19,14 +19,16 @@ pub fn nn_test () {
     let mut opt = nn::optimizers::SGD(neural_net.params(), 0.01);
 
     let x = autodiff::randn(vec![16, 512]);
+    let target = autodiff::randn(vec![16, 128]);
     let mut res = autodiff::empty();
 
     // prev: 0..1000       
     autodiff::ir_for(0..10000, |_| {
         let y = neural_net.f(x.clone());
+        let l = nn::loss::mse_loss(y.clone(), target.clone(), nn::loss::Reduction::Mean);
         opt.zero_grad();
-        y.forward();
-        y.backward();
+        l.forward();
+        l.backward();
         opt.step();
 
         res = y;
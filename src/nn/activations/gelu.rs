@@ -0,0 +1,29 @@
+use std::f32::consts::FRAC_1_SQRT_2;
+use crate::Tensor;
+use crate::nn::{Module, SeqF};
+
+pub struct GELU {}
+
+impl Module for GELU {
+    fn params (&self) -> Vec<Tensor> {
+        vec![]
+    }
+}
+
+impl SeqF for GELU {
+    fn f (&self, x: Tensor) -> Tensor {
+        x.gelu()
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn GELU () -> GELU {
+    GELU {}
+}
+
+impl Tensor {
+    // exact form (not the tanh approximation): x * Φ(x) = x/2 * (1 + erf(x / sqrt(2)))
+    pub fn gelu (&self) -> Tensor {
+        self.clone() * 0.5 * (1.0 + (self.clone() * FRAC_1_SQRT_2).erf())
+    }
+}
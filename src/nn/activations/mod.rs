@@ -1,7 +1,15 @@
 pub mod sigmoid;
 pub mod relu;
 pub mod softmax;
+pub mod tanh;
+pub mod gelu;
+pub mod silu;
+pub mod softplus;
 
 pub use sigmoid::*;
 pub use relu::*;
 pub use softmax::*;
+pub use tanh::*;
+pub use gelu::*;
+pub use silu::*;
+pub use softplus::*;
@@ -24,4 +24,60 @@ impl Tensor {
     pub fn relu (&self) -> Tensor {
         self.clone() * self.more_than(&autodiff::constant(0.0, self.dim()))
     }
-}
\ No newline at end of file
+}
+
+// ======================= Leaky ReLU ======================= 
+pub struct LeakyReLU {
+    pub slope: f32
+}
+
+impl Module for LeakyReLU {
+    fn params (&self) -> Vec<Tensor> {
+        vec![]
+    }
+}
+
+impl SeqF for LeakyReLU {
+    fn f (&self, x: Tensor) -> Tensor {
+        x.leaky_relu(self.slope)
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn LeakyReLU (slope: f32) -> LeakyReLU {
+    LeakyReLU { slope }
+}
+
+// ======================= ELU ======================= 
+pub struct ELU {
+    pub alpha: f32
+}
+
+impl Module for ELU {
+    fn params (&self) -> Vec<Tensor> {
+        vec![]
+    }
+}
+
+impl SeqF for ELU {
+    fn f (&self, x: Tensor) -> Tensor {
+        x.elu(self.alpha)
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn ELU (alpha: f32) -> ELU {
+    ELU { alpha }
+}
+
+impl Tensor {
+    pub fn leaky_relu (&self, slope: f32) -> Tensor {
+        self.relu() + self.clone() * self.less_than(&autodiff::constant(0.0, self.dim())) * slope
+    }
+
+    // x for x > 0, alpha * (e^x - 1) otherwise. exp only sees the negative part (e^0 - 1 = 0 elsewhere), so it can't overflow
+    pub fn elu (&self, alpha: f32) -> Tensor {
+        let neg = self.clone() * self.less_than(&autodiff::constant(0.0, self.dim()));
+        self.relu() + (neg.exp() - 1.0) * alpha
+    }
+}
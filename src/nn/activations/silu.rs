@@ -0,0 +1,35 @@
+use crate::Tensor;
+use crate::nn::{Module, SeqF};
+
+pub struct SiLU {}
+
+impl Module for SiLU {
+    fn params (&self) -> Vec<Tensor> {
+        vec![]
+    }
+}
+
+impl SeqF for SiLU {
+    fn f (&self, x: Tensor) -> Tensor {
+        x.silu()
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn SiLU () -> SiLU {
+    SiLU {}
+}
+
+impl Tensor {
+    // x * sigmoid(x); the backward doesn't go through sigmoid's e^(-x), which overflows for very negative x
+    pub fn silu (&self) -> Tensor {
+        self.composed(
+            |x| x.clone() * x.sigmoid(),
+            // s * (1 + x * (1 - s))
+            |x, _| {
+                let s = x.sigmoid();
+                s.clone() * (1.0 + x.clone() * (1.0 - s))
+            }
+        )
+    }
+}
@@ -1,4 +1,6 @@
-use crate::Tensor;
+use std::f32::consts::LOG2_E;
+use crate::{ir_b_add, ir_b_id, IRCmds, NodeTrait, Tensor, Value, VarId};
+use crate::graph::ops::func::c_exp;
 use crate::nn::{Module, SeqF};
 
 pub struct Softmax {
@@ -13,7 +15,7 @@ impl Module for Softmax {
 
 impl SeqF for Softmax {
     fn f (&self, x: Tensor) -> Tensor {
-        x.exp() / x.exp().sum(self.dim)
+        x.softmax(self.dim)
     }
 }
 
@@ -24,8 +26,138 @@ pub fn Softmax (dim: i32) -> Softmax {
     }
 }
 
+/**
+ * softmax, log_softmax and logsumexp along any dim
+ * all three are computed from the max-shifted logsumexp: lse(x) = m + ln(sum(exp(x - m))), m = max(x)
+ * so exp never sees a positive input (and can't overflow). Backward passes are fused, using the forward result
+ */
+#[derive(Clone, Copy)]
+enum SoftmaxKind {
+    Softmax,
+    LogSoftmax,
+    LogSumExp
+}
+
+#[derive(Clone)]
+pub struct SoftmaxNode {
+    parent: Tensor,
+    dim: usize,
+    kind: SoftmaxKind,
+    val: Option<Value>
+}
+
+impl NodeTrait for SoftmaxNode {
+    fn forward (&mut self) -> Value {
+        if let Some(v) = self.val() {
+            return v;
+        }
+
+        let p_val = self.parent.forward();
+        let id = self.val.as_ref().map(|v| v.id);
+        let rank = p_val.dim.len();
+        let n = p_val.dim[self.dim];
+
+        let x = p_val.to_node();
+        let m = x.max(self.dim as i32);
+        let s = (x.clone() - expand(&m, self.dim, n, rank)).exp().sum(self.dim as i32).ln();
+
+        let res_val = match self.kind {
+            SoftmaxKind::LogSumExp => c_add(&m.forward(), &s.forward(), id),
+            SoftmaxKind::LogSoftmax => {
+                let lse = m + s;
+                c_add(&p_val, &(-expand(&lse, self.dim, n, rank)).forward(), id)
+            },
+            SoftmaxKind::Softmax => {
+                let lse = m + s;
+                c_exp(&((x - expand(&lse, self.dim, n, rank)) * LOG2_E).forward(), id)
+            }
+        };
+
+        self.val = Some(res_val.clone());
+        res_val
+    }
+
+    fn dim (&self) -> Vec<usize> {
+        let mut d = self.parent.dim();
+        if let SoftmaxKind::LogSumExp = self.kind {
+            d.remove(self.dim);
+            if d.is_empty() { d.push(1); }
+        }
+        d
+    }
+
+    fn backward (&mut self, grad: Value) {
+        let x = self.parent.val().expect("parent forward").to_node();
+        let y = self.val.clone().expect("softmax forward").to_node();
+        let g = grad.to_node();
+
+        let rank = x.dim().len();
+        let n = x.dim()[self.dim];
+        let d = self.dim as i32;
+
+        let p_grad = match self.kind {
+            // d lse / dx = softmax(x)
+            SoftmaxKind::LogSumExp => expand(&g, self.dim, n, rank) * (x - expand(&y, self.dim, n, rank)).exp(),
+            // dx = g - softmax(x) * sum(g)
+            SoftmaxKind::LogSoftmax => g.clone() - y.exp() * expand(&g.sum(d), self.dim, n, rank),
+            // dx = y * (g - sum(g * y))
+            SoftmaxKind::Softmax => y.clone() * (g.clone() - expand(&(g * y).sum(d), self.dim, n, rank))
+        };
+
+        self.parent.n.borrow_mut().backward(p_grad.forward());
+    }
+
+    fn val (&self) -> Option<Value> {
+        self.parent.val()?;
+        self.val.clone()
+    }
+
+    fn deep_copy (&self) -> Box<dyn NodeTrait> {
+        Box::new(self.clone())
+    }
+}
+
+// broadcast a tensor reduced along dim back to the rank-dim input (1-dim inputs reduce to [1], which isn't unsqueezed)
+fn expand (t: &Tensor, dim: usize, n: usize, rank: usize) -> Tensor {
+    let t = if t.dim().len() < rank { t.unsqueeze(dim as i32) } else { t.clone() };
+    t.broadcast(dim as i32, n)
+}
+
+fn c_add (a: &Value, b: &Value, id: Option<VarId>) -> Value {
+    let id = id.or_else(|| Some(ir_b_id()) ).unwrap();
+    ir_b_add(IRCmds::ElwAdd {
+        a: a.id,
+        b: b.id,
+        res: id
+    });
+
+    Value {
+        dim: a.dim.clone(),
+        id
+    }
+}
+
 impl Tensor {
-    pub fn softmax (&self, dim:i32) -> Tensor {
-        self.exp() / self.exp().sum(dim)
+    fn softmax_node (&self, dim: i32, kind: SoftmaxKind) -> Tensor {
+        let dim = if dim < 0 { self.dim().len() as i32 + dim } else { dim } as usize;
+        Tensor::new(SoftmaxNode {
+            parent: self.clone(),
+            dim,
+            kind,
+            val: None
+        })
+    }
+
+    pub fn softmax (&self, dim: i32) -> Tensor {
+        self.softmax_node(dim, SoftmaxKind::Softmax)
+    }
+
+    pub fn log_softmax (&self, dim: i32) -> Tensor {
+        self.softmax_node(dim, SoftmaxKind::LogSoftmax)
+    }
+
+    // ln(sum(exp(x))) along dim; removes dim
+    pub fn logsumexp (&self, dim: i32) -> Tensor {
+        self.softmax_node(dim, SoftmaxKind::LogSumExp)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,28 @@
+use crate::Tensor;
+use crate::nn::{Module, SeqF};
+
+pub struct Softplus {}
+
+impl Module for Softplus {
+    fn params (&self) -> Vec<Tensor> {
+        vec![]
+    }
+}
+
+impl SeqF for Softplus {
+    fn f (&self, x: Tensor) -> Tensor {
+        x.softplus()
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn Softplus () -> Softplus {
+    Softplus {}
+}
+
+impl Tensor {
+    // ln(1 + e^x) = max(x, 0) + ln(1 + e^(-|x|)), so exp never overflows
+    pub fn softplus (&self) -> Tensor {
+        self.relu() + (1.0 + (-self.abs()).exp()).ln()
+    }
+}
@@ -0,0 +1,31 @@
+use crate::Tensor;
+use crate::nn::{Module, SeqF};
+
+pub struct Tanh {}
+
+impl Module for Tanh {
+    fn params (&self) -> Vec<Tensor> {
+        vec![]
+    }
+}
+
+impl SeqF for Tanh {
+    fn f (&self, x: Tensor) -> Tensor {
+        x.tanh()
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn Tanh () -> Tanh {
+    Tanh {}
+}
+
+impl Tensor {
+    // tanh(x) = 1 - 2 / (1 + e^(2x)); e^(2x) may overflow to inf, so the backward uses 1 - tanh^2 directly
+    pub fn tanh (&self) -> Tensor {
+        self.composed(
+            |x| 1.0 - 2.0 / (1.0 + (x.clone() * 2.0).exp()),
+            |_, y| 1.0 - y.pow2()
+        )
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{autodiff, Tensor, Module};
+
+use super::SeqF;
+
+// ========== Conv2d ==========
+// x: [N, C_in, H, W] --> [N, C_out, H_out, W_out]
+pub struct Conv2d {
+    pub w: Tensor, // [C_out, C_in / groups, k, k]
+    pub b: Option<Tensor>, // [C_out]
+    pub stride: usize,
+    pub padding: usize,
+    pub dilation: usize,
+    pub groups: usize
+}
+
+impl Module for Conv2d {
+    fn params (&self) -> Vec<Tensor> {
+        if let Some(b) = &self.b {
+            vec![self.w.clone(), b.clone()]
+        } else {
+            vec![self.w.clone()]
+        }
+    }
+}
+
+impl SeqF for Conv2d {
+    fn f (&self, x: Tensor) -> Tensor {
+        let res = x.conv2d(&self.w, self.stride, self.padding, self.dilation, self.groups);
+        add_bias(res, &self.b)
+    }
+}
+
+// dilation 1, groups 1. Set the fields for anything else
+#[allow(non_snake_case)]
+pub fn Conv2d (in_channels: usize, out_channels: usize, kernel_size: usize, stride: usize, padding: usize, bias: bool) -> Conv2d {
+    Conv2d {
+        w: autodiff::randn(vec![out_channels, in_channels, kernel_size, kernel_size]),
+        b: if bias {Some(autodiff::randn(vec![out_channels]))} else {None},
+        stride,
+        padding,
+        dilation: 1,
+        groups: 1
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn Conv2dWithWeights (w: Tensor, b: Option<Tensor>, stride: usize, padding: usize, dilation: usize, groups: usize) -> Conv2d {
+    assert_eq!(w.dim().len(), 4, "weight must be [C_out, C_in / groups, kh, kw]");
+    if let Some(b_s) = b.clone() {
+        assert_eq!(b_s.dim(), vec![w.dim()[0]], "bias dim must be [C_out]");
+    }
+
+    Conv2d {
+        w,
+        b,
+        stride,
+        padding,
+        dilation,
+        groups
+    }
+}
+
+// ========== ConvTranspose2d ==========
+// x: [N, C_in, H, W] --> [N, C_out, (H - 1) * stride - 2 * padding + dilation * (k - 1) + 1, ..]
+pub struct ConvTranspose2d {
+    pub w: Tensor, // [C_in, C_out / groups, k, k]
+    pub b: Option<Tensor>, // [C_out]
+    pub stride: usize,
+    pub padding: usize,
+    pub dilation: usize,
+    pub groups: usize
+}
+
+impl Module for ConvTranspose2d {
+    fn params (&self) -> Vec<Tensor> {
+        if let Some(b) = &self.b {
+            vec![self.w.clone(), b.clone()]
+        } else {
+            vec![self.w.clone()]
+        }
+    }
+}
+
+impl SeqF for ConvTranspose2d {
+    fn f (&self, x: Tensor) -> Tensor {
+        let res = x.conv_transpose2d(&self.w, self.stride, self.padding, self.dilation, self.groups);
+        add_bias(res, &self.b)
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn ConvTranspose2d (in_channels: usize, out_channels: usize, kernel_size: usize, stride: usize, padding: usize, bias: bool) -> ConvTranspose2d {
+    ConvTranspose2d {
+        w: autodiff::randn(vec![in_channels, out_channels, kernel_size, kernel_size]),
+        b: if bias {Some(autodiff::randn(vec![out_channels]))} else {None},
+        stride,
+        padding,
+        dilation: 1,
+        groups: 1
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn ConvTranspose2dWithWeights (w: Tensor, b: Option<Tensor>, stride: usize, padding: usize, dilation: usize, groups: usize) -> ConvTranspose2d {
+    assert_eq!(w.dim().len(), 4, "weight must be [C_in, C_out / groups, kh, kw]");
+    if let Some(b_s) = b.clone() {
+        assert_eq!(b_s.dim(), vec![w.dim()[1] * groups], "bias dim must be [C_out]");
+    }
+
+    ConvTranspose2d {
+        w,
+        b,
+        stride,
+        padding,
+        dilation,
+        groups
+    }
+}
+
+// bias [C_out] broadcasts over [N, C_out, H, W]
+fn add_bias (res: Tensor, b: &Option<Tensor>) -> Tensor {
+    match b {
+        Some(b) => res + b.view(vec![-1, 1, 1]),
+        None => res
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{Tensor, Module};
+
+use super::SeqF;
+
+// ========== Dropout ==========
+// set `training` to false for evaluation (dropout becomes the identity)
+pub struct Dropout {
+    pub p: f32,
+    pub training: bool
+}
+
+impl Module for Dropout {
+    fn params (&self) -> Vec<Tensor> {
+        vec![]
+    }
+}
+
+impl SeqF for Dropout {
+    fn f (&self, x: Tensor) -> Tensor {
+        x.dropout(self.p, self.training)
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn Dropout (p: f32) -> Dropout {
+    Dropout {
+        p,
+        training: true
+    }
+}
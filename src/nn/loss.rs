@@ -0,0 +1,93 @@
+use crate::{autodiff, Tensor, Value};
+
+/**
+ * Loss functions
+ * Every loss returns a [1] tensor, so `.backward()` can be called on it directly
+ * Class targets are class indices [N], or one-hot (or class probabilities) [N, C], see `one_hot`
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reduction {
+    Mean,
+    Sum
+}
+
+// reduces every element into a [1] tensor
+fn reduce (x: Tensor, reduction: Reduction) -> Tensor {
+    let total = x.dim().iter().product::<usize>() as f32;
+    let s = x.flatten().unsqueeze(0).sum(1);
+
+    match reduction {
+        Reduction::Mean => s / total,
+        Reduction::Sum => s
+    }
+}
+
+// [N] class indices --> [N, C] one-hot targets
+pub fn one_hot (classes: &[usize], num_classes: usize) -> Tensor {
+    let mut data = vec![0.0; classes.len() * num_classes];
+    for (i, &c) in classes.iter().enumerate() {
+        assert!(c < num_classes, "Class {} is out of range for {} classes", c, num_classes);
+        data[i * num_classes + c] = 1.0;
+    }
+
+    Value::new(data, vec![classes.len(), num_classes]).to_node()
+}
+
+// [N] class indices (stored as floats, ex: a placeholder) --> [N, C] one-hot, built on the device
+fn class_mask (target: Tensor, num_classes: usize) -> Tensor {
+    let n = target.dim()[0];
+    let classes = Value::new((0..num_classes).map(|c| c as f32).collect(), vec![1, num_classes]).to_node();
+    target.unsqueeze(1).broadcast(1, num_classes).equal(&classes.broadcast(0, n))
+}
+
+// (input - target)^2
+pub fn mse_loss (input: Tensor, target: Tensor, reduction: Reduction) -> Tensor {
+    assert_eq!(input.dim(), target.dim(), "Input and target dims must match for mse_loss");
+    reduce((input - target).pow2(), reduction)
+}
+
+// -sum(target * log_probs) over classes; mean is over the batch (N)
+pub fn nll_loss (log_probs: Tensor, target: Tensor, reduction: Reduction) -> Tensor {
+    assert_eq!(log_probs.dim().len(), 2, "nll_loss expects log_probs of [N, C]");
+    let target = if target.dim().len() == 1 {
+        assert_eq!(target.dim()[0], log_probs.dim()[0], "Target must be class indices [N] or one-hot [N, C] for nll_loss");
+        class_mask(target, log_probs.dim()[1])
+    } else {
+        assert_eq!(log_probs.dim(), target.dim(), "Target must be class indices [N] or one-hot [N, C] for nll_loss");
+        target
+    };
+
+    let n = log_probs.dim()[0] as f32;
+    let total = -reduce(log_probs * target, Reduction::Sum);
+
+    match reduction {
+        Reduction::Mean => total / n,
+        Reduction::Sum => total
+    }
+}
+
+// nll_loss over the log-softmax of the logits [N, C]
+pub fn cross_entropy (logits: Tensor, target: Tensor, reduction: Reduction) -> Tensor {
+    nll_loss(logits.log_softmax(1), target, reduction)
+}
+
+// -(target * ln(input) + (1 - target) * ln(1 - input)); input are probabilities within (0, 1)
+pub fn binary_cross_entropy (input: Tensor, target: Tensor, reduction: Reduction) -> Tensor {
+    assert_eq!(input.dim(), target.dim(), "Input and target dims must match for binary_cross_entropy");
+    let l = target.clone() * input.ln() + (1.0 - target) * (1.0 - input).ln();
+    reduce(-l, reduction)
+}
+
+// 0.5 * d^2 if |d| < delta, else delta * (|d| - 0.5 * delta), where d = input - target
+pub fn huber_loss (input: Tensor, target: Tensor, delta: f32, reduction: Reduction) -> Tensor {
+    assert_eq!(input.dim(), target.dim(), "Input and target dims must match for huber_loss");
+    let d = input - target;
+
+    // comparisons are constant (no grad), so they can be used as masks
+    let sign = 2.0 * d.more_than(&autodiff::constant(0.0, d.dim())) - 1.0;
+    let abs = d.clone() * sign;
+    let quadratic = abs.less_than(&autodiff::constant(delta, d.dim()));
+
+    let l = quadratic.clone() * 0.5 * d.pow2() + (1.0 - quadratic) * delta * (abs - 0.5 * delta);
+    reduce(l, reduction)
+}
@@ -5,10 +5,17 @@ pub mod optimizers;
 pub mod sequential;
 pub mod norm;
 pub mod transformer;
+pub mod loss;
+pub mod conv;
+pub mod pool;
+pub mod dropout;
 
 pub use activations::*;
 pub use linear::*;
 pub use module::*;
 pub use sequential::*;
 pub use norm::*;
-pub use transformer::*;
\ No newline at end of file
+pub use transformer::*;
+pub use conv::*;
+pub use pool::*;
+pub use dropout::*;
\ No newline at end of file
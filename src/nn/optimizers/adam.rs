@@ -0,0 +1,123 @@
+use crate::{autodiff, Tensor};
+use super::{Optimizer, ParamGroup};
+
+/**
+ * Adam optimizer (Kingma & Ba)
+ * m = beta1 * m + (1 - beta1) * g
+ * v = beta2 * v + (1 - beta2) * g^2
+ * p -= lr * (m / (1 - beta1^t)) / (sqrt(v / (1 - beta2^t)) + eps)
+ *
+ * The moments and the step counter t are tensors updated with op-equal (+=, *=), 
+ * so the bias correction stays correct inside `ir_for` loops and across executions.
+ *
+ * The weight decay of a group is added to the gradient (L2 penalty), 
+ * or with `decoupled` (AdamW, Loshchilov & Hutter), applied directly: p -= lr * weight_decay * p before the update
+ */
+pub struct Adam {
+    groups: Vec<ParamGroup>,
+    m: Vec<Tensor>,
+    v: Vec<Tensor>,
+    t: Tensor,
+    beta1: f32,
+    beta2: f32,
+    eps: f32,
+    decoupled: bool
+}
+
+pub type AdamW = Adam;
+
+impl Optimizer for Adam {
+    fn step (&mut self) {
+        let Adam { groups, m, v, t, beta1, beta2, eps, decoupled } = self;
+
+        *t += 1.0;
+        t.forward();
+
+        // beta^t = 2^(t * log2(beta)), as t is only known at runtime
+        let bc1 = 1.0 - (t.clone() * beta1.log2()).exp2();
+        let bc2 = 1.0 - (t.clone() * beta2.log2()).exp2();
+
+        // moments are in the same order as the parameters of all groups
+        let mut moments = m.iter_mut().zip(v.iter_mut());
+
+        for group in groups.iter_mut() {
+            for p in group.params.iter_mut() {
+                let (m, v) = moments.next().expect("Parameters were added after creating the optimizer");
+                // not `+=`, as that would write into the gradient itself
+                let g = if group.weight_decay != 0.0 && !*decoupled {
+                    p.grad().to_node() + group.weight_decay * p.detach()
+                } else {
+                    p.grad().to_node()
+                };
+
+                if group.weight_decay != 0.0 && *decoupled {
+                    *p *= (1.0 - group.lr.clone() * group.weight_decay).detach();
+                    p.forward();
+                }
+
+                *m *= *beta1;
+                m.forward();
+                *m += (1.0 - *beta1) * g.clone();
+                m.forward();
+
+                *v *= *beta2;
+                v.forward();
+                *v += (1.0 - *beta2) * (g.clone() * g);
+                v.forward();
+
+                // detached, so the gradient of the next step doesn't flow into the moments
+                let update = group.lr.clone() * (m.clone() / bc1.clone()) / ((v.clone() / bc2.clone()).sqrt() + *eps);
+                *p -= update.detach();
+                p.forward();
+            }
+        }
+    }
+
+    fn param_groups (&mut self) -> &mut Vec<ParamGroup> {
+        &mut self.groups
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn Adam (m: Vec<Tensor>, lr: f32) -> Adam {
+    AdamWithParams(m, lr, 0.9, 0.999, 1e-8)
+}
+
+#[allow(non_snake_case)]
+pub fn AdamWithParams (m: Vec<Tensor>, lr: f32, beta1: f32, beta2: f32, eps: f32) -> Adam {
+    AdamWithGroups(vec![ParamGroup::new(m, lr, 0.0)], beta1, beta2, eps)
+}
+
+#[allow(non_snake_case)]
+pub fn AdamWithGroups (groups: Vec<ParamGroup>, beta1: f32, beta2: f32, eps: f32) -> Adam {
+    let params: Vec<&Tensor> = groups.iter().flat_map(|g| g.params.iter()).collect();
+
+    Adam {
+        m: params.iter().map(|p| autodiff::zeros(p.dim())).collect(),
+        v: params.iter().map(|p| autodiff::zeros(p.dim())).collect(),
+        t: autodiff::scalar(0.0),
+        groups,
+        beta1,
+        beta2,
+        eps,
+        decoupled: false
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn AdamW (m: Vec<Tensor>, lr: f32, weight_decay: f32) -> AdamW {
+    AdamWWithParams(m, lr, 0.9, 0.999, 1e-8, weight_decay)
+}
+
+#[allow(non_snake_case)]
+pub fn AdamWWithParams (m: Vec<Tensor>, lr: f32, beta1: f32, beta2: f32, eps: f32, weight_decay: f32) -> AdamW {
+    AdamWWithGroups(vec![ParamGroup::new(m, lr, weight_decay)], beta1, beta2, eps)
+}
+
+#[allow(non_snake_case)]
+pub fn AdamWWithGroups (groups: Vec<ParamGroup>, beta1: f32, beta2: f32, eps: f32) -> AdamW {
+    Adam {
+        decoupled: true,
+        ..AdamWithGroups(groups, beta1, beta2, eps)
+    }
+}
@@ -1,3 +1,9 @@
+pub mod optimizer;
+pub mod scheduler;
 pub mod sgd;
+pub mod adam;
 
-pub use sgd::*;
\ No newline at end of file
+pub use optimizer::*;
+pub use scheduler::*;
+pub use sgd::*;
+pub use adam::*;
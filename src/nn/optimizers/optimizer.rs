@@ -0,0 +1,45 @@
+use crate::{autodiff, Tensor};
+
+/**
+ * Parameters sharing the same learning rate and weight decay
+ * The learning rate is an on-device scalar, so schedulers can change it inside `ir_for` loops
+ */
+pub struct ParamGroup {
+    pub params: Vec<Tensor>,
+    pub lr: Tensor,
+    pub base_lr: f32,       // initial learning rate; schedulers scale it
+    pub weight_decay: f32
+}
+
+impl ParamGroup {
+    pub fn new (params: Vec<Tensor>, lr: f32, weight_decay: f32) -> ParamGroup {
+        ParamGroup {
+            params,
+            lr: autodiff::scalar(lr),
+            base_lr: lr,
+            weight_decay
+        }
+    }
+
+    // lr = value; the lr has to remain the same variable, so it's written with op-equals
+    pub fn set_lr (&mut self, value: Tensor) {
+        self.lr *= 0.0;
+        self.lr.forward();
+        self.lr += value.detach();
+        self.lr.forward();
+    }
+}
+
+pub trait Optimizer {
+    fn step (&mut self);
+
+    fn param_groups (&mut self) -> &mut Vec<ParamGroup>;
+
+    fn zero_grad (&mut self) {
+        for group in self.param_groups().iter() {
+            for p in group.params.iter() {
+                p.reset_grad();
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::f32::consts::PI;
+use crate::{autodiff, Tensor};
+use super::Optimizer;
+
+/**
+ * Learning rate schedulers
+ * `step` sets the lr of every group for the upcoming optimizer step, so it's called right before `opt.step()`:
+ *   scheduler.step(&mut opt);
+ *   opt.step();
+ * The step counters are on-device scalars, so the schedule progresses inside `ir_for` loops (and across executions)
+ */
+pub trait LRScheduler {
+    fn step (&mut self, opt: &mut dyn Optimizer);
+}
+
+// lr = base_lr * gamma^floor(t / step_size), where t is the # of steps taken so far
+pub struct StepLR {
+    count: Tensor,  // steps since the last decay
+    factor: Tensor, // gamma^(# of decays)
+    step_size: usize,
+    gamma: f32
+}
+
+impl LRScheduler for StepLR {
+    fn step (&mut self, opt: &mut dyn Optimizer) {
+        for group in opt.param_groups().iter_mut() {
+            group.set_lr(group.base_lr * self.factor.clone());
+        }
+
+        self.count += 1.0;
+        self.count.forward();
+
+        // decay (and restart counting) once step_size steps are taken
+        // detached, as count itself is replaced by `count *= ...`
+        let hit = self.count.equal(&autodiff::constant(self.step_size as f32, vec![1])).detach();
+        self.factor *= 1.0 + (self.gamma - 1.0) * hit.clone();
+        self.factor.forward();
+        self.count *= 1.0 - hit;
+        self.count.forward();
+    }
+}
+
+// lr = eta_min + (base_lr - eta_min) * (1 + cos(pi * t / t_max)) / 2, where t is the # of steps taken so far
+pub struct CosineLR {
+    t: Tensor,
+    t_max: usize,
+    eta_min: f32
+}
+
+impl LRScheduler for CosineLR {
+    fn step (&mut self, opt: &mut dyn Optimizer) {
+        let cos = (self.t.clone() * (PI / self.t_max as f32)).cos();
+
+        for group in opt.param_groups().iter_mut() {
+            let lr = self.eta_min + (group.base_lr - self.eta_min) * 0.5 * (1.0 + cos.clone());
+            group.set_lr(lr);
+        }
+
+        self.t += 1.0;
+        self.t.forward();
+    }
+}
+
+// lr = base_lr * min(1, t / warmup_steps), where t is the current step (starting at 1)
+pub struct LinearWarmup {
+    t: Tensor,
+    warmup_steps: usize
+}
+
+impl LRScheduler for LinearWarmup {
+    fn step (&mut self, opt: &mut dyn Optimizer) {
+        self.t += 1.0;
+        self.t.forward();
+
+        let warmup = autodiff::constant(self.warmup_steps as f32, vec![1]);
+        let ratio = self.t.clone() / self.warmup_steps as f32;
+        let factor = 1.0 + self.t.less_than(&warmup) * (ratio - 1.0);
+
+        for group in opt.param_groups().iter_mut() {
+            group.set_lr(group.base_lr * factor.clone());
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn StepLR (step_size: usize, gamma: f32) -> StepLR {
+    StepLR {
+        count: autodiff::scalar(0.0),
+        factor: autodiff::scalar(1.0),
+        step_size,
+        gamma
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn CosineLR (t_max: usize, eta_min: f32) -> CosineLR {
+    CosineLR {
+        t: autodiff::scalar(0.0),
+        t_max,
+        eta_min
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn LinearWarmup (warmup_steps: usize) -> LinearWarmup {
+    LinearWarmup {
+        t: autodiff::scalar(0.0),
+        warmup_steps
+    }
+}
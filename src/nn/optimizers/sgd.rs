@@ -1,29 +1,41 @@
 use crate::Tensor;
+use super::{Optimizer, ParamGroup};
 
 pub struct SGD {
-    n: Vec<Tensor>,
-    lr: f32
+    groups: Vec<ParamGroup>
 }
 
-impl SGD {
-    pub fn step (&mut self) {
-        for p in self.n.iter_mut() {
-            *p -= self.lr * p.grad().to_node();
-            p.forward();
+impl Optimizer for SGD {
+    fn step (&mut self) {
+        for group in self.groups.iter_mut() {
+            for p in group.params.iter_mut() {
+                // not `+=`, as that would write into the gradient itself
+                let grad = if group.weight_decay != 0.0 {
+                    p.grad().to_node() + group.weight_decay * p.detach()
+                } else {
+                    p.grad().to_node()
+                };
+
+                // detached, so the gradient of the next step doesn't flow into the lr
+                *p -= (group.lr.clone() * grad).detach();
+                p.forward();
+            }
         }
     }
 
-    pub fn zero_grad (&mut self) {
-        for p in self.n.iter_mut() {
-            p.reset_grad();
-        }
+    fn param_groups (&mut self) -> &mut Vec<ParamGroup> {
+        &mut self.groups
     }
 }
 
 #[allow(non_snake_case)]
 pub fn SGD (m: Vec<Tensor>, lr: f32) -> SGD {
+    SGDWithGroups(vec![ParamGroup::new(m, lr, 0.0)])
+}
+
+#[allow(non_snake_case)]
+pub fn SGDWithGroups (groups: Vec<ParamGroup>) -> SGD {
     SGD {
-        n: m,
-        lr
+        groups
     }
-}
\ No newline at end of file
+}
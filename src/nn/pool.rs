@@ -0,0 +1,85 @@
+use crate::{Tensor, Module};
+
+use super::SeqF;
+
+// ========== MaxPool2d ==========
+pub struct MaxPool2d {
+    pub kernel_size: usize,
+    pub stride: usize,
+    pub padding: usize,
+    pub dilation: usize
+}
+
+impl Module for MaxPool2d {
+    fn params (&self) -> Vec<Tensor> {
+        vec![]
+    }
+}
+
+impl SeqF for MaxPool2d {
+    fn f (&self, x: Tensor) -> Tensor {
+        x.max_pool2d(self.kernel_size, self.stride, self.padding, self.dilation)
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn MaxPool2d (kernel_size: usize, stride: usize, padding: usize) -> MaxPool2d {
+    MaxPool2d {
+        kernel_size,
+        stride,
+        padding,
+        dilation: 1
+    }
+}
+
+// ========== AvgPool2d ==========
+pub struct AvgPool2d {
+    pub kernel_size: usize,
+    pub stride: usize,
+    pub padding: usize
+}
+
+impl Module for AvgPool2d {
+    fn params (&self) -> Vec<Tensor> {
+        vec![]
+    }
+}
+
+impl SeqF for AvgPool2d {
+    fn f (&self, x: Tensor) -> Tensor {
+        x.avg_pool2d(self.kernel_size, self.stride, self.padding)
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn AvgPool2d (kernel_size: usize, stride: usize, padding: usize) -> AvgPool2d {
+    AvgPool2d {
+        kernel_size,
+        stride,
+        padding
+    }
+}
+
+// ========== AdaptiveAvgPool2d ==========
+pub struct AdaptiveAvgPool2d {
+    pub out_size: (usize, usize)
+}
+
+impl Module for AdaptiveAvgPool2d {
+    fn params (&self) -> Vec<Tensor> {
+        vec![]
+    }
+}
+
+impl SeqF for AdaptiveAvgPool2d {
+    fn f (&self, x: Tensor) -> Tensor {
+        x.adaptive_avg_pool2d(self.out_size)
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn AdaptiveAvgPool2d (out_size: (usize, usize)) -> AdaptiveAvgPool2d {
+    AdaptiveAvgPool2d {
+        out_size
+    }
+}
@@ -1,52 +1,76 @@
 // Attention mechanism
-use crate::{autodiff, Module, Tensor};
+use std::rc::Rc;
+use crate::{autodiff, Module, Tensor, Value};
 use crate::nn::Linear;
 
 use super::{LayerNorm, SeqF, Sequential};
 
 // ======================= Attention ======================= 
+// Q, K, V are (..., seq, d); any leading dims (ex: heads) are batched
 
 #[allow(non_snake_case)]
 pub fn Attention (Q:Tensor, K:Tensor, V:Tensor, d_model:usize) -> Tensor {
-    autodiff::dot(
+    autodiff::matmul(
         (
-            autodiff::dot(Q, K.t()) / 
+            autodiff::matmul(Q, transpose(&K)) / 
             (d_model as f32).sqrt()
         ).softmax(-1),
         V
     )
 }
 
+// mask is additive: 0 where attending, -inf where masked (see causal_mask)
 #[allow(non_snake_case)]
 pub fn AttentionMasked (Q:Tensor, K:Tensor, V:Tensor, d_model:usize, mask:Tensor) -> Tensor {
-    autodiff::dot(
-        (
-            autodiff::dot(Q, K.t()) / 
-            (d_model as f32).sqrt() * 
-            mask
-        ).softmax(-1),
+    let scores = autodiff::matmul(Q, transpose(&K)) / (d_model as f32).sqrt();
+    let mask = broadcast_to(mask, &scores.dim());
+
+    autodiff::matmul(
+        (scores + mask).softmax(-1),
         V
     )
 }
 
+// (seq, seq) additive mask where position i can only attend to positions <= i
+pub fn causal_mask (seq: usize) -> Tensor {
+    let data = (0..seq*seq).map(|idx| if idx % seq > idx / seq { f32::NEG_INFINITY } else { 0.0 }).collect();
+    Value::new(data, vec![seq, seq]).to_node()
+}
+
+// swaps the last two dims
+fn transpose (t: &Tensor) -> Tensor {
+    let n = t.dim().len();
+    let mut p: Vec<usize> = (0..n).collect();
+    p.swap(n-1, n-2);
+    t.permute(&p)
+}
+
+// broadcasts (seq, seq) mask over the leading dims of the scores
+fn broadcast_to (mask: Tensor, dim: &[usize]) -> Tensor {
+    let mut mask = mask;
+    for (i, &r) in dim[..dim.len()-2].iter().enumerate() {
+        mask = mask.unsqueeze(i as i32).broadcast(i as i32, r);
+    }
+    mask
+}
+
 // ======================= MultiHead Attention ======================= 
+// Q, K and V come from a single (d_model, 3 * d_model) projection; every head is computed at once by splitting
+// the projections into (heads, seq, d_model / heads) and batching
 
 pub struct MultiHeadAttention {
-    pub wq: Vec<Linear>,
-    pub wk: Vec<Linear>,
-    pub wv: Vec<Linear>,
+    pub wqkv: Linear,
     pub wo: Linear,
     pub num_heads: usize, 
     pub d_model: usize,
-    pub mask: Option<Tensor>
+    pub mask: Option<Tensor>, // additive mask
+    pub causal: bool
 }
 
 impl Module for MultiHeadAttention {
     fn params (&self) -> Vec<Tensor> { 
         vec![
-            self.wq.iter().map(|i| i.params()).collect::<Vec<Vec<Tensor>>>().concat(), 
-            self.wk.iter().map(|i| i.params()).collect::<Vec<Vec<Tensor>>>().concat(), 
-            self.wv.iter().map(|i| i.params()).collect::<Vec<Vec<Tensor>>>().concat(), 
+            self.wqkv.params(), 
             self.wo.params()
         ].concat()
     }
@@ -54,68 +78,76 @@ impl Module for MultiHeadAttention {
 
 impl MultiHeadAttention {
     pub fn f (&self, query: Tensor, key: Tensor, value: Tensor) -> Tensor {
-        let head_out: Vec<Tensor> = if let Some(m) = &self.mask { 
-            // masked attention
-            (0..self.num_heads).map(|i| 
-                AttentionMasked(
-                    self.wq[i].f(query.clone()), 
-                    self.wk[i].f(key.clone()),
-                    self.wv[i].f(value.clone()), 
-                    self.d_model,
-                    m.clone()
-                )
-            ).collect()
+        let seq = query.dim()[0];
+        let (h, d) = (self.num_heads as i32, self.d_model as i32);
+        let d_v = d / h;
+
+        // (seq, d_model) --> (heads, seq, d_v)
+        let split = |x: Tensor| x.view(vec![-1, h, d_v]).permute(&vec![1, 0, 2]);
+
+        let (q, k, v) = if Rc::ptr_eq(&query.n, &key.n) && Rc::ptr_eq(&key.n, &value.n) {
+            // self attention: one projection to (seq, 3 * d_model) --> (3, heads, seq, d_v)
+            let qkv = self.wqkv.f(query).view(vec![-1, 3, h, d_v]).permute(&vec![1, 2, 0, 3]);
+            (qkv.i(0, 0), qkv.i(1, 0), qkv.i(2, 0))
+        } else {
+            // the projection's weight is (d_model, [q | k | v]); project each input with its part
+            let w = self.wqkv.w.view(vec![d, 3, d]);
+            let proj = |x: Tensor, i: usize| split(autodiff::dot(x, w.i(i, 1)));
+            (proj(query, 0), proj(key, 1), proj(value, 2))
+        };
+
+        let mask = match (&self.mask, self.causal) {
+            (Some(m), true) => Some(m.clone() + causal_mask(seq)),
+            (Some(m), false) => Some(m.clone()),
+            (None, true) => Some(causal_mask(seq)),
+            (None, false) => None
+        };
+
+        let heads = if let Some(m) = mask { 
+            AttentionMasked(q, k, v, self.d_model, m)
         } else {
-            // no masked attention 
-            (0..self.num_heads).map(|i| 
-                Attention(
-                    self.wq[i].f(query.clone()), 
-                    self.wk[i].f(key.clone()),
-                    self.wv[i].f(value.clone()), 
-                    self.d_model
-                )
-            ).collect()
+            Attention(q, k, v, self.d_model)
         };
 
-        self.wo.f(autodiff::concat(head_out, -1))
+        // (heads, seq, d_v) --> (seq, d_model); same layout as concatenating the heads along the last dim
+        self.wo.f(heads.permute(&vec![1, 0, 2]).view(vec![seq as i32, d]))
     }
 }
 
 #[allow(non_snake_case)]
 pub fn MultiHeadAttention (d_model: usize, num_heads: usize) -> MultiHeadAttention {
     assert!(d_model % num_heads == 0, "d_model must be divisible by num heads");
-    let d_v = d_model / num_heads;
 
     MultiHeadAttention { 
-        wq: (0..num_heads).map(|_| Linear(d_model, d_v, false)).collect(),
-        wk: (0..num_heads).map(|_| Linear(d_model, d_v, false)).collect(), 
-        wv: (0..num_heads).map(|_| Linear(d_model, d_v, false)).collect(), 
+        wqkv: Linear(d_model, 3 * d_model, false),
         wo: Linear(d_model, d_model, false),
         num_heads,
         d_model,
-        mask: None
+        mask: None,
+        causal: false
     } 
 }
 
+// mask is additive: 0 where attending, -inf where masked
 #[allow(non_snake_case)]
 pub fn MaskedMultiHeadAttention (d_model: usize, num_heads: usize, mask: Tensor) -> MultiHeadAttention {
-    assert!(d_model % num_heads == 0, "d_model must be divisible by num heads");
-    let d_v = d_model / num_heads;
+    let mut mha = MultiHeadAttention(d_model, num_heads);
+    mha.mask = Some(mask);
+    mha
+}
 
-    MultiHeadAttention { 
-        wq: (0..num_heads).map(|_| Linear(d_model, d_v, false)).collect(),
-        wk: (0..num_heads).map(|_| Linear(d_model, d_v, false)).collect(), 
-        wv: (0..num_heads).map(|_| Linear(d_model, d_v, false)).collect(), 
-        wo: Linear(d_model, d_model, false),
-        num_heads,
-        d_model,
-        mask: Some(mask)
-    } 
+#[allow(non_snake_case)]
+pub fn CausalMultiHeadAttention (d_model: usize, num_heads: usize) -> MultiHeadAttention {
+    let mut mha = MultiHeadAttention(d_model, num_heads);
+    mha.causal = true;
+    mha
 }
+
 // ======================= Attention Feedforward ======================= 
 pub struct AttentionFeedforward {
     w_expand: Linear,   // d_model to inner_dim
     w_contract: Linear, // inner_dim to d_model
+    pub activation: Option<Box<dyn SeqF>> // applied to the inner_dim; none by default
 }
 
 #[allow(non_snake_case)]
@@ -123,9 +155,18 @@ pub fn AttentionFeedforward (d_model:usize, inner_dim: usize) -> AttentionFeedfo
     AttentionFeedforward { 
         w_expand: Linear(d_model, inner_dim, true), 
         w_contract: Linear(inner_dim, d_model, true), 
+        activation: None
     }
 }
 
+// ex: AttentionFeedforwardWithActivation(d_model, inner_dim, GELU())
+#[allow(non_snake_case)]
+pub fn AttentionFeedforwardWithActivation (d_model:usize, inner_dim: usize, activation: impl SeqF + 'static) -> AttentionFeedforward {
+    let mut ffwd = AttentionFeedforward(d_model, inner_dim);
+    ffwd.activation = Some(Box::new(activation));
+    ffwd
+}
+
 impl Module for AttentionFeedforward {
     fn params (&self) -> Vec<Tensor> {
         vec![self.w_expand.params(), self.w_contract.params()].concat()
@@ -134,7 +175,12 @@ impl Module for AttentionFeedforward {
 
 impl SeqF for AttentionFeedforward {
     fn f (&self, x: Tensor) -> Tensor {
-        self.w_contract.f(self.w_expand.f(x)) 
+        let h = self.w_expand.f(x);
+        let h = match &self.activation {
+            Some(activation) => activation.f(h),
+            None => h
+        };
+        self.w_contract.f(h)
     } 
 }
 
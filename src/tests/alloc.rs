@@ -0,0 +1,46 @@
+// allocation passes after fusion (kernel::alloc)
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use crate::{alloc::alloc_temp_opt, kernel_decl::{BinaryOp, Expression, Input, KernelProcedure, Kernels, Matrix, Output, ReduceOp}, VarId};
+
+    fn mat (id: u32, access: Expression) -> Matrix {
+        Matrix { id: VarId(id), access }
+    }
+
+    fn global (id: u32) -> Input {
+        Input::Mat { mat: mat(id, Expression::make_global()) }
+    }
+
+    #[test]
+    fn alloc_temp_read_only () {
+        // b (1) is alloc + dealloc within the fusion but only read, so it can't be the temp; a (2) still can
+        let kernels = vec![
+            Kernels::Alloc { id: VarId(2), size: 4, content: None },
+            Kernels::Reduce {
+                id: 0,
+                a: Input::Mat { mat: mat(0, Expression::make_add(Expression::make_mult(Expression::make_y(), Expression::make_const(4)), Expression::make_x())) },
+                res: Output::Mat { mat: mat(2, Expression::make_x()) },
+                op: ReduceOp::Sum,
+                vec_size: 4,
+                reduce_size: 2
+            },
+            Kernels::Alloc { id: VarId(1), size: 4, content: Some(Arc::new(vec![1.0, 2.0, 3.0, 4.0])) },
+            Kernels::Binary { id: 1, a: global(2), b: global(1), res: Output::Mat { mat: mat(2, Expression::make_global()) }, op: BinaryOp::Multiply, size: 4 },
+            Kernels::Binary { id: 2, a: global(3), b: global(2), res: Output::Mat { mat: mat(3, Expression::make_global()) }, op: BinaryOp::Add, size: 4 },
+            Kernels::Dealloc { id: VarId(1), size: 4 },
+            Kernels::Dealloc { id: VarId(2), size: 4 }
+        ];
+        let mut proc = KernelProcedure::new(vec![
+            Kernels::ReduceElwExpr { id: 3, kernels, vec_size: 4, reduce_size: 2 }
+        ], "main".to_string());
+
+        alloc_temp_opt(&mut proc);
+
+        let Kernels::ReduceElwExpr { kernels, .. } = &proc.kernels[0] else { panic!("Expected the fused kernel") };
+        let allocs: Vec<VarId> = kernels.iter().filter_map(|k| if let Kernels::Alloc { id, .. } = k { Some(*id) } else { None }).collect();
+        assert_eq!(allocs, vec![VarId(1)]);
+        assert!(matches!(&kernels[0], Kernels::Reduce { res: Output::Temp, .. }));
+        assert!(matches!(&kernels[2], Kernels::Binary { a: Input::Temp, b: Input::Mat { .. }, res: Output::Temp, .. }));
+    }
+}
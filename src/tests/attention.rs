@@ -0,0 +1,103 @@
+// fused multi-head attention against attention computed per head
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, nn::{self, Module, SeqF}, Tensor};
+
+    const D_MODEL: usize = 8;
+    const HEADS: usize = 2;
+    const SEQ: usize = 3;
+
+    fn eval (ts: &[Tensor]) -> Vec<Vec<f32>> {
+        for t in ts {
+            t.forward();
+            t.val().unwrap().keep();
+        }
+        autodiff::execute();
+
+        ts.iter().map(|t| t.val().unwrap().get().round(4).data.to_vec()).collect()
+    }
+
+    // each head projected, attended and concatenated separately
+    fn per_head (mha: &nn::MultiHeadAttention, x: &Tensor, mask: Option<Tensor>) -> Tensor {
+        let d_v = D_MODEL / HEADS;
+        let proj = |part: usize, h: usize| {
+            let start = part * D_MODEL + h * d_v;
+            autodiff::dot(x.clone(), mha.wqkv.w.r(start..start + d_v, 1))
+        };
+
+        let heads: Vec<Tensor> = (0..HEADS).map(|h| match &mask {
+            Some(m) => nn::AttentionMasked(proj(0, h), proj(1, h), proj(2, h), D_MODEL, m.clone()),
+            None => nn::Attention(proj(0, h), proj(1, h), proj(2, h), D_MODEL)
+        }).collect();
+
+        mha.wo.f(autodiff::concat(heads, -1))
+    }
+
+    #[test]
+    fn mha () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let mha = nn::MultiHeadAttention(D_MODEL, HEADS);
+        let x = autodiff::randn(vec![SEQ, D_MODEL]);
+
+        // separate query/key/value tensors use the projection's parts instead of a single projection
+        let y = x.view(vec![SEQ as i32, -1]);
+
+        let res = eval(&[mha.f(x.clone(), x.clone(), x.clone()), per_head(&mha, &x, None), mha.f(x.clone(), y.clone(), y)]);
+        assert_eq!(res[0], res[1]);
+        assert_eq!(res[0], res[2]);
+    }
+
+    #[test]
+    fn mha_causal () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let mha = nn::CausalMultiHeadAttention(D_MODEL, HEADS);
+        let x = autodiff::randn(vec![SEQ, D_MODEL]);
+        let res = mha.f(x.clone(), x.clone(), x.clone());
+
+        // the first position only attends to itself, so it doesn't depend on the rest of the sequence
+        let first = mha.f(x.r(0..1, 0), x.r(0..1, 0), x.r(0..1, 0));
+
+        let out = eval(&[res, per_head(&mha, &x, Some(nn::causal_mask(SEQ))), first]);
+        assert_eq!(out[0], out[1]);
+        assert_eq!(out[0][..D_MODEL], out[2][..]);
+        assert!(out[0].iter().all(|v| v.is_finite()));
+    }
+
+    #[test]
+    fn mha_grad () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let mha = nn::CausalMultiHeadAttention(D_MODEL, HEADS);
+        let x = autodiff::randn(vec![SEQ, D_MODEL]);
+
+        let res = mha.f(x.clone(), x.clone(), x.clone()).sum(1).sum(0);
+        res.forward();
+        res.backward();
+        mha.wqkv.w.grad().keep();
+        autodiff::execute();
+
+        let g = mha.wqkv.w.grad().get();
+        assert_eq!(g.dim, vec![D_MODEL, 3 * D_MODEL]);
+        assert!(g.data.iter().all(|v| v.is_finite()), "{:?}", g.data);
+    }
+
+    #[test]
+    fn ffwd_activation () {
+        // no activation between the projections unless one is passed
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::randn(vec![SEQ, D_MODEL]);
+        let ffwd = nn::AttentionFeedforward(D_MODEL, 16);
+        let ffwd_gelu = nn::AttentionFeedforwardWithActivation(D_MODEL, 16, nn::GELU());
+
+        let manual = |ffwd: &nn::AttentionFeedforward, act: &dyn Fn(Tensor) -> Tensor| {
+            let p = ffwd.params();
+            autodiff::dot(act(autodiff::dot(x.clone(), p[0].clone()) + p[1].clone()), p[2].clone()) + p[3].clone()
+        };
+
+        let vals = eval(&[
+            ffwd.f(x.clone()), manual(&ffwd, &|h| h),
+            ffwd_gelu.f(x.clone()), manual(&ffwd_gelu, &|h| h.gelu())
+        ]);
+        assert_eq!(vals[0], vals[1]);
+        assert_eq!(vals[2], vals[3]);
+    }
+}
@@ -0,0 +1,66 @@
+// compile once, run many times
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+    use crate::{autodiff, devices::cpu::Reference, CompiledProgram, Device, IRBase, ValueData, VarId};
+
+    // counts how many times the programs are compiled
+    struct Counting {
+        device: Reference,
+        compiled: Arc<AtomicUsize>
+    }
+
+    impl Device for Counting {
+        fn compile (&mut self, program: &CompiledProgram) {
+            self.compiled.fetch_add(1, Ordering::Relaxed);
+            self.device.compile(program);
+        }
+
+        fn run (&mut self, program: &CompiledProgram) {
+            self.device.run(program);
+        }
+
+        fn get_tensor (&self, id: VarId) -> ValueData {
+            self.device.get_tensor(id)
+        }
+
+        fn ir_callback (&self, cmds: &mut IRBase) {
+            self.device.ir_callback(cmds);
+        }
+    }
+
+    #[test]
+    fn compile () {
+        let compiled = Arc::new(AtomicUsize::new(0));
+        autodiff::set_device(Counting { device: Reference::new(), compiled: compiled.clone() });
+
+        let x = autodiff::placeholder(vec![1, 3]);
+        let w = autodiff::tensor(vec![1.0, 2.0, -1.0], vec![1, 3]);
+
+        let res = (x.clone() * w.clone()).sin().sum(1);
+        res.forward();
+        res.backward();
+        res.val().unwrap().keep();
+
+        let mut program = autodiff::compile();
+
+        for i in 0..3 {
+            let data = vec![i as f32, 0.5, -0.25 * i as f32];
+            autodiff::feed(&x, data.clone());
+            program.run();
+
+            let expected: f32 = data.iter().zip([1.0, 2.0, -1.0]).map(|(x, w)| (x * w).sin()).sum();
+            let expected_grad: Vec<f32> = data.iter().zip([1.0, 2.0, -1.0]).map(|(x, w)| x * (x * w).cos()).collect();
+
+            let val = res.val().unwrap().get();
+            assert!((val.data[0] - expected).abs() < 1e-5, "Run {}: {} vs {}", i, val.data[0], expected);
+
+            let grad = w.grad().get();
+            for (g, e) in grad.data.iter().zip(expected_grad.iter()) {
+                assert!((g - e).abs() < 1e-5, "Run {}: grad {} vs {}", i, g, e);
+            }
+        }
+
+        assert_eq!(compiled.load(Ordering::Relaxed), 1);
+    }
+}
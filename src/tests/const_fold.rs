@@ -0,0 +1,59 @@
+// constant folding and algebraic simplification of the HLIR (ir::opts::const_fold)
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, tests::harness::{check_same, count}, IRCmds};
+
+    #[test]
+    fn fold_identities () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::tensor(vec![1.0, -2.0, 3.0, 0.5, 4.0, -1.5], vec![2, 3]);
+
+        let two = autodiff::constant(2.0, vec![2, 3]);
+        let three = autodiff::constant(3.0, vec![2, 3]);
+        let res = (x.clone() * 1.0 + 0.0) * (two * three).sqrt()   // x * sqrt(6)
+            + x.recip().recip()                                       // x
+            + x.sin() * 0.0;                                          // 0
+        let proc = check_same(&[&res]);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::Recip { .. } | IRCmds::Sqrt { .. } | IRCmds::Sin { .. })), 0);
+        // x * sqrt(6) + x is all that's left; the kept result (+ 0) is still computed under its own id
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::ElwMultiply { .. })), 1);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::ElwAdd { .. })), 2);
+    }
+
+    #[test]
+    fn fold_sqrt_negative () {
+        // the devices (and interp) compute sqrt(|x|); folding must not turn it into NaN
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let s = autodiff::constant(-4.0, vec![2]).sqrt();
+        let proc = check_same(&[&s]);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::Sqrt { .. })), 0);
+        assert_eq!(*s.val().unwrap().get().data, vec![2.0, 2.0]);
+    }
+
+    #[test]
+    fn fold_reduce () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let c = autodiff::constant(1.5, vec![4, 3]);
+        let s = c.sum(1);
+        let m = c.max(1);
+        let p = c.prod(1);
+        let proc = check_same(&[&s, &m, &p]);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::Sum { .. } | IRCmds::Max { .. } | IRCmds::Prod { .. })), 0);
+        assert_eq!(*s.val().unwrap().get().data, vec![4.5; 4]);
+    }
+
+    #[test]
+    fn fold_changed () {
+        // variables written in a loop aren't constants, and aren't aliased away
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let mut y = autodiff::ones(vec![3]);
+        let x = autodiff::tensor(vec![1.0, 2.0, 3.0], vec![3]);
+        autodiff::ir_for(0..3, |_| {
+            y += x.clone() * 1.0;
+            y.forward();
+        });
+        let z = y.clone() * 1.0 + 0.0;
+        check_same(&[&z]);
+        assert_eq!(*z.val().unwrap().get().data, vec![4.0, 7.0, 10.0]);
+    }
+}
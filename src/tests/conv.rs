@@ -0,0 +1,144 @@
+// im2col convolutions against host computations
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, nn, SeqF, Tensor};
+
+    fn data (dim: &[usize], seed: f32) -> (Vec<f32>, Vec<usize>) {
+        let n: usize = dim.iter().product();
+        ((0..n).map(|i| ((i as f32 + seed) * 0.37).sin()).collect(), dim.to_vec())
+    }
+
+    fn eval (t: Tensor) -> (Vec<usize>, Vec<f32>) {
+        t.forward();
+        t.val().unwrap().keep();
+        autodiff::execute();
+
+        let v = t.val().unwrap().get().round(4);
+        (v.dim.clone(), v.data.to_vec())
+    }
+
+    fn round (v: Vec<f32>) -> Vec<f32> {
+        v.iter().map(|x| (x * 10000.0).round() / 10000.0).collect()
+    }
+
+    // (stride, padding, dilation, groups)
+    type Params = (usize, usize, usize, usize);
+
+    fn host_conv2d (x: &[f32], x_dim: &[usize], w: &[f32], w_dim: &[usize], (s, p, d, g): Params) -> (Vec<usize>, Vec<f32>) {
+        let (n, c, h, wd) = (x_dim[0], x_dim[1], x_dim[2], x_dim[3]);
+        let (co, cg, kh, kw) = (w_dim[0], w_dim[1], w_dim[2], w_dim[3]);
+        let ho = (h + 2*p - d*(kh-1) - 1) / s + 1;
+        let wo = (wd + 2*p - d*(kw-1) - 1) / s + 1;
+
+        let mut res = vec![0.0; n * co * ho * wo];
+        for b in 0..n { for o in 0..co { for i in 0..ho { for j in 0..wo {
+            let grp = o / (co / g);
+            let mut acc = 0.0;
+            for ci in 0..cg { for u in 0..kh { for v in 0..kw {
+                let (y, z) = ((i*s + u*d) as i32 - p as i32, (j*s + v*d) as i32 - p as i32);
+                if y < 0 || z < 0 || y >= h as i32 || z >= wd as i32 { continue }
+                let xc = grp * cg + ci;
+                acc += x[((b*c + xc)*h + y as usize)*wd + z as usize] * w[((o*cg + ci)*kh + u)*kw + v];
+            }}}
+            res[((b*co + o)*ho + i)*wo + j] = acc;
+        }}}}
+        (vec![n, co, ho, wo], res)
+    }
+
+    fn host_conv_transpose2d (x: &[f32], x_dim: &[usize], w: &[f32], w_dim: &[usize], (s, p, d, g): Params) -> (Vec<usize>, Vec<f32>) {
+        let (n, c, h, wd) = (x_dim[0], x_dim[1], x_dim[2], x_dim[3]);
+        let (cog, kh, kw) = (w_dim[1], w_dim[2], w_dim[3]);
+        let co = cog * g;
+        let ho = (h-1)*s + d*(kh-1) + 1 - 2*p;
+        let wo = (wd-1)*s + d*(kw-1) + 1 - 2*p;
+
+        // scatter every input into the output
+        let mut res = vec![0.0; n * co * ho * wo];
+        for b in 0..n { for ci in 0..c { for i in 0..h { for j in 0..wd {
+            let grp = ci / (c / g);
+            for oc in 0..cog { for u in 0..kh { for v in 0..kw {
+                let (y, z) = ((i*s + u*d) as i32 - p as i32, (j*s + v*d) as i32 - p as i32);
+                if y < 0 || z < 0 || y >= ho as i32 || z >= wo as i32 { continue }
+                let o = grp * cog + oc;
+                res[((b*co + o)*ho + y as usize)*wo + z as usize] += x[((b*c + ci)*h + i)*wd + j] * w[((ci*cog + oc)*kh + u)*kw + v];
+            }}}
+        }}}}
+        (vec![n, co, ho, wo], res)
+    }
+
+    fn check (x_dim: &[usize], w_dim: &[usize], params: Params, transpose: bool) {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let (x, _) = data(x_dim, 0.0);
+        let (w, _) = data(w_dim, 3.0);
+        let (s, p, d, g) = params;
+
+        let xt = autodiff::tensor(x.clone(), x_dim.to_vec());
+        let wt = autodiff::tensor(w.clone(), w_dim.to_vec());
+        let (res, (dim, expected)) = if transpose {
+            (xt.conv_transpose2d(&wt, s, p, d, g), host_conv_transpose2d(&x, x_dim, &w, w_dim, params))
+        } else {
+            (xt.conv2d(&wt, s, p, d, g), host_conv2d(&x, x_dim, &w, w_dim, params))
+        };
+        assert_eq!(eval(res), (dim, round(expected)));
+    }
+
+    #[test]
+    fn unfold_fold () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::tensor((0..7).map(|v| v as f32).collect(), vec![7]);
+        let u = x.unfold(0, 3, 2, 1);
+        assert_eq!(eval(u.clone()), (vec![3, 3], vec![0.0, 1.0, 2.0, 2.0, 3.0, 4.0, 4.0, 5.0, 6.0]));
+
+        // overlapping elements are summed
+        let f = u.fold(0, 7, 2, 1);
+        assert_eq!(eval(f), (vec![7], vec![0.0, 1.0, 4.0, 3.0, 8.0, 5.0, 6.0]));
+
+        let x = autodiff::tensor((0..8).map(|v| v as f32).collect(), vec![2, 4]);
+        assert_eq!(eval(x.unfold(1, 2, 1, 2)), (vec![2, 2, 2], vec![0.0, 2.0, 1.0, 3.0, 4.0, 6.0, 5.0, 7.0]));
+        assert_eq!(eval(x.narrow(1, 1, 2)), (vec![2, 2], vec![1.0, 2.0, 5.0, 6.0]));
+    }
+
+    #[test]
+    fn conv2d () {
+        check(&[2, 3, 5, 5], &[4, 3, 3, 3], (1, 0, 1, 1), false);
+        check(&[1, 2, 6, 5], &[3, 2, 3, 2], (2, 1, 1, 1), false);
+        check(&[1, 2, 7, 7], &[2, 2, 3, 3], (1, 2, 2, 1), false);
+        check(&[2, 4, 4, 4], &[6, 2, 2, 2], (1, 1, 1, 2), false);
+    }
+
+    #[test]
+    fn conv_transpose2d () {
+        check(&[2, 3, 3, 3], &[3, 2, 3, 3], (1, 0, 1, 1), true);
+        check(&[1, 2, 3, 4], &[2, 3, 3, 2], (2, 1, 1, 1), true);
+        check(&[1, 4, 3, 3], &[4, 1, 2, 2], (2, 0, 2, 2), true);
+    }
+
+    #[test]
+    fn conv_module () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let (x, x_dim) = data(&[1, 2, 4, 4], 0.0);
+        let (w, w_dim) = data(&[3, 2, 3, 3], 3.0);
+        let b = vec![0.5, -1.0, 2.0];
+
+        let conv = nn::Conv2dWithWeights(autodiff::tensor(w.clone(), w_dim.clone()), Some(autodiff::tensor(b.clone(), vec![3])), 1, 1, 1, 1);
+        let (dim, mut expected) = host_conv2d(&x, &x_dim, &w, &w_dim, (1, 1, 1, 1));
+        expected.iter_mut().enumerate().for_each(|(i, v)| *v += b[i / 16]);
+        assert_eq!(eval(conv.f(autodiff::tensor(x.clone(), x_dim.clone()))), (dim, round(expected)));
+
+        let conv = nn::ConvTranspose2d(2, 3, 3, 2, 1, true);
+        assert_eq!(conv.f(autodiff::tensor(x, x_dim)).dim(), vec![1, 3, 7, 7]);
+    }
+
+    #[test]
+    fn conv_grad () {
+        let check = |f: &dyn Fn(&[Tensor]) -> Tensor, inputs: Vec<(Vec<f32>, Vec<usize>)>| {
+            autodiff::set_device(autodiff::devices::cpu::Reference::new());
+            autodiff::gradcheck(f, inputs, 1e-2, 1e-2).expect("Gradient check failed");
+        };
+
+        check(&|t| t[0].unfold(1, 2, 2, 1).fold(1, 4, 1, 2).sin(), vec![data(&[2, 5], 0.0)]);
+        check(&|t| t[0].conv2d(&t[1], 2, 1, 1, 1).sin(), vec![data(&[1, 2, 4, 4], 0.0), data(&[2, 2, 3, 3], 3.0)]);
+        check(&|t| t[0].conv2d(&t[1], 1, 0, 1, 2).sin(), vec![data(&[1, 2, 3, 3], 0.0), data(&[2, 1, 2, 2], 3.0)]);
+        check(&|t| t[0].conv_transpose2d(&t[1], 2, 1, 1, 1).sin(), vec![data(&[1, 2, 3, 3], 0.0), data(&[2, 2, 3, 3], 3.0)]);
+    }
+}
@@ -2,11 +2,17 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::{autodiff, devices::CLDeviceType};
+    use crate::{autodiff, tests::harness::use_device};
     
     #[test]
-    fn if_ctrl () {
-        autodiff::set_device(autodiff::devices::OpenCL::new(CLDeviceType::ALL));
+    fn if_ctrl () { if_ctrl_on(false) }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn if_ctrl_opencl () { if_ctrl_on(true) }
+
+    fn if_ctrl_on (opencl: bool) {
+        use_device(opencl);
 
         let x = autodiff::scalar(1.0);
         let mut y = autodiff::scalar(3.0);
@@ -33,8 +39,14 @@ mod tests {
     }
 
     #[test]
-    fn if_else_ctrl () {
-        autodiff::set_device(autodiff::devices::OpenCL::new(CLDeviceType::ALL));
+    fn if_else_ctrl () { if_else_ctrl_on(false) }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn if_else_ctrl_opencl () { if_else_ctrl_on(true) }
+
+    fn if_else_ctrl_on (opencl: bool) {
+        use_device(opencl);
 
         let x = autodiff::scalar(1.0);
         let mut y = autodiff::scalar(3.0);
@@ -70,8 +82,14 @@ mod tests {
     }
 
     #[test]
-    fn for_ctrl () {
-        autodiff::set_device(autodiff::devices::OpenCL::new(CLDeviceType::ALL));
+    fn for_ctrl () { for_ctrl_on(false) }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn for_ctrl_opencl () { for_ctrl_on(true) }
+
+    fn for_ctrl_on (opencl: bool) {
+        use_device(opencl);
         
         let mut y = autodiff::scalar(10.0);
         autodiff::ir_for(-3..5, |i| {
@@ -88,9 +106,15 @@ mod tests {
     }
 
     // for loop and everything ctrl
-    #[test] 
-    fn evrty_ctrl () {
-        autodiff::set_device(autodiff::devices::OpenCL::new(CLDeviceType::ALL));
+    #[test]
+    fn evrty_ctrl () { evrty_ctrl_on(false) }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn evrty_ctrl_opencl () { evrty_ctrl_on(true) }
+
+    fn evrty_ctrl_on (opencl: bool) {
+        use_device(opencl);
 
         let mut y = autodiff::scalar(3.0);
         let mut y_two = autodiff::scalar(3.0);
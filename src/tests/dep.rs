@@ -0,0 +1,24 @@
+// dependency list: values the dependency optimizer has to keep
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, core::is_harsh};
+
+    #[test]
+    fn dep_harsh_reset () {
+        // eager_dep_opt only applies to the program it was set for; set_device starts a new one
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        autodiff::eager_dep_opt();
+        assert!(is_harsh());
+
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        assert!(!is_harsh());
+
+        // so a declared tensor is kept without keep(), even if nothing reads it
+        let x = autodiff::tensor(vec![1.0, 2.0], vec![2]);
+        autodiff::execute();
+
+        let x_val = x.val().unwrap().get();
+        assert!(!x_val.is_none);
+        assert_eq!(*x_val.data, vec![1.0, 2.0]);
+    }
+}
@@ -0,0 +1,50 @@
+// the reference device vs OpenCL on the same programs (ignored by default, needs an OpenCL device)
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, tests::harness::cmp_devices};
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn devices_elw_dot () {
+        cmp_devices(|| {
+            let x = autodiff::tensor(vec![1.0, -2.0, 3.0, 0.5, 2.0, -1.0], vec![2, 3]);
+            let w = autodiff::tensor(vec![0.5, -1.0, 2.0, 1.5, -0.5, 1.0], vec![3, 2]);
+
+            let res = (autodiff::dot(x.clone(), w.clone()).sin() * x.sum(1).unsqueeze(1)).exp().sum(0);
+            res.forward();
+            res.backward();
+
+            vec![res.val().unwrap(), x.grad(), w.grad()]
+        }, 1e-5);
+    }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn devices_movement () {
+        cmp_devices(|| {
+            let x = autodiff::tensor((0..12).map(|v| v as f32 * 0.25 - 1.0).collect(), vec![3, 4]);
+            let y = autodiff::tensor((0..6).map(|v| v as f32).collect(), vec![3, 2]);
+
+            let res = autodiff::concat(vec![x.clone(), y.clone()], 1).t().max(1);
+            res.forward();
+            res.backward();
+
+            vec![res.val().unwrap(), x.grad(), y.grad()]
+        }, 1e-5);
+    }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn devices_ctrl () {
+        cmp_devices(|| {
+            let x = autodiff::tensor(vec![0.5, -1.0, 2.0], vec![3]);
+            let mut y = autodiff::ones(vec![3]);
+            autodiff::ir_for(0..4, |_| {
+                y += x.cos();
+                y.forward();
+            });
+
+            vec![y.val().unwrap()]
+        }, 1e-5);
+    }
+}
@@ -0,0 +1,94 @@
+// differential testing: the same program with every optimization disabled (IROPT=0, KOPT=0) vs fully optimized
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use crate::{autodiff, tests::harness::{diff_check, diff_check_named}, Tensor};
+
+    // ============ Random program generation ============
+    fn rand_tensor (rng: &mut StdRng, dim: Vec<usize>) -> Tensor {
+        let data = (0..dim.iter().product::<usize>()).map(|_| rng.random_range(-1.0..1.0)).collect();
+        autodiff::tensor(data, dim)
+    }
+
+    // random chain of ops over 2-dim tensors. Returns the result + every declared tensor
+    fn random_program (seed: u64) -> (Tensor, Vec<Tensor>) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let dim = vec![rng.random_range(1..5), rng.random_range(1..5)];
+
+        let mut leaves = vec![rand_tensor(&mut rng, dim)];
+        let mut x = leaves[0].clone();
+
+        for _ in 0..rng.random_range(1..8) {
+            let (r, c) = (x.dim()[0], x.dim()[1]);
+            x = match rng.random_range(0..17) {
+                0 => x.sin(),
+                1 => x.cos(),
+                2 => x * rng.random_range(-2.0..2.0),
+                3 => x + rng.random_range(-2.0..2.0),
+                4 => (1.0 + (-x).exp()).recip(), // sigmoid
+                5 => x.t(),
+                6 => x.view(vec![c as i32, r as i32]),
+                7 => x.sum(1).unsqueeze(1),
+                8 => x.i(rng.random_range(0..r), 0).unsqueeze(0),
+                9 => x.i(rng.random_range(0..c), 1).unsqueeze(1),
+                10 => x.max(1).unsqueeze(1),
+                11 => x.min(0).unsqueeze(0),
+                _ => {
+                    // ops with another declared tensor
+                    let op = rng.random_range(0..5);
+                    let dim = match op {
+                        0 => vec![1, c], // broadcasted
+                        3 => vec![c, rng.random_range(1..5)],
+                        4 => vec![r, rng.random_range(1..4)],
+                        _ => vec![r, c]
+                    };
+                    let y = rand_tensor(&mut rng, dim);
+                    leaves.push(y.clone());
+
+                    match op {
+                        0 => x + y,
+                        1 => x * y,
+                        2 => x - y,
+                        3 => autodiff::dot(x, y),
+                        _ => autodiff::concat(vec![x, y], 1)
+                    }
+                }
+            };
+        }
+
+        (x.sum(1).sum(0), leaves)
+    }
+
+    fn random_check (seed: u64) {
+        diff_check_named(|| {
+            let (res, leaves) = random_program(seed);
+            res.forward();
+            res.backward();
+
+            let mut vals = vec![res.val().unwrap()];
+            vals.extend(leaves.iter().map(|l| l.grad()));
+            vals
+        }, 1e-4, &format!("seed {}", seed));
+    }
+
+    #[test]
+    fn diff () {
+        diff_check(|| {
+            let x = autodiff::tensor(vec![1.0, -2.0, 3.0, 0.5, 2.0, -1.0], vec![2, 3]);
+            let w = autodiff::tensor(vec![0.5, -1.0, 2.0, 1.5, -0.5, 1.0], vec![3, 2]);
+
+            let res = (autodiff::dot(x.clone(), w.clone()).sin() * x.sum(1).unsqueeze(1)).sum(0);
+            res.forward();
+            res.backward();
+
+            vec![res.val().unwrap(), x.grad(), w.grad()]
+        }, 1e-5);
+    }
+
+    #[test]
+    fn diff_random () {
+        for seed in 0..64 {
+            random_check(seed);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+// on-device random numbers and dropout
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, devices::cpu::rand::philox, nn, tests::harness::check_interp, SeqF};
+
+    #[test]
+    fn philox_kat () {
+        // known answers from Random123
+        assert_eq!(philox([0, 0], [0, 0, 0, 0]), [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]);
+        assert_eq!(
+            philox([0xffffffff, 0xffffffff], [0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff]), 
+            [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd]
+        );
+    }
+
+    #[test]
+    fn dropout_mask () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::ones(vec![20, 50]);
+        let y = x.dropout(0.25, true);
+        y.forward();
+        y.val().unwrap().keep();
+        autodiff::execute();
+
+        // kept elements are scaled by 1 / (1 - p)
+        let v = y.val().unwrap().get();
+        assert_eq!(v.dim, vec![20, 50]);
+        assert!(v.data.iter().all(|&f| f == 0.0 || (f - 1.0 / 0.75).abs() < 1e-6), "Dropout values must be 0 or 1 / (1 - p)");
+
+        let dropped = v.data.iter().filter(|&&f| f == 0.0).count() as f32 / 1000.0;
+        assert!((dropped - 0.25).abs() < 0.05, "Dropped {} of the elements, expected 0.25", dropped);
+    }
+
+    #[test]
+    fn dropout_loop () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::ones(vec![64]);
+        let mut y = autodiff::zeros(vec![64]);
+
+        // with the same mask every iteration, every element would be either 0 or 8
+        autodiff::ir_for(0..4, |_| {
+            y += x.dropout(0.5, true);
+            y.forward();
+        });
+        y.val().unwrap().keep();
+
+        let mut program = autodiff::compile();
+        program.run();
+        let first = y.val().unwrap().get().data.to_vec();
+        assert!(first.iter().any(|&f| f != 0.0 && f != 8.0), "Masks are repeated across iterations");
+
+        // the generator state is carried over to the next run as well
+        program.run();
+        let second = y.val().unwrap().get().data.to_vec();
+        assert_ne!(first, second, "Masks are repeated across runs");
+    }
+
+    #[test]
+    fn dropout_eval () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::tensor(vec![1.0, -2.0, 3.0, 4.0], vec![2, 2]);
+
+        let mut drop = nn::Dropout(0.5);
+        drop.training = false;
+        let y = drop.f(x.clone()) + x.dropout(0.9, false);
+        y.forward();
+        y.val().unwrap().keep();
+        autodiff::execute();
+
+        assert_eq!(*y.val().unwrap().get().data, vec![2.0, -4.0, 6.0, 8.0]);
+    }
+
+    #[test]
+    fn dropout_grad () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::tensor((0..30).map(|v| v as f32 + 1.0).collect(), vec![30]);
+        let y = x.dropout(0.5, true);
+        let l = y.unsqueeze(0).sum(1);
+        l.forward();
+        l.backward();
+        y.val().unwrap().keep();
+        autodiff::execute();
+
+        // the gradient goes through the same mask as the forward pass
+        let y_val = y.val().unwrap().get();
+        let grad = x.grad().get();
+        for (i, (y, g)) in y_val.data.iter().zip(grad.data.iter()).enumerate() {
+            assert_eq!(*y, *g * (i as f32 + 1.0), "Gradient mask doesn't match the forward mask");
+        }
+    }
+
+    #[test]
+    fn rand_interp () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::tensor((0..24).map(|v| (v as f32 * 0.37).sin()).collect(), vec![2, 3, 4]);
+
+        let res = x.dropout(0.3, true).sin() + x.dropout(0.6, true).permute(&vec![0, 2, 1]).permute(&vec![0, 2, 1]);
+        check_interp(&res);
+    }
+}
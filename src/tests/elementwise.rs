@@ -0,0 +1,86 @@
+// elementwise math funcs and activations built from the existing unary IR
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, Tensor};
+
+    // forwards f on data and compares with expected (same order)
+    fn check_vals<F: Fn(&Tensor) -> Tensor> (f: F, data: Vec<f32>, expected: Vec<f32>, tol: f32) {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let n = data.len();
+        let x = autodiff::tensor(data, vec![n]);
+        let y = f(&x);
+        y.forward();
+        y.val().unwrap().keep();
+        autodiff::execute();
+
+        let v = y.val().unwrap().get();
+        for (a, b) in v.data.iter().zip(expected.iter()) {
+            assert!((a - b).abs() <= tol, "{:?} vs {:?}", v.data, expected);
+        }
+    }
+
+    fn check_grad<F: Fn(&[Tensor]) -> Tensor> (f: F, data: Vec<f32>) {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let n = data.len();
+        autodiff::gradcheck(f, vec![(data, vec![n])], 1e-2, 1e-2).expect("Gradient check failed");
+    }
+
+    #[test]
+    fn elw_std () {
+        // against the std implementations
+        let x = vec![-3.0, -1.5, -0.2, 0.0, 0.4, 1.0, 2.5, 12.0];
+        let map = |f: fn(f32) -> f32| x.iter().map(|&v| f(v)).collect::<Vec<f32>>();
+
+        check_vals(|t| t.abs(), x.clone(), map(f32::abs), 0.0);
+        check_vals(|t| t.sign(), x.clone(), map(|v| if v == 0.0 { 0.0 } else { v.signum() }), 0.0);
+        check_vals(|t| t.tanh(), x.clone(), map(f32::tanh), 1e-6);
+        check_vals(|t| t.atan(), x.clone(), map(f32::atan), 1e-6);
+        check_vals(|t| t.clamp(-1.0, 2.0), x.clone(), map(|v| v.clamp(-1.0, 2.0)), 0.0);
+
+        let u = vec![-1.0, -0.7, -0.1, 0.0, 0.3, 0.99, 1.0];
+        check_vals(|t| t.acos(), u.clone(), u.iter().map(|v| v.acos()).collect(), 1e-6);
+
+        let c = vec![1.0, 1.5, 3.0, 100.0];
+        check_vals(|t| t.acosh(), c.clone(), c.iter().map(|v| v.acosh()).collect(), 1e-5);
+    }
+
+    #[test]
+    fn elw_activations () {
+        let x = vec![-100.0, -2.0, -0.5, 0.0, 0.5, 2.0, 100.0];
+
+        check_vals(|t| t.erf(), x.clone(), vec![-1.0, -0.9953223, -0.5204999, 0.0, 0.5204999, 0.9953223, 1.0], 2e-7);
+        check_vals(|t| t.gelu(), x.clone(), vec![0.0, -0.0455003, -0.1542688, 0.0, 0.3457312, 1.9544997, 100.0], 1e-6);
+        check_vals(|t| t.silu(), x.clone(), vec![0.0, -0.2384058, -0.1887703, 0.0, 0.3112297, 1.7615942, 100.0], 1e-5);
+        check_vals(|t| t.elu(1.0), x.clone(), vec![-1.0, -0.8646647, -0.3934693, 0.0, 0.5, 2.0, 100.0], 1e-6);
+        check_vals(|t| t.leaky_relu(0.1), x.clone(), vec![-10.0, -0.2, -0.05, 0.0, 0.5, 2.0, 100.0], 1e-6);
+        check_vals(|t| t.softplus(), x.clone(), vec![0.0, 0.1269280, 0.4740770, 0.6931472, 0.9740770, 2.1269280, 100.0], 1e-5);
+    }
+
+    #[test]
+    fn elw_grad () {
+        let x = vec![-1.7, -0.6, 0.3, 0.8, 1.4, 2.2];
+        check_grad(|t| t[0].tanh() + t[0].atan() + t[0].erf(), x.clone());
+        check_grad(|t| t[0].gelu() + t[0].silu() + t[0].softplus(), x.clone());
+        check_grad(|t| t[0].elu(0.7) + t[0].leaky_relu(0.2) + t[0].abs() * t[0].clamp(-1.0, 1.0), x.clone());
+        check_grad(|t| t[0].acos(), vec![-0.9, -0.4, 0.0, 0.5, 0.8]);
+        check_grad(|t| t[0].acosh(), vec![1.2, 1.6, 2.5, 4.0]);
+    }
+
+    #[test]
+    fn elw_grad_saturated () {
+        // large inputs overflow exp in the forward; the gradients must still be finite
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::tensor(vec![-200.0, -90.0, 90.0, 200.0], vec![4]);
+        let y = (x.tanh() + x.silu() + x.softplus() + x.elu(1.0) + x.gelu()).unsqueeze(0).sum(1);
+        y.forward();
+        y.backward();
+        autodiff::execute();
+
+        let g = x.grad().get();
+        assert!(g.data.iter().all(|v| v.is_finite()), "{:?}", g.data);
+        // tanh' + silu' + softplus' + elu' + gelu' at +-inf
+        for (v, e) in g.data.iter().zip([0.0, 0.0, 4.0, 4.0]) {
+            assert!((v - e).abs() < 1e-5, "{:?}", g.data);
+        }
+    }
+}
@@ -1,11 +1,17 @@
 #[cfg(test)]
 
 mod tests {
-    use crate::{autodiff, devices::CLDeviceType, Tensor};
+    use crate::{autodiff, tests::harness::use_device, Tensor};
 
     #[test]
-    fn eq () {
-        autodiff::set_device(autodiff::devices::OpenCL::new(CLDeviceType::ALL));
+    fn eq () { eq_on(false) }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn eq_opencl () { eq_on(true) }
+
+    fn eq_on (opencl: bool) {
+        use_device(opencl);
 
         // ============ Equality test ============ 
         let x = autodiff::tensor(
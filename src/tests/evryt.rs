@@ -3,12 +3,18 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::{autodiff, devices::CLDeviceType};
+    use crate::{autodiff, tests::harness::use_device};
 
     #[ignore]
     #[test]
-    fn everything () {
-        autodiff::set_device(autodiff::devices::OpenCL::new(CLDeviceType::ALL));
+    fn everything () { everything_on(false) }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn everything_opencl () { everything_on(true) }
+
+    fn everything_on (opencl: bool) {
+        use_device(opencl);
 
         let x = autodiff::tensor(
             vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], 
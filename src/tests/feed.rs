@@ -0,0 +1,62 @@
+// feeding placeholders between executions, without rebuilding the IR
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, ir_b_proc, IRCmds};
+
+    #[test]
+    fn feed () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        let x = autodiff::placeholder(vec![2, 2]);
+        let w = autodiff::tensor(vec![1.0, -1.0, 2.0, 0.5], vec![2, 2]);
+
+        let res = autodiff::dot(x.clone(), w.clone()).sum(1).sum(0);
+        res.forward();
+        res.backward();
+        res.val().unwrap().keep();
+
+        let batches = vec![
+            (vec![1.0, 2.0, 3.0, 4.0], 15.0, vec![4.0, 4.0, 6.0, 6.0]),
+            (vec![0.0, -1.0, 2.0, 0.5], -1.25, vec![2.0, 2.0, -0.5, -0.5]),
+        ];
+
+        for (data, res_val, w_grad) in batches {
+            autodiff::feed(&x, data);
+            autodiff::execute();
+
+            assert_eq!(*res.val().unwrap().get().data, vec![res_val]);
+            assert_eq!(*w.grad().get().data, w_grad);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "# of values doesn't match placeholder size when feeding")]
+    fn feed_wrong_size () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        let x = autodiff::placeholder(vec![2, 2]);
+        autodiff::feed(&x, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn feed_extend () {
+        // ops built after an execution go before its EX, so the next execution runs them (with a single EX at the end)
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        let x = autodiff::placeholder(vec![2]);
+        let y = x.clone() * 2.0;
+        y.forward();
+        y.val().unwrap().keep();
+        autodiff::feed(&x, vec![1.0, 2.0]);
+        autodiff::execute();
+
+        let z = y.clone() + 1.0;
+        z.forward();
+        z.val().unwrap().keep();
+        autodiff::feed(&x, vec![3.0, 4.0]);
+        autodiff::execute();
+
+        assert_eq!(ir_b_proc().main.iter().filter(|c| **c == IRCmds::EX).count(), 1);
+        assert_eq!(*z.val().unwrap().get().data, vec![7.0, 9.0]);
+    }
+}
@@ -0,0 +1,67 @@
+// kernel fusion + the to_kernel flags. Kernels reading a buffer written within the same fused kernel at anything other than #global aren't fused (kernel::fusion::hazard)
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, devices::cpu::Reference, ir_b_proc, kernel::{kernel_decl::Kernels, to_kernel::to_kernel}, tests::harness::{diff_check, OptFlags}};
+
+    fn x (r: usize, c: usize) -> autodiff::Tensor {
+        autodiff::tensor((0..r * c).map(|v| v as f32 * 0.5 - 2.0).collect(), vec![r, c])
+    }
+
+    #[test]
+    fn fusion_elw_transpose () {
+        diff_check(|| {
+            let y = x(3, 3) * 2.0;
+            let res = y.t() + 1.0;
+            res.forward();
+            vec![res.val().unwrap()]
+        }, 1e-5);
+    }
+
+    #[test]
+    fn fusion_dp_transpose () {
+        diff_check(|| {
+            let d = autodiff::dot(x(3, 2), x(2, 3));
+            let res = d.t() * 3.0;
+            res.forward();
+            vec![res.val().unwrap()]
+        }, 1e-5);
+    }
+
+    #[test]
+    fn fusion_rd_transpose () {
+        diff_check(|| {
+            let r = x(4, 5).sum(1);
+            let res = r.view(vec![2, 2]).t() * 2.0;
+            res.forward();
+            vec![res.val().unwrap()]
+        }, 1e-5);
+    }
+
+    // # of fused kernels when lowering a chain of elementwise ops
+    fn fused_kernels () -> usize {
+        autodiff::set_device(Reference::new());
+        let res = ((x(2, 3) * 2.0).sin() + 1.0).exp();
+        res.forward();
+        res.val().unwrap().keep();
+
+        let (kernel_proc, _) = to_kernel(&Reference::new(), &ir_b_proc());
+        kernel_proc.kernels.iter().filter(|k| matches!(k, Kernels::ElwExpr { .. })).count()
+    }
+
+    #[test]
+    fn fusion_kopt_disabled () {
+        assert_eq!(fused_kernels(), 1);
+        let _flags = OptFlags::set(false);
+        assert_eq!(fused_kernels(), 0, "KOPT=0 must skip fusion");
+    }
+
+    #[test]
+    fn fusion_kept_view () {
+        // a kept data manipulation result is made contigious, so it can be read back like any other value
+        diff_check(|| {
+            let res = x(2, 3).t();
+            res.forward();
+            vec![res.val().unwrap()]
+        }, 1e-5);
+    }
+}
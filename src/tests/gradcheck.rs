@@ -0,0 +1,92 @@
+// numerical gradient check of every node's backward
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, Tensor};
+
+    const EPS: f32 = 1e-2;
+    const TOL: f32 = 1e-2;
+
+    fn x () -> (Vec<f32>, Vec<usize>) {
+        (vec![0.5, -1.2, 0.8, 1.5, -0.3, 0.9], vec![2, 3])
+    }
+
+    fn y () -> (Vec<f32>, Vec<usize>) {
+        (vec![1.1, 0.4, -0.7, 0.2, 0.6, -1.3], vec![2, 3])
+    }
+
+    fn pos () -> (Vec<f32>, Vec<usize>) {
+        (vec![0.5, 1.2, 0.8, 1.5, 0.3, 0.9], vec![2, 3])
+    }
+
+    fn check<F: Fn(&[Tensor]) -> Tensor> (f: F, inputs: Vec<(Vec<f32>, Vec<usize>)>) {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        autodiff::gradcheck(f, inputs, EPS, TOL).expect("Gradient check failed");
+    }
+
+    #[test]
+    fn gradcheck_func () {
+        check(|t| t[0].exp2() + t[0].sin() + t[0].cos() + t[0].exp(), vec![x()]);
+        check(|t| t[0].log2() + t[0].ln() + t[0].recip() + t[0].sqrt(), vec![pos()]);
+        check(|t| t[0].pow2() + t[0].pow3() + t[0].powf(1.5), vec![pos()]);
+    }
+
+    #[test]
+    fn gradcheck_ops () {
+        check(|t| t[0].clone() * t[1].clone() + t[0].clone() - t[1].clone(), vec![x(), y()]);
+        check(|t| t[0].clone() / t[1].clone() - t[0].clone() * 2.0 + 1.0, vec![x(), pos()]);
+        check(|t| -(t[0].clone() * t[0].clone()) / 3.0 - 0.5, vec![x()]);
+    }
+
+    #[test]
+    fn gradcheck_reduce () {
+        check(|t| t[0].sum(1).sin(), vec![x()]);
+        check(|t| t[0].sum(0).sin(), vec![x()]);
+        check(|t| t[0].mean(1) * t[0].var(1, 1), vec![x()]);
+    }
+
+    #[test]
+    fn gradcheck_index () {
+        check(|t| t[0].i(1, 0) * t[0].i(0, 0), vec![x()]);
+        check(|t| t[0].i(0, 1) * t[0].i(2, 1).sin(), vec![x()]);
+        check(|t| t[0].i(1, 1).exp(), vec![x()]);
+        check(|t| t[0].r(1..3, 1).sin(), vec![x()]);
+    }
+
+    #[test]
+    fn gradcheck_data () {
+        check(|t| autodiff::concat(vec![t[0].clone(), t[1].sin()], 0).sin(), vec![x(), y()]);
+        check(|t| autodiff::concat(vec![t[0].clone(), t[1].clone(), t[0].clone()], 1).sin(), vec![x(), y()]);
+        check(|t| t[0].t().sin() * t[1].t(), vec![x(), y()]);
+        check(|t| t[0].view(vec![3, 2]).sin() * t[1].view(vec![3, -1]), vec![x(), y()]);
+        check(|t| t[0].permute(&vec![1, 0]).unsqueeze(0).sin().squeeze(0), vec![x()]);
+    }
+
+    #[test]
+    fn gradcheck_broadcast () {
+        check(|t| t[0].clone() * t[1].clone(), vec![x(), (vec![0.3, -0.8, 1.1], vec![1, 3])]);
+        check(|t| t[0].unsqueeze(1).broadcast(1, 4).sin(), vec![(vec![0.3, -0.8, 1.1], vec![3])]);
+    }
+
+    #[test]
+    fn gradcheck_dot () {
+        check(
+            |t| autodiff::dot(t[0].clone(), t[1].t()).sin(),
+            vec![x(), y()]
+        );
+        check(
+            |t| autodiff::dot(t[0].t(), t[1].clone()) * 0.5,
+            vec![x(), y()]
+        );
+    }
+
+    #[test]
+    fn gradcheck_mismatch () {
+        // relu has a kink at 0: the central difference there is 0.5, the backward gives 0 or 1
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let mismatches = autodiff::gradcheck(|t| t[0].relu(), vec![(vec![0.0, 1.0, -1.0], vec![3])], EPS, TOL).unwrap_err();
+
+        assert_eq!(mismatches.len(), 1, "{:?}", mismatches);
+        assert_eq!((mismatches[0].input, mismatches[0].index), (0, 0));
+        assert!((mismatches[0].numerical - 0.5).abs() < 1e-3, "{:?}", mismatches);
+    }
+}
@@ -1,15 +1,21 @@
 // gradient calculation, accumulation, and the add equal operation
 #[cfg(test)]
 mod tests {
-    use crate::{autodiff, devices::CLDeviceType, Tensor};
+    use crate::{autodiff, tests::harness::use_device, Tensor};
     
     fn f (a: &Tensor, b: &Tensor) -> Tensor {
         a.clone() * b.clone()
     }
     
     #[test]
-    fn grd () {
-        autodiff::set_device(autodiff::devices::OpenCL::new(CLDeviceType::ALL));
+    fn grd () { grd_on(false) }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn grd_opencl () { grd_on(true) }
+
+    fn grd_on (opencl: bool) {
+        use_device(opencl);
 
         autodiff::add_heading("Declaring tensors");
         let mut a = autodiff::tensor(vec![3.0, 2.0, 1.0, 3.0], vec![2, 2]);
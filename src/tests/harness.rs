@@ -0,0 +1,160 @@
+// helpers shared between the tests
+use crate::{autodiff, devices::{cpu::Reference, CLDeviceType, OpenCL}, ir::{interp::interp, optimize::ir_optimize}, ir_b_add, ir_b_device_callback, ir_b_execute, ir_b_proc, IRCmds, IRProcedure, Tensor, Value, ValueData};
+
+// sets the device of the test; the OpenCL variants are #[ignore]d, since they need a driver (+ a device)
+pub fn use_device (opencl: bool) {
+    if opencl { autodiff::set_device(OpenCL::new(CLDeviceType::ALL)); }
+    else { autodiff::set_device(Reference::new()); }
+}
+
+// builds + runs f on the current device
+fn run_values<F: Fn() -> Vec<Value>> (f: &F) -> Vec<ValueData> {
+    let vals = f();
+    for v in vals.iter() { v.keep(); }
+    autodiff::execute();
+    vals.iter().map(|v| v.get()).collect()
+}
+
+/*
+Builds + runs the program on the reference device and on OpenCL; every value returned by f must agree within tol (relative to the magnitude)
+Needs an OpenCL device, so the tests calling it are #[ignore]d. f is called twice, it must build the exact same graph each time
+*/
+pub fn cmp_devices<F: Fn() -> Vec<Value>> (f: F, tol: f32) {
+    use_device(false);
+    let reference = run_values(&f);
+    use_device(true);
+    let opencl = run_values(&f);
+
+    assert_close(&reference, &opencl, tol, "reference", "opencl");
+}
+
+pub fn assert_close (a: &[ValueData], b: &[ValueData], tol: f32, a_name: &str, b_name: &str) {
+    assert_eq!(a.len(), b.len(), "# of values mismatch between {} and {}", a_name, b_name);
+    for (i, (u, o)) in a.iter().zip(b.iter()).enumerate() {
+        assert_eq!(u.is_none, o.is_none, "Value {} ({}) exists in only one of {} and {}", i, u.id, a_name, b_name);
+        assert_eq!(u.dim, o.dim, "Value {} ({}) dim mismatch between {} and {}", i, u.id, a_name, b_name);
+        assert_eq!(u.data.len(), o.data.len(), "Value {} ({}) size mismatch between {} and {}", i, u.id, a_name, b_name);
+        for (j, (x, y)) in u.data.iter().zip(o.data.iter()).enumerate() {
+            assert!(
+                is_close(*x, *y, tol),
+                "Value {} ({}) mismatch at {}: {} {} vs {} {}", i, u.id, j, a_name, x, b_name, y
+            );
+        }
+    }
+}
+
+// same as assert_close, over host data
+pub fn assert_close_data (a: &[f32], b: &[f32], tol: f32) {
+    assert_eq!(a.len(), b.len(), "Size mismatch: {:?} vs {:?}", a, b);
+    for (x, y) in a.iter().zip(b.iter()) {
+        assert!(is_close(*x, *y, tol), "{:?} vs {:?}", a, b);
+    }
+}
+
+pub fn is_close (a: f32, b: f32, tol: f32) -> bool {
+    if a.is_nan() || b.is_nan() { return a.is_nan() && b.is_nan(); }
+    if a == b { return true; } // covers inf
+    (a - b).abs() <= tol * 1.0_f32.max(a.abs()).max(b.abs())
+}
+
+// sets IROPT and KOPT; makes sure the opt flags are reset, even if the program panics
+pub struct OptFlags;
+
+impl OptFlags {
+    pub fn set (enabled: bool) -> OptFlags {
+        let val = if enabled { "1" } else { "0" };
+        std::env::set_var("IROPT", val);
+        std::env::set_var("KOPT", val);
+        OptFlags
+    }
+}
+
+impl Drop for OptFlags {
+    fn drop (&mut self) {
+        std::env::remove_var("IROPT");
+        std::env::remove_var("KOPT");
+    }
+}
+
+// returns values from the device and values from interpreting the IR directly (before any optimization)
+fn run_opt<F: Fn() -> Vec<Value>> (f: &F, opt: bool) -> (Vec<ValueData>, Vec<ValueData>) {
+    let _flags = OptFlags::set(opt);
+    autodiff::set_device(Reference::new());
+
+    let vals = f();
+    for v in vals.iter() { v.keep(); }
+
+    let it = interp(&ir_b_proc());
+    autodiff::execute();
+
+    (
+        vals.iter().map(|v| v.get()).collect(),
+        vals.iter().map(|v| it.get_tensor(v.id)).collect()
+    )
+}
+
+/*
+Builds + runs the program once unoptimized and once optimized; every value returned by f must agree within tol (relative to the magnitude)
+Both are also checked against the IR interpreter, so a mismatch tells which layer is at fault:
+    interp vs unoptimized --> kernel lowering (to_instr, trackers, indexing)
+    unoptimized vs optimized --> ir_optimize or the memory/fusion passes in to_kernel
+f is called twice. It must build the exact same graph each time (ex: use a seeded rng)
+*/
+pub fn diff_check<F: Fn() -> Vec<Value>> (f: F, tol: f32) {
+    diff_check_named(f, tol, "");
+}
+
+// same as diff_check; name (ex: the seed of a random program) is added to the failure messages
+pub fn diff_check_named<F: Fn() -> Vec<Value>> (f: F, tol: f32, name: &str) {
+    let (unopt, it) = run_opt(&f, false);
+    let (opt, _) = run_opt(&f, true);
+
+    let label = |l: &str| if name.is_empty() { l.to_string() } else { format!("{} ({})", l, name) };
+    assert_close(&it, &unopt, tol, &label("interp"), &label("unoptimized"));
+    assert_close(&unopt, &opt, tol, &label("unoptimized"), &label("optimized"));
+}
+
+// # of commands matching f, including nested procedures
+pub fn count<F: Fn(&IRCmds) -> bool> (proc: &IRProcedure, f: F) -> usize {
+    let mut proc = proc.clone();
+    let mut n = 0;
+    proc.apply(&mut |p| { n += p.iter().filter(|c| f(c)).count(); });
+    n
+}
+
+// same values before ir_optimize, after it and on the device; returns the optimized procedure (for the IR opt tests)
+pub fn check_same (ts: &[&Tensor]) -> IRProcedure {
+    for t in ts { t.forward(); t.val().unwrap().keep(); }
+
+    ir_b_add(IRCmds::EX);
+    ir_b_device_callback();
+    let before = interp(&ir_b_proc());
+    ir_optimize();
+    let proc = ir_b_proc();
+    let after = interp(&proc);
+    ir_b_execute(false);
+
+    for t in ts {
+        let v = t.val().unwrap();
+        let b = before.get_tensor(v.id).round(5);
+        let a = after.get_tensor(v.id).round(5);
+        let d = v.get().round(5);
+        assert_eq!((&b.dim, &b.data), (&a.dim, &a.data), "changed after ir opt");
+        assert_eq!((&b.dim, &b.data), (&d.dim, &d.data), "differs on device");
+    }
+    proc
+}
+
+// the device gives the same value as interpreting the IR (rounded to 4 decimals)
+pub fn check_interp (t: &Tensor) {
+    t.forward();
+    t.val().unwrap().keep();
+
+    ir_b_add(IRCmds::EX);
+    ir_b_device_callback();
+    let expected = interp(&ir_b_proc()).get_tensor(t.val().unwrap().id).round(4);
+    ir_b_execute(false);
+
+    let v = t.val().unwrap().get().round(4);
+    assert_eq!((v.dim, v.data), (expected.dim, expected.data));
+}
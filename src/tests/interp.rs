@@ -0,0 +1,79 @@
+// HLIR interpreter: same program before ir_optimize, after ir_optimize, and on the device
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{autodiff, ir::{interp::interp, optimize::ir_optimize}, ir_b_add, ir_b_device_callback, ir_b_execute, ir_b_proc, IRCmds, IRProcedure, VarId};
+
+    #[test]
+    fn interp_opt () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        let x = autodiff::tensor(
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 
+            vec![2, 3]
+        );
+        let w = autodiff::tensor(
+            vec![0.5, -1.0, 2.0, 1.5, -0.5, 1.0], 
+            vec![3, 2]
+        );
+
+        let h = autodiff::dot(x.clone(), w.clone());                    // (2, 2)
+        let c = autodiff::concat(vec![h.clone(), w.t()], 1);            // (2, 5)
+        let s = (c.t().sin() * 2.0).sum(1) + x.i(1, 0).sum(0);          // (5,)
+        let res = (s.clone() * s).sqrt().sum(0);
+
+        res.forward();
+        res.backward();
+
+        res.val().unwrap().keep();
+
+        ir_b_add(IRCmds::EX);
+        ir_b_device_callback();
+        let before = interp(&ir_b_proc());
+        ir_optimize();
+        let after = interp(&ir_b_proc());
+        ir_b_execute(false);
+
+        let vals = vec![
+            ("res", res.val().unwrap()), 
+            ("x grad", x.grad()), 
+            ("w grad", w.grad())
+        ];
+
+        for (name, v) in vals {
+            let b = before.get_tensor(v.id).round(4);
+            let a = after.get_tensor(v.id).round(4);
+            let d = v.get().round(4);
+            assert_eq!(b.dim, a.dim, "{} dim changed after ir opt", name);
+            assert_eq!(*b.data, *a.data, "{} changed after ir opt", name);
+            assert_eq!(b.dim, d.dim, "{} dim differs on device", name);
+            assert_eq!(*b.data, *d.data, "{} differs on device", name);
+        }
+
+        // checked against finite differences
+        let x_grad = x.grad().get().round(4);
+        assert_eq!(x_grad.dim, vec![2, 3]);
+        assert_eq!(*x_grad.data, vec![
+            -1.5573, -3.109,  1.5573, 
+             6.0832, -1.636,  3.9168
+        ]);
+    }
+
+    #[test]
+    fn interp_op_eq_dim () {
+        // s += o.view(..): s keeps its own dim, so the sum reduces over s's last dim
+        let (s, o, v, res) = (VarId(0), VarId(1), VarId(2), VarId(3));
+        let mut proc = IRProcedure::new("main".to_string());
+        proc.push(IRCmds::CreateConstant { contents: 1.0, id: s, dim: vec![2, 3] });
+        proc.push(IRCmds::CreateMat { contents: Arc::new((0..6).map(|x| x as f32).collect()), dim: vec![6], id: o });
+        proc.push(IRCmds::View { a: o, target_dim: vec![3, 2], res: v });
+        proc.push(IRCmds::ElwAddEq { s, o: v });
+        proc.push(IRCmds::Sum { a: s, res });
+
+        let it = interp(&proc);
+        assert_eq!(it.get_tensor(s).dim, vec![2, 3]);
+        let r = it.get_tensor(res);
+        assert_eq!((r.dim, r.data.to_vec()), (vec![2], vec![6.0, 15.0]));
+    }
+}
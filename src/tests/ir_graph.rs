@@ -0,0 +1,126 @@
+// graph form of the HLIR (ir::graph)
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, ir::{graph::to_graph, interp::interp, opts::{dep_opt, track_var_changed}}, ir_b_add, ir_b_device_callback, ir_b_proc, IRCmds, IRProcedure, VarId};
+
+    const A: VarId = VarId(0);
+    const S: VarId = VarId(1);
+    const COND: VarId = VarId(2);
+    const T: VarId = VarId(3);
+    const U: VarId = VarId(4);
+    const DEAD: VarId = VarId(5);
+    const DEAD_2: VarId = VarId(6);
+
+    fn c (id: VarId) -> IRCmds { IRCmds::CreateConstant { contents: 2.0, id, dim: vec![3] } }
+    fn add (a: VarId, b: VarId, res: VarId) -> IRCmds { IRCmds::ElwAdd { a, b, res } }
+    fn sin (a: VarId, res: VarId) -> IRCmds { IRCmds::Sin { a, res } }
+
+    // a, s, cond; while cond { t = a + s; s += t; cond = cond + a }; u = sin(s); dead = sin(t)
+    fn program () -> IRProcedure {
+        let mut block = IRProcedure::new("b".to_string());
+        block.push(add(A, S, T));
+        block.push(IRCmds::ElwAddEq { s: S, o: T });
+        block.push(add(COND, A, COND));
+
+        let mut main = IRProcedure::new("main".to_string());
+        main.push(c(A));
+        main.push(c(S));
+        main.push(c(COND));
+        main.push(IRCmds::While { conditional_var: COND, block });
+        main.push(sin(S, U));
+        main.push(sin(T, DEAD));
+        main.push(sin(DEAD, DEAD_2));
+        main
+    }
+
+    #[test]
+    fn graph_edges () {
+        let g = to_graph(&program(), &[S, COND]);
+
+        // a, s, cond, while, (t, s +=, cond), u, dead, dead_2
+        assert_eq!(g.nodes.len(), 10);
+        assert_eq!(g.regions.len(), 2);
+        assert_eq!(g.regions[1].parent, Some(3));
+        assert_eq!(g.regions[1].nodes, vec![4, 5, 6]);
+        assert_eq!(g.nodes[3].regions, vec![1]);
+        assert!(matches!(&g.nodes[3].cmd, IRCmds::While { block, .. } if block.main.is_empty()));
+
+        assert_eq!(g.nodes[4].deps, vec![Some(0), Some(1)]);  // t = a + s
+        assert_eq!(g.nodes[5].deps, vec![Some(1), Some(4)]);  // s += t
+        assert_eq!(g.nodes[3].deps, vec![Some(2)]);           // while reads cond before the loop
+        assert_eq!(g.nodes[7].deps, vec![Some(5)]);           // sin(s) reads the write inside the loop
+        assert_eq!(g.nodes[9].deps, vec![Some(8)]);
+        assert_eq!(g.nodes[0].users, vec![4, 6]);
+        assert!(g.nodes[9].users.is_empty());
+    }
+
+    #[test]
+    fn graph_remove_unused () {
+        let proc = program();
+        let var_changed = vec![S, COND];
+
+        // dead_2 --> dead in one pass; t is read by s += t
+        let mut g = to_graph(&proc, &var_changed);
+        assert_eq!(g.remove_unused(|r| r == U), 2);
+        let flat = g.flatten();
+        assert_eq!(flat.main.len(), proc.main.len() - 2);
+        assert!(matches!(&flat.main[3], IRCmds::While { block, .. } if block.main.len() == 3));
+
+        // nothing kept: u goes as well, the loop doesn't have a result
+        let mut g = to_graph(&proc, &var_changed);
+        assert_eq!(g.remove_unused(|_| false), 3);
+        assert_eq!(g.flatten().main.len(), proc.main.len() - 3);
+    }
+
+    #[test]
+    fn graph_changed_kept () {
+        // cond = cond + a has no user in the graph (the while only links to the write before the loop)
+        let mut g = to_graph(&program(), &[S, COND]);
+        assert!(g.nodes[6].users.is_empty());
+        g.remove_unused(|_| false);
+        assert!(!g.nodes[6].removed);
+        assert!(matches!(&g.flatten().main[3], IRCmds::While { block, .. } if block.main.len() == 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "changes throughout the program")]
+    fn graph_remove_changed () {
+        let mut g = to_graph(&program(), &[S, COND]);
+        g.remove(6);
+    }
+
+    #[test]
+    fn graph_roundtrip () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let mut y = autodiff::ones(vec![3]);
+        let mut y_two = autodiff::ones(vec![3]);
+        let x = autodiff::tensor(vec![1.0, 2.0, 3.0], vec![3]);
+        autodiff::ir_for(0..3, |i| {
+            let cond = i.less_than(&autodiff::constant(1.0, vec![1]));
+            autodiff::ir_if_else(|| cond, || {
+                y += x.sin();
+                y.forward();
+            }, || {
+                y_two += x.clone() * 2.0;
+                y_two.forward();
+            });
+        });
+        let z = (y.clone() * 3.0 + y_two.clone()).sum(0);
+        z.forward();
+        z.val().unwrap().keep();
+
+        ir_b_add(IRCmds::EX);
+        ir_b_device_callback();
+        let proc = ir_b_proc();
+        assert_eq!(to_graph(&proc, &[]).flatten(), proc);
+
+        // dep_opt on the graph keeps the results; a single pass removes everything
+        let mut opt = proc.clone();
+        let var_changed = track_var_changed(&mut opt);
+        dep_opt(&mut opt, &var_changed);
+        assert_eq!(dep_opt(&mut opt, &var_changed), 0);
+
+        let id = z.val().unwrap().id;
+        assert_eq!(interp(&proc).get_tensor(id).data, interp(&opt).get_tensor(id).data);
+    }
+}
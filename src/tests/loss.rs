@@ -0,0 +1,120 @@
+// loss functions against host computations, and their gradients
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, nn::loss::{self, Reduction}, Tensor, Value};
+
+    const X: [f32; 6] = [0.5, -1.2, 0.8, 1.5, -0.3, 0.9];
+    const Y: [f32; 6] = [1.1, 0.4, -0.7, 0.2, 0.6, -1.3];
+    const P: [f32; 6] = [0.2, 0.7, 0.9, 0.4, 0.5, 0.1];
+    const T: [f32; 6] = [0.0, 1.0, 1.0, 0.0, 1.0, 0.0];
+
+    fn eval (l: Tensor) -> f32 {
+        l.forward();
+        l.val().unwrap().keep();
+        autodiff::execute();
+
+        let v = l.val().unwrap().get();
+        assert_eq!(v.dim, vec![1]);
+        v.data[0]
+    }
+
+    fn assert_close (a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{} vs {}", a, b);
+    }
+
+    fn constant (data: &[f32]) -> Tensor {
+        Value::new(data.to_vec(), vec![2, 3]).to_node()
+    }
+
+    fn check<F: Fn(&[Tensor]) -> Tensor> (f: F, inputs: Vec<(Vec<f32>, Vec<usize>)>) {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        autodiff::gradcheck(f, inputs, 1e-2, 1e-2).expect("Gradient check failed");
+    }
+
+    #[test]
+    fn mse () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let sum: f32 = X.iter().zip(Y.iter()).map(|(x, y)| (x - y) * (x - y)).sum();
+
+        let l = loss::mse_loss(constant(&X), constant(&Y), Reduction::Sum);
+        assert_close(eval(l), sum);
+
+        let l = loss::mse_loss(constant(&X), constant(&Y), Reduction::Mean);
+        assert_close(eval(l), sum / 6.0);
+
+        check(|t| loss::mse_loss(t[0].clone(), constant(&Y), Reduction::Mean), vec![(X.to_vec(), vec![2, 3])]);
+    }
+
+    #[test]
+    fn cross_entropy () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let classes = [2, 0];
+
+        // -log(softmax(x)[class]) per row
+        let expected: f32 = X.chunks(3).zip(classes.iter()).map(|(row, &c)| {
+            let lse = row.iter().map(|v| v.exp()).sum::<f32>().ln();
+            lse - row[c]
+        }).sum();
+
+        let l = loss::cross_entropy(constant(&X), loss::one_hot(&classes, 3), Reduction::Mean);
+        assert_close(eval(l), expected / 2.0);
+
+        // large logits don't overflow
+        let big: Vec<f32> = X.iter().map(|v| v + 1000.0).collect();
+        let l = loss::cross_entropy(constant(&big), loss::one_hot(&classes, 3), Reduction::Sum);
+        assert_close(eval(l), expected);
+
+        check(|t| loss::cross_entropy(t[0].clone(), loss::one_hot(&classes, 3), Reduction::Mean), vec![(X.to_vec(), vec![2, 3])]);
+    }
+
+    #[test]
+    fn nll () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let log_probs: Vec<f32> = P.iter().map(|p| p.ln()).collect();
+
+        let l = loss::nll_loss(constant(&log_probs), loss::one_hot(&[1, 0], 3), Reduction::Sum);
+        assert_close(eval(l), -(0.7_f32.ln() + 0.4_f32.ln()));
+    }
+
+    #[test]
+    fn class_index_target () {
+        // [N] class indices give the same loss as their one-hot targets
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let log_probs: Vec<f32> = P.iter().map(|p| p.ln()).collect();
+        let indices = || Value::new(vec![1.0, 0.0], vec![2]).to_node();
+
+        let l = loss::nll_loss(constant(&log_probs), indices(), Reduction::Sum);
+        assert_close(eval(l), -(0.7_f32.ln() + 0.4_f32.ln()));
+
+        let expected = eval(loss::cross_entropy(constant(&X), loss::one_hot(&[1, 0], 3), Reduction::Mean));
+        assert_close(eval(loss::cross_entropy(constant(&X), indices(), Reduction::Mean)), expected);
+
+        check(|t| loss::cross_entropy(t[0].clone(), indices(), Reduction::Mean), vec![(X.to_vec(), vec![2, 3])]);
+    }
+
+    #[test]
+    fn bce () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let expected: f32 = P.iter().zip(T.iter()).map(|(p, t)| -(t * p.ln() + (1.0 - t) * (1.0 - p).ln())).sum();
+
+        let l = loss::binary_cross_entropy(constant(&P), constant(&T), Reduction::Mean);
+        assert_close(eval(l), expected / 6.0);
+
+        check(|t| loss::binary_cross_entropy(t[0].clone(), constant(&T), Reduction::Sum), vec![(P.to_vec(), vec![2, 3])]);
+    }
+
+    #[test]
+    fn huber () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let delta = 1.0;
+        let expected: f32 = X.iter().zip(Y.iter()).map(|(x, y)| {
+            let d = (x - y).abs();
+            if d < delta { 0.5 * d * d } else { delta * (d - 0.5 * delta) }
+        }).sum();
+
+        let l = loss::huber_loss(constant(&X), constant(&Y), delta, Reduction::Sum);
+        assert_close(eval(l), expected);
+
+        check(|t| loss::huber_loss(t[0].clone(), constant(&Y), delta, Reduction::Mean), vec![(X.to_vec(), vec![2, 3])]);
+    }
+}
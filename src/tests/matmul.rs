@@ -0,0 +1,104 @@
+// batched matmul with broadcasting against host computations
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, Tensor};
+
+    // deterministic data of the given dim
+    fn data (dim: &[usize], seed: f32) -> (Vec<f32>, Vec<usize>) {
+        let n: usize = dim.iter().product();
+        ((0..n).map(|i| ((i as f32 + seed) * 0.37).sin()).collect(), dim.to_vec())
+    }
+
+    // (batch, m, k) x (batch, k, n); a batch of 1 is shared
+    fn host_bmm (a: &[f32], b: &[f32], (ab, bb): (usize, usize), (m, k, n): (usize, usize, usize)) -> Vec<f32> {
+        let batch = ab.max(bb);
+        let mut res = vec![0.0; batch * m * n];
+        for bt in 0..batch {
+            let a_off = if ab == 1 { 0 } else { bt * m * k };
+            let b_off = if bb == 1 { 0 } else { bt * k * n };
+            for i in 0..m {
+                for j in 0..n {
+                    res[bt*m*n + i*n + j] = (0..k).map(|l| a[a_off + i*k + l] * b[b_off + l*n + j]).sum();
+                }
+            }
+        }
+        res
+    }
+
+    fn eval (t: Tensor) -> (Vec<usize>, Vec<f32>) {
+        t.forward();
+        t.val().unwrap().keep();
+        autodiff::execute();
+
+        let v = t.val().unwrap().get().round(4);
+        (v.dim.clone(), v.data.to_vec())
+    }
+
+    fn round (v: Vec<f32>) -> Vec<f32> {
+        v.iter().map(|x| (x * 10000.0).round() / 10000.0).collect()
+    }
+
+    fn check_matmul (a_dim: &[usize], b_dim: &[usize], res_dim: &[usize], batches: (usize, usize), mkn: (usize, usize, usize)) {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let (a, _) = data(a_dim, 0.0);
+        let (b, _) = data(b_dim, 5.0);
+
+        let res = autodiff::matmul(autodiff::tensor(a.clone(), a_dim.to_vec()), autodiff::tensor(b.clone(), b_dim.to_vec()));
+        assert_eq!(eval(res), (res_dim.to_vec(), round(host_bmm(&a, &b, batches, mkn))));
+    }
+
+    #[test]
+    fn bmm () {
+        check_matmul(&[3, 2, 4], &[3, 4, 5], &[3, 2, 5], (3, 3), (2, 4, 5));
+        check_matmul(&[2, 3], &[3, 4], &[2, 4], (1, 1), (2, 3, 4));
+    }
+
+    #[test]
+    fn bmm_shared () {
+        // 2-dim side (or batch of 1) is shared across the batch
+        check_matmul(&[3, 2, 4], &[4, 5], &[3, 2, 5], (3, 1), (2, 4, 5));
+        check_matmul(&[2, 4], &[3, 4, 5], &[3, 2, 5], (1, 3), (2, 4, 5));
+        check_matmul(&[1, 2, 4], &[3, 4, 5], &[3, 2, 5], (1, 3), (2, 4, 5));
+    }
+
+    #[test]
+    fn bmm_broadcast () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let (a, _) = data(&[2, 1, 2, 3], 0.0);
+        let (b, _) = data(&[3, 3, 4], 5.0);
+
+        let res = autodiff::matmul(autodiff::tensor(a.clone(), vec![2, 1, 2, 3]), autodiff::tensor(b.clone(), vec![3, 3, 4]));
+
+        // (2, 1) x (3) batch --> (2, 3)
+        let mut expected = vec![];
+        for i in 0..2 {
+            expected.extend(host_bmm(&a[i*6..(i+1)*6], &b, (1, 3), (2, 3, 4)));
+        }
+        assert_eq!(eval(res), (vec![2, 3, 2, 4], round(expected)));
+    }
+
+    #[test]
+    fn bmm_fused () {
+        // dot product followed by elementwise ops is fused into a single kernel
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let (a, _) = data(&[3, 2, 4], 0.0);
+        let (b, _) = data(&[4, 5], 5.0);
+
+        let res = autodiff::matmul(autodiff::tensor(a.clone(), vec![3, 2, 4]), autodiff::tensor(b.clone(), vec![4, 5])).sin() * 2.0;
+        let expected = host_bmm(&a, &b, (3, 1), (2, 4, 5)).iter().map(|v| v.sin() * 2.0).collect();
+        assert_eq!(eval(res), (vec![3, 2, 5], round(expected)));
+    }
+
+    #[test]
+    fn bmm_grad () {
+        let check = |f: &dyn Fn(&[Tensor]) -> Tensor, inputs: Vec<(Vec<f32>, Vec<usize>)>| {
+            autodiff::set_device(autodiff::devices::cpu::Reference::new());
+            autodiff::gradcheck(f, inputs, 1e-2, 1e-2).expect("Gradient check failed");
+        };
+
+        check(&|t| autodiff::matmul(t[0].clone(), t[1].clone()).sin(), vec![data(&[2, 2, 3], 0.0), data(&[2, 3, 2], 5.0)]);
+        check(&|t| autodiff::matmul(t[0].clone(), t[1].clone()).sin(), vec![data(&[2, 2, 3], 0.0), data(&[3, 2], 5.0)]);
+        check(&|t| autodiff::matmul(t[0].clone(), t[1].clone()).sin(), vec![data(&[2, 3], 0.0), data(&[2, 3, 2], 5.0)]);
+        check(&|t| autodiff::matmul(t[0].clone(), t[1].clone()).sin(), vec![data(&[2, 1, 2, 3], 0.0), data(&[2, 3, 2], 5.0)]);
+    }
+}
@@ -0,0 +1,39 @@
+// kernel memory optimization (kernel::memory::mem_opt)
+#[cfg(test)]
+mod tests {
+    use crate::autodiff;
+
+    #[test]
+    fn mem_opt_broadcast_dep () {
+        // a is broadcasted inside the add: its buffer (3) can't hold the result (6), even if a isn't read afterwards
+        // (res_size guard; the non global read guard also rejects this one)
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::tensor(vec![1.0, 2.0, 3.0], vec![1, 3]);
+        let y = autodiff::tensor(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3]);
+
+        let a = x * 2.0;
+        let res = (a + y).sin();
+        res.forward();
+        res.val().unwrap().keep();
+        autodiff::execute();
+
+        let expected: Vec<f32> = [3.0, 6.0, 9.0, 6.0, 9.0, 12.0].iter().map(|v: &f32| v.sin()).collect();
+        assert_eq!(res.val().unwrap().get().data.to_vec(), expected);
+    }
+
+    #[test]
+    fn mem_opt_transposed_dep () {
+        // a is read transposed: writing the result into its buffer would overwrite elements that later threads still read
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::tensor((0..9).map(|v| v as f32).collect(), vec![3, 3]);
+
+        let a = x * 2.0;
+        let res = (a.t() * -1.0).sin();
+        res.forward();
+        res.val().unwrap().keep();
+        autodiff::execute();
+
+        let expected: Vec<f32> = [0.0, 3.0, 6.0, 1.0, 4.0, 7.0, 2.0, 5.0, 8.0].iter().map(|v: &f32| (v * -2.0).sin()).collect();
+        assert_eq!(res.val().unwrap().get().data.to_vec(), expected);
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(test)]
+pub mod harness;
 mod ct;
 mod evryt;
 mod nn;
@@ -5,4 +7,31 @@ mod grd;
 mod eq;
 mod ctrl;
 mod norm;
-mod view;
\ No newline at end of file
+mod view;
+mod interp;
+mod diff;
+mod gradcheck;
+mod feed;
+mod compile;
+mod persist;
+mod optim;
+mod loss;
+mod reduce;
+mod softmax;
+mod matmul;
+mod attention;
+mod conv;
+mod pool;
+mod pad;
+mod dropout;
+mod random;
+mod elementwise;
+mod const_fold;
+mod movement_opt;
+mod ir_graph;
+mod repeat_opt;
+mod devices;
+mod mem_opt;
+mod dep;
+mod fusion;
+mod alloc;
@@ -0,0 +1,59 @@
+// canonicalization of view/permute/contigious chains (ir::opts::movement_opt)
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, tests::harness::{check_same, count}, IRCmds, Tensor};
+
+    fn x () -> Tensor {
+        autodiff::tensor((0..24).map(|v| v as f32 * 0.5 - 3.0).collect(), vec![2, 3, 4])
+    }
+
+    #[test]
+    fn movement_reduce () {
+        // permute -> view -> sum -> view -> permute along the last dim of a 2-dim tensor is only the sum
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = x().view(vec![6, 4]);
+        let s = x.sum(-1).sin();
+
+        let proc = check_same(&[&s]);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::Permute { .. })), 0);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::View { .. })), 1); // x's view
+    }
+
+    #[test]
+    fn movement_chains () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = x();
+        let ident_view = x.view(vec![4, 6]).view(vec![24]).view(vec![2, 3, 4]).sin();
+        let ident_perm = x.permute(&vec![1, 2, 0]).permute(&vec![2, 0, 1]).sin();
+        let views = x.view(vec![4, 6]).view(vec![8, 3]).sin();
+        let perms = x.permute(&vec![1, 0, 2]).permute(&vec![0, 2, 1]).sin();
+        let tt = x.view(vec![6, 4]).t().t().sin();
+
+        let proc = check_same(&[&ident_view, &ident_perm, &views, &perms, &tt]);
+        // [8, 3] and [6, 4] are left; the composed permute is the only one
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::View { .. })), 2);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::Permute { p, .. } if *p == vec![1, 2, 0])), 1);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::Permute { .. })), 1);
+    }
+
+    #[test]
+    fn movement_contigious () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = x();
+        let c = x.permute(&vec![2, 0, 1]).contigious().contigious().sin();
+
+        let proc = check_same(&[&c]);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::Contigious { .. })), 1);
+    }
+
+    #[test]
+    fn movement_kept () {
+        // kept variables are materialized under their own id, so they aren't skipped
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = x();
+        let v = x.view(vec![24]);
+        let w = v.view(vec![2, 3, 4]) * 2.0;
+
+        check_same(&[&v, &w]);
+    }
+}
@@ -1,13 +1,17 @@
 #[cfg(test)]
 mod tests {
-    use crate::autodiff::devices::{OpenCL, CLDeviceType};
-    use crate::autodiff;
-    use crate::nn::{self, SeqF, Module};
+    use crate::{autodiff, tests::harness::use_device};
+    use crate::nn::{self, optimizers::Optimizer, SeqF, Module};
     
     #[test]
-    fn nn () {
-        // autodiff::set_device(autodiff::devices::CPU::new());
-        autodiff::set_device(OpenCL::new(CLDeviceType::GPU));
+    fn nn () { nn_on(false) }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn nn_opencl () { nn_on(true) }
+
+    fn nn_on (opencl: bool) {
+        use_device(opencl);
         autodiff::eager_dep_opt();
 
         let l1_w = autodiff::tensor(vec![
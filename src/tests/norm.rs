@@ -1,12 +1,17 @@
 #[cfg(test)]
 mod tests {
-    use crate::devices::CLDeviceType;
-    use crate::{autodiff, LayerNorm, RMS};
+    use crate::{autodiff, tests::harness::use_device, LayerNorm, RMS};
     use crate::nn::module::SeqF;
 
     #[test]
-    pub fn rmsnorm () {
-        autodiff::set_device(autodiff::devices::OpenCL::new(CLDeviceType::ALL));
+    pub fn rmsnorm () { rmsnorm_on(false) }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    pub fn rmsnorm_opencl () { rmsnorm_on(true) }
+
+    fn rmsnorm_on (opencl: bool) {
+        use_device(opencl);
 
         let x = autodiff::tensor(
             vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0],
@@ -29,8 +34,14 @@ mod tests {
     }
 
     #[test]
-    pub fn layernorm () {
-        autodiff::set_device(autodiff::devices::OpenCL::new(CLDeviceType::ALL));
+    pub fn layernorm () { layernorm_on(false) }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    pub fn layernorm_opencl () { layernorm_on(true) }
+
+    fn layernorm_on (opencl: bool) {
+        use_device(opencl);
 
         let x = autodiff::tensor(
             vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 9.0],
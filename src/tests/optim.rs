@@ -0,0 +1,145 @@
+// optimizers, compared against a host implementation
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, nn::{self, optimizers::Optimizer}, tests::harness::assert_close_data, Tensor};
+
+    const W: [f32; 4] = [1.0, -2.0, 0.5, 0.1];
+    const LR: f32 = 0.05;
+
+    // loss = sum(w^3); grad = 3w^2
+    fn loss (w: &Tensor) -> Tensor {
+        (w.clone() * w.clone() * w.clone()).unsqueeze(0).sum(1)
+    }
+
+    fn host_adam (steps: usize, weight_decay: f32) -> Vec<f32> {
+        let (b1, b2, eps) = (0.9_f32, 0.999_f32, 1e-8_f32);
+        let mut w = W.to_vec();
+        let mut m = vec![0.0; w.len()];
+        let mut v = vec![0.0; w.len()];
+
+        for t in 1..=steps {
+            for i in 0..w.len() {
+                let g = 3.0 * w[i] * w[i];
+                w[i] *= 1.0 - LR * weight_decay;
+                m[i] = b1 * m[i] + (1.0 - b1) * g;
+                v[i] = b2 * v[i] + (1.0 - b2) * g * g;
+                let m_hat = m[i] / (1.0 - b1.powi(t as i32));
+                let v_hat = v[i] / (1.0 - b2.powi(t as i32));
+                w[i] -= LR * m_hat / (v_hat.sqrt() + eps);
+            }
+        }
+
+        w
+    }
+
+    #[test]
+    fn adam () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        let w = autodiff::tensor(W.to_vec(), vec![2, 2]);
+        let mut opt = nn::optimizers::Adam(vec![w.clone()], LR);
+
+        autodiff::ir_for(0..3, |_| {
+            let y = loss(&w);
+            opt.zero_grad();
+            y.forward();
+            y.backward();
+            opt.step();
+        });
+        w.val().unwrap().keep();
+
+        // moments and the step counter carry over to the next execution
+        autodiff::execute();
+        assert_close_data(&w.val().unwrap().get().data, &host_adam(3, 0.0), 1e-4);
+        autodiff::execute();
+        assert_close_data(&w.val().unwrap().get().data, &host_adam(6, 0.0), 1e-4);
+    }
+
+    #[test]
+    fn adamw () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        let w = autodiff::tensor(W.to_vec(), vec![4]);
+        let mut opt = nn::optimizers::AdamW(vec![w.clone()], LR, 0.1);
+
+        for _ in 0..2 {
+            let y = loss(&w);
+            opt.zero_grad();
+            y.forward();
+            y.backward();
+            opt.step();
+        }
+        w.val().unwrap().keep();
+
+        autodiff::execute();
+        assert_close_data(&w.val().unwrap().get().data, &host_adam(2, 0.1), 1e-4);
+    }
+
+    #[test]
+    fn param_groups () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        let a = autodiff::tensor(vec![1.0, -2.0], vec![2]);
+        let b = autodiff::tensor(vec![0.5, 3.0], vec![2]);
+        let mut opt = nn::optimizers::SGDWithGroups(vec![
+            nn::optimizers::ParamGroup::new(vec![a.clone()], 0.1, 0.0),
+            nn::optimizers::ParamGroup::new(vec![b.clone()], 0.01, 0.5),
+        ]);
+
+        // grad = 2 * p for both
+        let y = (a.clone() * a.clone() + b.clone() * b.clone()).unsqueeze(0).sum(1);
+        opt.zero_grad();
+        y.forward();
+        y.backward();
+        opt.step();
+
+        a.val().unwrap().keep();
+        b.val().unwrap().keep();
+        autodiff::execute();
+
+        // a -= 0.1 * 2a; b -= 0.01 * (2b + 0.5b)
+        assert_close_data(&a.val().unwrap().get().data, &[0.8, -1.6], 1e-4);
+        assert_close_data(&b.val().unwrap().get().data, &[0.4875, 2.925], 1e-4);
+    }
+
+    // grad of w is always 2, so w ends at 10 - 2 * (sum of the lrs)
+    fn run_scheduler<S: nn::optimizers::LRScheduler> (new_sched: impl Fn() -> S, steps: i32) -> (f32, f32) {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        let mut sched = new_sched();
+        let w = autodiff::tensor(vec![10.0], vec![1]);
+        let mut opt = nn::optimizers::SGD(vec![w.clone()], 1.0);
+
+        autodiff::ir_for(0..steps, |_| {
+            let y = w.clone() * 2.0;
+            opt.zero_grad();
+            y.forward();
+            y.backward();
+
+            sched.step(&mut opt);
+            opt.step();
+        });
+
+        let lr = opt.param_groups()[0].lr.clone();
+        w.val().unwrap().keep();
+        lr.val().unwrap().keep();
+        autodiff::execute();
+
+        (w.val().unwrap().get().data[0], lr.val().unwrap().get().data[0])
+    }
+
+    #[test]
+    fn schedulers () {
+        let lrs = [1.0, 1.0, 0.5, 0.5, 0.25];
+        let (w, lr) = run_scheduler(|| nn::optimizers::StepLR(2, 0.5), 5);
+        assert_close_data(&[w, lr], &[10.0 - 2.0 * lrs.iter().sum::<f32>(), 0.25], 1e-5);
+
+        let lrs: Vec<f32> = (0..4).map(|t| 0.1 + 0.9 * 0.5 * (1.0 + (std::f32::consts::PI * t as f32 / 4.0).cos())).collect();
+        let (w, lr) = run_scheduler(|| nn::optimizers::CosineLR(4, 0.1), 4);
+        assert_close_data(&[w, lr], &[10.0 - 2.0 * lrs.iter().sum::<f32>(), lrs[3]], 1e-5);
+
+        let lrs = [0.25, 0.5, 0.75, 1.0, 1.0];
+        let (w, lr) = run_scheduler(|| nn::optimizers::LinearWarmup(4), 5);
+        assert_close_data(&[w, lr], &[10.0 - 2.0 * lrs.iter().sum::<f32>(), 1.0], 1e-5);
+    }
+}
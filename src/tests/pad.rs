@@ -0,0 +1,136 @@
+// constant, reflect and replicate padding
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, devices::helper::get_inputs_args, kernel_decl::{Expression, Input, Matrix, Output}, tests::harness::{check_interp, cmp_devices}, PadMode, Tensor, VarId};
+
+    fn data (dim: &[usize], seed: f32) -> (Vec<f32>, Vec<usize>) {
+        let n: usize = dim.iter().product();
+        ((0..n).map(|i| ((i as f32 + seed) * 0.37).sin()).collect(), dim.to_vec())
+    }
+
+    fn eval (t: Tensor) -> (Vec<usize>, Vec<f32>) {
+        t.forward();
+        t.val().unwrap().keep();
+        autodiff::execute();
+
+        let v = t.val().unwrap().get();
+        (v.dim.clone(), v.data.to_vec())
+    }
+
+    #[test]
+    fn pad_modes () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::tensor(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3]);
+
+        assert_eq!(eval(x.pad(&[(2, 1)], PadMode::Constant(-1.0))), (vec![2, 6], vec![
+            -1.0, -1.0, 1.0, 2.0, 3.0, -1.0,
+            -1.0, -1.0, 4.0, 5.0, 6.0, -1.0
+        ]));
+        assert_eq!(eval(x.pad(&[(2, 1)], PadMode::Reflect)), (vec![2, 6], vec![
+            3.0, 2.0, 1.0, 2.0, 3.0, 2.0,
+            6.0, 5.0, 4.0, 5.0, 6.0, 5.0
+        ]));
+        assert_eq!(eval(x.pad(&[(2, 1)], PadMode::Replicate)), (vec![2, 6], vec![
+            1.0, 1.0, 1.0, 2.0, 3.0, 3.0,
+            4.0, 4.0, 4.0, 5.0, 6.0, 6.0
+        ]));
+
+        // both dims, then moved around
+        assert_eq!(eval(x.pad(&[(1, 0), (0, 1)], PadMode::Constant(0.0)).t()), (vec![4, 3], vec![
+            0.0, 1.0, 4.0,
+            0.0, 2.0, 5.0,
+            0.0, 3.0, 6.0,
+            0.0, 0.0, 0.0
+        ]));
+        assert_eq!(eval(x.pad(&[(1, 1), (1, 1)], PadMode::Replicate).sum(1)), (vec![4], vec![10.0, 10.0, 25.0, 25.0]));
+    }
+
+    #[test]
+    fn pad_nested () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::tensor(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2]);
+        let y = autodiff::tensor(vec![5.0, 6.0], vec![2, 1]);
+
+        // pads of pads and of a concat
+        let p = x.pad(&[(1, 0)], PadMode::Constant(7.0)).pad(&[(0, 1)], PadMode::Constant(8.0));
+        assert_eq!(eval(p), (vec![2, 4], vec![7.0, 1.0, 2.0, 8.0, 7.0, 3.0, 4.0, 8.0]));
+
+        let c = autodiff::concat(vec![x.clone(), y], 1).pad(&[(1, 1)], PadMode::Reflect);
+        assert_eq!(eval(c), (vec![2, 5], vec![2.0, 1.0, 2.0, 5.0, 2.0, 4.0, 3.0, 4.0, 6.0, 4.0]));
+    }
+
+    #[test]
+    fn pad_interp () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let (x, x_dim) = data(&[2, 3, 4], 0.0);
+        let x = autodiff::tensor(x, x_dim);
+
+        let res = x.pad(&[(1, 2), (2, 1)], PadMode::Reflect).sin()
+            + x.pad(&[(1, 2), (2, 1)], PadMode::Replicate)
+            + x.pad(&[(1, 2), (2, 1)], PadMode::Constant(0.5)).permute(&vec![0, 2, 1]).permute(&vec![0, 2, 1]);
+        check_interp(&res);
+    }
+
+    #[test]
+    fn pad_grad () {
+        let check = |f: &dyn Fn(&[Tensor]) -> Tensor, inputs: Vec<(Vec<f32>, Vec<usize>)>| {
+            autodiff::set_device(autodiff::devices::cpu::Reference::new());
+            autodiff::gradcheck(f, inputs, 1e-2, 1e-2).expect("Gradient check failed");
+        };
+
+        check(&|t| t[0].pad(&[(1, 2), (2, 1)], PadMode::Constant(0.5)).sin(), vec![data(&[3, 4], 0.0)]);
+        check(&|t| t[0].pad(&[(1, 2), (2, 1)], PadMode::Reflect).sin(), vec![data(&[3, 4], 0.0)]);
+        check(&|t| t[0].pad(&[(1, 2), (2, 1)], PadMode::Replicate).sin(), vec![data(&[3, 4], 0.0)]);
+    }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn pad_devices () {
+        // constant pads are selects in the kernel (Input::ConcatMatrix); so are zero_pad/fill_pad and everything built on them
+        cmp_devices(|| {
+            let (d, dim) = data(&[1, 2, 4, 5], 0.0);
+            let x = autodiff::tensor(d, dim);
+            let (d, dim) = data(&[3, 2, 3, 3], 1.0);
+            let w = autodiff::tensor(d, dim);
+
+            let pads = x.pad(&[(1, 2), (2, 0)], PadMode::Constant(-1.5)).sin()
+                + x.pad(&[(1, 2), (2, 0)], PadMode::Reflect)
+                + x.pad(&[(1, 2), (2, 0)], PadMode::Replicate);
+            let conv = x.conv2d(&w, 1, 1, 1, 1);
+            let pool = x.max_pool2d(3, 2, 1, 1);
+            let narrow = x.narrow(-1, 1, 3);
+            let fold = x.unfold(-1, 2, 1, 1).fold(-2, 5, 1, 1);
+
+            let res = pads.sum(-1).sum(-1).sum(-1).sum(-1)
+                + conv.sum(-1).sum(-1).sum(-1).sum(-1)
+                + pool.sum(-1).sum(-1).sum(-1).sum(-1)
+                + narrow.pow2().sum(-1).sum(-1).sum(-1).sum(-1)
+                + fold.sum(-1).sum(-1).sum(-1).sum(-1);
+            res.forward();
+            res.backward();
+
+            vec![pads.val().unwrap(), conv.val().unwrap(), pool.val().unwrap(), fold.val().unwrap(), x.grad(), w.grad()]
+        }, 1e-4);
+    }
+
+    #[test]
+    fn pad_opencl_src () {
+        // a padded by 1 (before) with -1.5, then concatenated with b after 4 elements
+        let mat = |id: u32, access: Expression| Input::Mat { mat: Matrix { id: VarId(id), access } };
+        let padded = Input::ConcatMatrix {
+            id_one: Box::new(mat(0, Expression::make_minus(Expression::make_global(), Expression::make_const(1)))),
+            id_two: Box::new(Input::Constant { val: -1.5 }),
+            conditional: Expression::make_less_than(Expression::make_global(), Expression::make_const(1))
+        };
+        let inp = Input::ConcatMatrix {
+            id_one: Box::new(padded),
+            id_two: Box::new(mat(1, Expression::make_global())),
+            conditional: Expression::make_more_than(Expression::make_global(), Expression::make_const(3))
+        };
+
+        assert_eq!(inp.to_opencl(), "((_global_id > 3) ? b[_global_id] : ((_global_id < 1) ? -1.5 : a[(_global_id - 1)]))");
+
+        let out = Output::Mat { mat: Matrix { id: VarId(2), access: Expression::make_global() } };
+        assert_eq!(get_inputs_args(vec![&inp], vec![&out]), vec![VarId(0), VarId(1), VarId(2)]);
+    }
+}
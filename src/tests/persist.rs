@@ -0,0 +1,50 @@
+// parameters changed by a program stay on the device between executions
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, nn::{self, optimizers::Optimizer}, tests::harness::assert_close_data, Tensor};
+
+    // one SGD step on sum(w * w) scales w by (1 - 2 * lr)
+    fn sgd_step (w: &Tensor, lr: f32) {
+        let mut opt = nn::optimizers::SGD(vec![w.clone()], lr);
+        let y = (w.clone() * w.clone()).unsqueeze(0).sum(1);
+
+        opt.zero_grad();
+        y.forward();
+        y.backward();
+        opt.step();
+    }
+
+    #[test]
+    fn persist_execute () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        let w = autodiff::tensor(vec![1.0, -2.0, 0.5], vec![3]);
+        sgd_step(&w, 0.1);
+        w.val().unwrap().keep();
+
+        for i in 1..4 {
+            autodiff::execute();
+
+            let scale = 0.8_f32.powi(i);
+            assert_close_data(&w.val().unwrap().get().data, &[scale, -2.0 * scale, 0.5 * scale], 1e-5);
+        }
+    }
+
+    #[test]
+    fn persist_compiled () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        let w = autodiff::tensor(vec![1.0, -2.0, 0.5], vec![3]);
+        autodiff::ir_for(0..2, |_| sgd_step(&w, 0.1));
+        w.val().unwrap().keep();
+
+        // the loop counter restarts on every run; only w is carried over
+        let mut program = autodiff::compile();
+        for i in 1..4 {
+            program.run();
+
+            let scale = 0.8_f32.powi(2 * i);
+            assert_close_data(&w.val().unwrap().get().data, &[scale, -2.0 * scale, 0.5 * scale], 1e-5);
+        }
+    }
+}
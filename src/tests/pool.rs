@@ -0,0 +1,106 @@
+// pooling against host computations
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, nn, PoolOp, SeqF, Tensor};
+
+    fn data (dim: &[usize], seed: f32) -> (Vec<f32>, Vec<usize>) {
+        let n: usize = dim.iter().product();
+        ((0..n).map(|i| ((i as f32 + seed) * 0.37).sin()).collect(), dim.to_vec())
+    }
+
+    fn eval (t: Tensor) -> (Vec<usize>, Vec<f32>) {
+        t.forward();
+        t.val().unwrap().keep();
+        autodiff::execute();
+
+        let v = t.val().unwrap().get().round(4);
+        (v.dim.clone(), v.data.to_vec())
+    }
+
+    fn round (v: Vec<f32>) -> Vec<f32> {
+        v.iter().map(|x| (x * 10000.0).round() / 10000.0).collect()
+    }
+
+    // [C, H, W] pooling; padded elements are -inf (max) or 0 (avg)
+    fn host_pool (x: &[f32], (c, h, w): (usize, usize, usize), op: PoolOp, (k, s, p, d): (usize, usize, usize, usize)) -> (Vec<usize>, Vec<f32>) {
+        let ho = (h + 2*p - d*(k-1) - 1) / s + 1;
+        let wo = (w + 2*p - d*(k-1) - 1) / s + 1;
+
+        let mut res = vec![];
+        for ch in 0..c { for i in 0..ho { for j in 0..wo {
+            let vals = (0..k*k).map(|t| {
+                let (y, z) = ((i*s + (t/k)*d) as i32 - p as i32, (j*s + (t%k)*d) as i32 - p as i32);
+                if y < 0 || z < 0 || y >= h as i32 || z >= w as i32 {
+                    if let PoolOp::Max = op { f32::NEG_INFINITY } else { 0.0 }
+                } else {
+                    x[(ch*h + y as usize)*w + z as usize]
+                }
+            }).collect::<Vec<f32>>();
+
+            res.push(match op {
+                PoolOp::Max => vals.iter().cloned().fold(f32::NEG_INFINITY, f32::max),
+                PoolOp::Avg => vals.iter().sum::<f32>() / (k*k) as f32
+            });
+        }}}
+        (vec![c, ho, wo], res)
+    }
+
+    fn check (op: PoolOp, dim: (usize, usize, usize), params: (usize, usize, usize, usize)) {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let (x, x_dim) = data(&[dim.0, dim.1, dim.2], 0.0);
+        let (k, s, p, d) = params;
+
+        let res = autodiff::tensor(x.clone(), x_dim).pool2d(op, k, s, p, d);
+        let (res_dim, expected) = host_pool(&x, dim, op, params);
+        assert_eq!(eval(res), (res_dim, round(expected)));
+    }
+
+    #[test]
+    fn max_pool () {
+        check(PoolOp::Max, (2, 4, 4), (2, 2, 0, 1));
+        check(PoolOp::Max, (1, 5, 6), (3, 2, 1, 1));
+        check(PoolOp::Max, (2, 7, 7), (2, 1, 0, 2));
+    }
+
+    #[test]
+    fn avg_pool () {
+        check(PoolOp::Avg, (2, 4, 4), (2, 2, 0, 1));
+        check(PoolOp::Avg, (1, 5, 6), (3, 2, 1, 1));
+    }
+
+    #[test]
+    fn adaptive_avg_pool () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = autodiff::tensor((0..20).map(|v| v as f32).collect(), vec![1, 4, 5]);
+
+        // rows: [0, 2), [2, 4); cols: [0, 2), [1, 4), [3, 5)
+        let expected = vec![3.0, 4.5, 6.0, 13.0, 14.5, 16.0];
+        assert_eq!(eval(x.adaptive_avg_pool2d((2, 3))), (vec![1, 2, 3], expected));
+
+        // global average
+        assert_eq!(eval(x.adaptive_avg_pool2d((1, 1))), (vec![1, 1, 1], vec![9.5]));
+    }
+
+    #[test]
+    fn pool_module () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let (x, x_dim) = data(&[2, 3, 6, 6], 0.0);
+        let x = autodiff::tensor(x, x_dim);
+
+        assert_eq!(nn::MaxPool2d(2, 2, 0).f(x.clone()).dim(), vec![2, 3, 3, 3]);
+        assert_eq!(nn::AvgPool2d(3, 1, 1).f(x.clone()).dim(), vec![2, 3, 6, 6]);
+        assert_eq!(nn::AdaptiveAvgPool2d((4, 4)).f(x).dim(), vec![2, 3, 4, 4]);
+    }
+
+    #[test]
+    fn pool_grad () {
+        let check = |f: &dyn Fn(&[Tensor]) -> Tensor, inputs: Vec<(Vec<f32>, Vec<usize>)>| {
+            autodiff::set_device(autodiff::devices::cpu::Reference::new());
+            autodiff::gradcheck(f, inputs, 1e-2, 1e-2).expect("Gradient check failed");
+        };
+
+        check(&|t| t[0].max_pool2d(2, 1, 1, 1).sin(), vec![data(&[1, 4, 4], 0.0)]);
+        check(&|t| t[0].avg_pool2d(2, 2, 0).sin(), vec![data(&[2, 4, 4], 0.0)]);
+        check(&|t| t[0].adaptive_avg_pool2d((2, 3)).sin(), vec![data(&[1, 3, 5], 0.0)]);
+    }
+}
@@ -0,0 +1,165 @@
+// random tensors generated on the device
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, graph::ops::random::RNG_MAX_OFFSET, nn::{self, optimizers::Optimizer}, tests::harness::check_interp, Tensor};
+
+    fn run (t: &Tensor) -> Vec<f32> {
+        t.val().unwrap().keep();
+        autodiff::execute();
+        t.val().unwrap().get().data.to_vec()
+    }
+
+    fn mean_var (v: &[f32]) -> (f32, f32) {
+        let mean = v.iter().sum::<f32>() / v.len() as f32;
+        (mean, v.iter().map(|x| (x - mean) * (x - mean)).sum::<f32>() / v.len() as f32)
+    }
+
+    #[test]
+    fn random_dists () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let u = autodiff::rand(vec![64, 64]);
+        let n = autodiff::Generator::global().randn(vec![64, 64]);
+        let b = autodiff::bernoulli(0.3, vec![64, 64]);
+        let i = autodiff::randint(-3, 5, vec![64, 64]);
+        for t in [&u, &n, &b, &i] { t.val().unwrap().keep(); }
+        autodiff::execute();
+
+        let u = u.val().unwrap().get().data.to_vec();
+        assert!(u.iter().all(|v| (0.0..1.0).contains(v)));
+        let (m, var) = mean_var(&u);
+        assert!((m - 0.5).abs() < 0.02 && (var - 1.0 / 12.0).abs() < 0.01, "uniform: mean {} var {}", m, var);
+
+        let (m, var) = mean_var(&n.val().unwrap().get().data);
+        assert!(m.abs() < 0.05 && (var - 1.0).abs() < 0.1, "normal: mean {} var {}", m, var);
+
+        let b = b.val().unwrap().get().data.to_vec();
+        assert!(b.iter().all(|&v| v == 0.0 || v == 1.0));
+        let (m, _) = mean_var(&b);
+        assert!((m - 0.3).abs() < 0.03, "bernoulli: mean {}", m);
+
+        let i = i.val().unwrap().get().data.to_vec();
+        assert!(i.iter().all(|&v| v.fract() == 0.0 && (-3.0..5.0).contains(&v)));
+        for k in -3..5 {
+            assert!(i.contains(&(k as f32)), "randint never drew {}", k);
+        }
+    }
+
+    #[test]
+    fn random_seed () {
+        let draw = |seed: u32| {
+            autodiff::set_device(autodiff::devices::cpu::Reference::new());
+            let g = autodiff::generator(seed);
+            (run(&g.randn(vec![32])), run(&g.randn(vec![32])))
+        };
+
+        let (a, b) = draw(7);
+        assert_eq!((a.clone(), b.clone()), draw(7), "Same seed must give the same draws");
+        assert_ne!(a, b, "Consecutive draws must differ");
+        assert_ne!(a, draw(8).0, "Different seeds must give different draws");
+    }
+
+    #[test]
+    fn random_loop () {
+        // the draws inside an ir_for advance the generator the same way as sequential draws
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let g = autodiff::generator(42);
+        let mut expected = g.rand(vec![16]);
+        for _ in 0..3 {
+            expected += g.rand(vec![16]);
+        }
+        expected.forward();
+        let expected = run(&expected);
+
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let g = autodiff::generator(42);
+        let mut y = autodiff::zeros(vec![16]);
+        autodiff::ir_for(0..4, |_| {
+            y += g.rand(vec![16]);
+            y.forward();
+        });
+        let y = run(&y);
+
+        for (a, b) in y.iter().zip(expected.iter()) {
+            assert!((a - b).abs() < 1e-5, "{:?} vs {:?}", y, expected);
+        }
+    }
+
+    #[test]
+    fn random_persist () {
+        // host initialized weights are carried over to the next run; device draws are redrawn every run
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let w = autodiff::randn(vec![3]);
+        let mut opt = nn::optimizers::SGD(vec![w.clone()], 0.1);
+        let y = (w.clone() * w.clone()).sum(0);
+        opt.zero_grad();
+        y.forward();
+        y.backward();
+        opt.step();
+
+        let n = autodiff::Generator::global().randn(vec![8]);
+        n.val().unwrap().keep();
+
+        let first = run(&w);
+        let first_n = n.val().unwrap().get().data.to_vec();
+        autodiff::execute();
+        let second = w.val().unwrap().get().data.to_vec();
+        assert_eq!(first.len(), second.len());
+        for (a, b) in first.iter().zip(second.iter()) {
+            assert!((a * 0.8 - b).abs() < 1e-5, "{:?} vs {:?}", first, second);
+        }
+        assert_ne!(first_n, n.val().unwrap().get().data.to_vec(), "Generator::randn must be redrawn every run");
+    }
+
+    #[test]
+    fn random_persist_linear () {
+        // same for the weights of the nn modules
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let lin = nn::Linear(8, 1, false);
+        let w = lin.w.clone();
+        let mut opt = nn::optimizers::SGD(vec![w.clone()], 0.1);
+        let y = (w.clone() * w.clone()).sum(0);
+        opt.zero_grad();
+        y.forward();
+        y.backward();
+        opt.step();
+
+        let first = run(&w);
+        autodiff::execute();
+        let second = w.val().unwrap().get().data.to_vec();
+        for (a, b) in first.iter().zip(second.iter()) {
+            assert!((a * 0.8 - b).abs() < 1e-5, "{:?} vs {:?}", first, second);
+        }
+    }
+
+    #[test]
+    fn random_offset_wrap () {
+        // the offset stops being exact at 2^24, so it wraps into the epoch (the second word of the key) instead
+        let draws = |offset: u32, epoch: u32| {
+            autodiff::set_device(autodiff::devices::cpu::Reference::new());
+            let g = autodiff::Generator::with_state(5, offset, epoch);
+            let a = g.rand(vec![16]);
+            let b = g.rand(vec![16]);
+            for t in [&a, &b] { t.val().unwrap().keep(); }
+            autodiff::execute();
+            (a.val().unwrap().get().data.to_vec(), b.val().unwrap().get().data.to_vec(), g.state.get().data.to_vec())
+        };
+
+        let max = RNG_MAX_OFFSET;
+        let (last, wrapped, state) = draws(max, 0);
+        assert_eq!(state, vec![5.0, 1.0, 1.0]);
+        assert_ne!(last, wrapped, "Draws must keep changing past 2^24");
+        assert_eq!(wrapped, draws(0, 1).0, "The offset continues from 0 with the next epoch");
+        assert_ne!(wrapped, draws(0, 0).0, "The next epoch must not repeat the first draws");
+    }
+
+    #[test]
+    fn random_interp () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let g = autodiff::generator(123);
+        let res = g.rand(vec![4, 8]) * 2.0
+            + g.randn(vec![4, 8]).exp()
+            + autodiff::bernoulli(0.7, vec![4, 8])
+            + g.randint(0, 10, vec![8, 4]).permute(&vec![1, 0]);
+        check_interp(&res);
+    }
+}
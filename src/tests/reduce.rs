@@ -0,0 +1,108 @@
+// max, min, prod, argmax, argmin along any dim
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, Tensor};
+
+    fn x () -> Tensor {
+        autodiff::tensor(vec![
+            0.5, -1.2, 0.8, 
+            1.5, -0.3, 0.9,
+
+            2.0, 0.1, -0.4,
+            -2.5, 0.7, 0.7
+        ], vec![2, 2, 3])
+    }
+
+    fn eval (t: Tensor) -> (Vec<usize>, Vec<f32>) {
+        t.forward();
+        t.val().unwrap().keep();
+        autodiff::execute();
+
+        let v = t.val().unwrap().get().round(4);
+        (v.dim.clone(), v.data.to_vec())
+    }
+
+    #[test]
+    fn max_min () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        assert_eq!(eval(x().max(2)), (vec![2, 2], vec![0.8, 1.5, 2.0, 0.7]));
+        assert_eq!(eval(x().max(0)), (vec![2, 3], vec![2.0, 0.1, 0.8, 1.5, 0.7, 0.9]));
+        assert_eq!(eval(x().min(1)), (vec![2, 3], vec![0.5, -1.2, 0.8, -2.5, 0.1, -0.4]));
+        assert_eq!(eval(x().min(-1)), (vec![2, 2], vec![-1.2, -0.3, -0.4, -2.5]));
+    }
+
+    #[test]
+    fn argmax_argmin () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        // ties (0.7, 0.7) go to the first index
+        assert_eq!(eval(x().argmax(2)), (vec![2, 2], vec![2.0, 0.0, 0.0, 1.0]));
+        assert_eq!(eval(x().argmax(0)), (vec![2, 3], vec![1.0, 1.0, 0.0, 0.0, 1.0, 0.0]));
+        assert_eq!(eval(x().argmin(1)), (vec![2, 3], vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn prod () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        assert_eq!(eval(x().prod(2)), (vec![2, 2], vec![-0.48, -0.405, -0.08, -1.225]));
+        assert_eq!(eval(x().prod(0)), (vec![2, 3], vec![1.0, -0.12, -0.32, -3.75, -0.21, 0.63]));
+    }
+
+    #[test]
+    fn reduce_grad () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+
+        // gradient only goes to the first max
+        let t = x();
+        let res = t.max(2).sum(1).sum(0);
+        res.forward();
+        res.backward();
+        autodiff::execute();
+
+        let g = t.grad().get();
+        assert_eq!(*g.data, vec![
+            0.0, 0.0, 1.0,
+            1.0, 0.0, 0.0,
+            1.0, 0.0, 0.0,
+            0.0, 1.0, 0.0
+        ]);
+
+        let inputs = || vec![(vec![0.5, -1.2, 0.8, 1.5, -0.3, 0.9], vec![2, 3])];
+        let check = |f: fn(&[Tensor]) -> Tensor| {
+            autodiff::set_device(autodiff::devices::cpu::Reference::new());
+            autodiff::gradcheck(f, inputs(), 1e-2, 1e-2).expect("Gradient check failed");
+        };
+
+        check(|t| t[0].max(1).sin());
+        check(|t| t[0].min(0).sin());
+        check(|t| t[0].prod(1).sin());
+        check(|t| t[0].prod(0) * t[0].max(0));
+
+        // with zeros: the gradient is the product of the other elements, not prod / x
+        let zeros = || vec![(vec![0.5, 0.0, 0.8, 0.0, -0.3, 0.0, 1.2, 0.9], vec![2, 4])];
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        autodiff::gradcheck(|t| t[0].prod(1), zeros(), 1e-2, 1e-2).expect("Gradient check failed");
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        autodiff::gradcheck(|t| t[0].prod(0).sin(), zeros(), 1e-2, 1e-2).expect("Gradient check failed");
+
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let t = autodiff::tensor(vec![0.5, 0.0, 0.8, 2.0], vec![1, 4]);
+        let res = t.prod(1).sum(0);
+        res.forward();
+        res.backward();
+        autodiff::execute();
+        assert_eq!(*t.grad().get().data, vec![0.0, 0.8, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn reduce_4d () {
+        // reducing the first of 4 dims permutes the remaining dims by a cycle (rather than a swap)
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let data: Vec<f32> = (0..24).map(|i| i as f32).collect();
+        let expected: Vec<f32> = (0..12).map(|i| data[i] + data[12 + i]).collect();
+
+        assert_eq!(eval(autodiff::tensor(data, vec![2, 2, 3, 2]).sum(0)), (vec![2, 3, 2], expected));
+    }
+}
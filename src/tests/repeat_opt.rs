@@ -0,0 +1,110 @@
+// loop invariant hoisting and deduplication across blocks (ir::opts::repeat_opt)
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, tests::harness::{check_same, count}, IRCmds, IRProcedure, Tensor};
+
+    fn while_block (proc: &IRProcedure) -> &IRProcedure {
+        proc.iter().find_map(|c| if let IRCmds::While { block, .. } = c { Some(block) } else { None }).unwrap()
+    }
+
+    fn x () -> Tensor {
+        autodiff::tensor((0..6).map(|v| v as f32 * 0.5 - 1.0).collect(), vec![2, 3])
+    }
+
+    #[test]
+    fn repeat_hoist_loop () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = x();
+        let mut y = autodiff::ones(vec![3, 2]);
+        let mut z = autodiff::ones(vec![3, 2]);
+        let mut w = autodiff::ones(vec![3, 2]);
+
+        autodiff::ir_for(0..3, |_| {
+            y += x.t().sin();
+            y.forward();
+            w += 0.5;
+            w.forward();
+            z += x.t().sin() * w.clone();
+            z.forward();
+        });
+
+        let proc = check_same(&[&y, &z, &w]);
+        let block = while_block(&proc);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::Sin { .. })), 1);
+        assert_eq!(count(block, |c| matches!(c, IRCmds::Sin { .. } | IRCmds::Permute { .. })), 0);
+
+        // the multiply reads w, which is written every iteration
+        assert_eq!(count(block, |c| matches!(c, IRCmds::ElwMultiply { .. })), 1);
+    }
+
+    #[test]
+    fn repeat_hoist_nested () {
+        // moves up one loop at a time until it's out of both
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = x();
+        let mut y = autodiff::ones(vec![2, 3]);
+
+        autodiff::ir_for(0..2, |_| {
+            autodiff::ir_for(0..3, |_| {
+                y += (x.clone() * 2.0).sin();
+                y.forward();
+            });
+        });
+
+        let proc = check_same(&[&y]);
+        assert_eq!(count(while_block(&proc), |c| matches!(c, IRCmds::Sin { .. })), 0);
+        assert!(proc.iter().any(|c| matches!(c, IRCmds::Sin { .. })));
+    }
+
+    #[test]
+    fn repeat_if_branches () {
+        // both branches reuse the sin computed before the if
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = x();
+        let s = x.sin();
+        s.forward();
+
+        let mut y = autodiff::ones(vec![2, 3]);
+        let mut y_two = autodiff::ones(vec![2, 3]);
+        let cond = autodiff::scalar(1.0);
+
+        autodiff::ir_if_else(|| cond, || {
+            y += x.sin();
+            y.forward();
+        }, || {
+            y_two += x.sin() * 2.0;
+            y_two.forward();
+        });
+
+        let proc = check_same(&[&s, &y, &y_two]);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::Sin { .. })), 1);
+    }
+
+    #[test]
+    fn repeat_kept_duplicate () {
+        // both are kept, so the second one can't be replaced by the first: the user reads it under its own id
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let x = x();
+        let a = x.sin();
+        let b = x.sin();
+
+        let proc = check_same(&[&a, &b]);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::Sin { .. })), 2);
+    }
+
+    #[test]
+    fn repeat_changed_between () {
+        // w is written between the two sins, so they aren't the same value
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let mut w = autodiff::ones(vec![2, 3]);
+        let a = w.sin();
+        a.forward();
+        w += 0.5;
+        w.forward();
+        let b = w.sin();
+        let res = a + b;
+
+        let proc = check_same(&[&res]);
+        assert_eq!(count(&proc, |c| matches!(c, IRCmds::Sin { .. })), 2);
+    }
+}
@@ -0,0 +1,111 @@
+// softmax, log_softmax and logsumexp against host computations
+#[cfg(test)]
+mod tests {
+    use crate::{autodiff, Tensor, Value};
+
+    const X: [f32; 6] = [0.5, -1.2, 0.8, 1.5, -0.3, 0.9];
+
+    fn eval (t: Tensor) -> (Vec<usize>, Vec<f32>) {
+        t.forward();
+        t.val().unwrap().keep();
+        autodiff::execute();
+
+        let v = t.val().unwrap().get();
+        (v.dim.clone(), v.data.to_vec())
+    }
+
+    fn assert_close (a: &[f32], b: &[f32]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < 1e-4, "{:?} vs {:?}", a, b);
+        }
+    }
+
+    fn constant (data: &[f32]) -> Tensor {
+        Value::new(data.to_vec(), vec![2, 3]).to_node()
+    }
+
+    // logsumexp of the rows (dim=1) or columns (dim=0) of X as [2, 3]
+    fn host_lse (data: &[f32], dim: usize) -> Vec<f32> {
+        let lines: Vec<Vec<f32>> = if dim == 1 { 
+            data.chunks(3).map(|r| r.to_vec()).collect() 
+        } else { 
+            (0..3).map(|c| vec![data[c], data[3 + c]]).collect() 
+        };
+
+        lines.iter().map(|l| {
+            let m = l.iter().cloned().fold(f32::MIN, f32::max);
+            m + l.iter().map(|v| (v - m).exp()).sum::<f32>().ln()
+        }).collect()
+    }
+
+    fn check<F: Fn(&[Tensor]) -> Tensor> (f: F) {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        autodiff::gradcheck(f, vec![(X.to_vec(), vec![2, 3])], 1e-2, 1e-2).expect("Gradient check failed");
+    }
+
+    #[test]
+    fn softmax () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let lse = host_lse(&X, 1);
+        let expected: Vec<f32> = X.iter().enumerate().map(|(i, v)| (v - lse[i / 3]).exp()).collect();
+
+        let (dim, data) = eval(constant(&X).softmax(-1));
+        assert_eq!(dim, vec![2, 3]);
+        assert_close(&data, &expected);
+
+        let lse = host_lse(&X, 0);
+        let expected: Vec<f32> = X.iter().enumerate().map(|(i, v)| (v - lse[i % 3]).exp()).collect();
+        assert_close(&eval(constant(&X).softmax(0)).1, &expected);
+
+        // 1-dim input
+        let (dim, data) = eval(Value::new(vec![1.0, 2.0, 3.0], vec![3]).to_node().softmax(0));
+        assert_eq!(dim, vec![3]);
+        let s = 1f32.exp() + 2f32.exp() + 3f32.exp();
+        assert_close(&data, &[1f32.exp() / s, 2f32.exp() / s, 3f32.exp() / s]);
+    }
+
+    #[test]
+    fn log_softmax_logsumexp () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let lse = host_lse(&X, 1);
+        let expected: Vec<f32> = X.iter().enumerate().map(|(i, v)| v - lse[i / 3]).collect();
+
+        assert_close(&eval(constant(&X).log_softmax(1)).1, &expected);
+
+        let (dim, data) = eval(constant(&X).logsumexp(1));
+        assert_eq!(dim, vec![2]);
+        assert_close(&data, &lse);
+
+        let (dim, data) = eval(constant(&X).logsumexp(0));
+        assert_eq!(dim, vec![3]);
+        assert_close(&data, &host_lse(&X, 0));
+    }
+
+    #[test]
+    fn large_logits () {
+        autodiff::set_device(autodiff::devices::cpu::Reference::new());
+        let big: Vec<f32> = X.iter().map(|v| v * 100.0 + 1000.0).collect();
+        let small: Vec<f32> = X.iter().map(|v| v * 100.0).collect();
+
+        let lse_small = host_lse(&small, 1);
+        let expected: Vec<f32> = small.iter().enumerate().map(|(i, v)| (v - lse_small[i / 3]).exp()).collect();
+        assert_close(&eval(constant(&big).softmax(1)).1, &expected);
+
+        let expected: Vec<f32> = lse_small.iter().map(|v| v + 1000.0).collect();
+        assert_close(&eval(constant(&big).logsumexp(1)).1, &expected);
+
+        let (_, data) = eval(constant(&big).log_softmax(1));
+        assert!(data.iter().all(|v| v.is_finite()), "{:?}", data);
+    }
+
+    #[test]
+    fn softmax_grad () {
+        check(|t| t[0].softmax(1).sin());
+        check(|t| t[0].softmax(0) * constant(&X));
+        check(|t| t[0].log_softmax(1).sin());
+        check(|t| t[0].log_softmax(0) * constant(&X));
+        check(|t| t[0].logsumexp(1).sin());
+        check(|t| t[0].logsumexp(0).sin());
+    }
+}
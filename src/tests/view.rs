@@ -1,11 +1,17 @@
 #[cfg(test)]
 mod tests {
-    use crate::{autodiff, devices::CLDeviceType};
+    use crate::{autodiff, devices::cpu::Reference, tests::harness::use_device};
 
     #[ignore]
     #[test]
-    fn view () {
-        autodiff::set_device(autodiff::devices::OpenCL::new(CLDeviceType::ALL));
+    fn view () { view_on(false) }
+
+    #[ignore = "needs an OpenCL device, run with --ignored"]
+    #[test]
+    fn view_opencl () { view_on(true) }
+
+    fn view_on (opencl: bool) {
+        use_device(opencl);
 
         let a = autodiff::tensor(vec![2.0, 1.0, 3.0, 4.0], vec![2,2]);
         let res = a.view(vec![1, 1, 2, -1]);
@@ -14,4 +20,56 @@ mod tests {
 
         autodiff::print_and_exec();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn permute_cycle () {
+        // a permutation that isn't its own inverse: dim i of the result is dim p[i] of the source
+        autodiff::set_device(Reference::new());
+        let a = autodiff::tensor((0..24).map(|v| v as f32).collect(), vec![2, 3, 4]);
+        let r = a.permute(&vec![1, 2, 0]).contigious();
+        let r_two = a.permute(&vec![2, 0, 1]) * 1.0;
+        for t in [&r, &r_two] { t.forward(); t.val().unwrap().keep(); }
+        autodiff::execute();
+
+        let mut expected = vec![];
+        let mut expected_two = vec![];
+        for i in 0..3 { for j in 0..4 { for k in 0..2 { expected.push((k * 12 + i * 4 + j) as f32); } } }
+        for i in 0..4 { for j in 0..2 { for k in 0..3 { expected_two.push((j * 12 + k * 4 + i) as f32); } } }
+
+        let (v, v_two) = (r.val().unwrap().get(), r_two.val().unwrap().get());
+        assert_eq!((v.dim.clone(), v.data.to_vec()), (vec![3, 4, 2], expected));
+        assert_eq!((v_two.dim.clone(), v_two.data.to_vec()), (vec![4, 2, 3], expected_two));
+    }
+
+    #[test]
+    fn index_middle_dim () {
+        // index removes the dim; the access into the source has to put it back at the same position
+        autodiff::set_device(Reference::new());
+        let a = autodiff::tensor((0..24).map(|v| v as f32).collect(), vec![2, 3, 4]);
+        let r = a.i(1, 1) * 1.0;
+        r.forward();
+        r.val().unwrap().keep();
+        autodiff::execute();
+
+        let v = r.val().unwrap().get();
+        assert_eq!((v.dim.clone(), v.data.to_vec()), (vec![2, 4], vec![4.0, 5.0, 6.0, 7.0, 16.0, 17.0, 18.0, 19.0]));
+    }
+
+    #[test]
+    fn concat_moved_source () {
+        // the first source is transposed; which source a thread reads is decided on the coords of the concat, not of the source
+        autodiff::set_device(Reference::new());
+        let a = autodiff::tensor((0..6).map(|v| v as f32).collect(), vec![2, 3]);
+        let b = autodiff::tensor((0..6).map(|v| v as f32 + 10.0).collect(), vec![3, 2]);
+        let r = autodiff::concat(vec![a.t(), b], 1) * 1.0;
+        r.forward();
+        r.val().unwrap().keep();
+        autodiff::execute();
+
+        let v = r.val().unwrap().get();
+        assert_eq!(
+            (v.dim.clone(), v.data.to_vec()),
+            (vec![3, 4], vec![0.0, 3.0, 10.0, 11.0, 1.0, 4.0, 12.0, 13.0, 2.0, 5.0, 14.0, 15.0])
+        );
+    }
+}